@@ -0,0 +1,36 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use partch::lattice::{DimensionBound, Lattice, LatticeDimension};
+use partch::ratio::Ratio;
+
+fn ratio_new(c: &mut Criterion) {
+    c.bench_function("ratio_new", |b| {
+        b.iter(|| {
+            for n in 1..2000 {
+                black_box(Ratio::new(black_box(n), black_box(720)));
+            }
+        })
+    });
+    c.bench_function("ratio_new_unit", |b| {
+        b.iter(|| {
+            for n in 1..2000 {
+                black_box(Ratio::new(black_box(n), black_box(1)));
+            }
+        })
+    });
+}
+
+fn lattice_points(c: &mut Criterion) {
+    let dimension = |numer, denom| LatticeDimension {
+        ratio: Ratio::new(numer, denom),
+        bounds: DimensionBound::Infinity,
+    };
+    let lattice = Lattice::new(vec![dimension(3, 2), dimension(5, 4), dimension(7, 4)]);
+    c.bench_function("lattice_points", |b| {
+        b.iter(|| black_box(lattice.points(black_box(3))))
+    });
+}
+
+criterion_group!(benches, ratio_new, lattice_points);
+criterion_main!(benches);
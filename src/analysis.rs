@@ -0,0 +1,222 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::report::{Report, Table};
+use crate::scala::{ScalaError, ScalaFile};
+use crate::scale::Propriety;
+
+const EDO_SEARCH: (i32, i32) = (5, 72);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaleSummary {
+    pub name: String,
+    pub description: String,
+    pub size: usize,
+    pub prime_limit: Option<i32>,
+    pub propriety: Propriety,
+    pub best_edo: Option<(i32, f64)>,
+    pub mean_step: Option<f64>,
+    pub step_variance: Option<f64>,
+}
+
+#[derive(Debug)]
+pub enum AnalysisError {
+    Io(PathBuf, io::Error),
+    Scala(PathBuf, ScalaError),
+}
+
+impl ScaleSummary {
+    pub fn new(name: &str, scl: &ScalaFile) -> Self {
+        let scale = &scl.scale;
+        let statistics = scale.statistics();
+        Self {
+            name: name.to_string(),
+            description: scl.description.clone(),
+            size: scale.len(),
+            prime_limit: scale.prime_limit(),
+            propriety: scale.propriety(),
+            best_edo: scale.best_edo(EDO_SEARCH.0, EDO_SEARCH.1),
+            mean_step: statistics.as_ref().map(|s| s.mean_step),
+            step_variance: statistics.as_ref().map(|s| s.step_variance),
+        }
+    }
+}
+
+pub fn analyze_dir(dir: &Path) -> io::Result<Vec<Result<ScaleSummary, AnalysisError>>> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e.eq_ignore_ascii_case("scl")))
+        .collect();
+    paths.sort();
+
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let input =
+                fs::read_to_string(&path).map_err(|e| AnalysisError::Io(path.clone(), e))?;
+            let scl =
+                ScalaFile::parse(&input).map_err(|e| AnalysisError::Scala(path.clone(), e))?;
+            let name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            Ok(ScaleSummary::new(&name, &scl))
+        })
+        .collect())
+}
+
+const CSV_HEADER: &str =
+    "name,description,size,prime_limit,propriety,best_edo,edo_error,mean_step,step_variance";
+
+pub fn to_csv(summaries: &[ScaleSummary]) -> String {
+    let mut out = format!("{CSV_HEADER}\n");
+    for s in summaries {
+        let fields = [
+            csv_field(&s.name),
+            csv_field(&s.description),
+            s.size.to_string(),
+            optional(s.prime_limit),
+            propriety_name(s.propriety).to_string(),
+            optional(s.best_edo.map(|(edo, _)| edo)),
+            optional(s.best_edo.map(|(_, e)| format!("{e:.3}"))),
+            optional(s.mean_step.map(|m| format!("{m:.3}"))),
+            optional(s.step_variance.map(|v| format!("{v:.3}"))),
+        ];
+        out.push_str(&format!("{}\n", fields.join(",")));
+    }
+    out
+}
+
+pub fn to_json(summaries: &[ScaleSummary]) -> String {
+    summaries.to_json()
+}
+
+impl Report for [ScaleSummary] {
+    fn tables(&self) -> Vec<Table> {
+        let mut table = Table::new(
+            "Scales",
+            &[
+                "name",
+                "description",
+                "size",
+                "prime_limit",
+                "propriety",
+                "best_edo",
+                "edo_error",
+                "mean_step",
+                "step_variance",
+            ],
+        );
+        for s in self {
+            table.push(vec![
+                s.name.as_str().into(),
+                s.description.as_str().into(),
+                s.size.into(),
+                s.prime_limit.into(),
+                propriety_name(s.propriety).into(),
+                s.best_edo.map(|(edo, _)| edo).into(),
+                s.best_edo.map(|(_, e)| e).into(),
+                s.mean_step.into(),
+                s.step_variance.into(),
+            ]);
+        }
+        vec![table]
+    }
+}
+
+pub fn propriety_name(propriety: Propriety) -> &'static str {
+    match propriety {
+        Propriety::StrictlyProper => "strictly proper",
+        Propriety::Proper => "proper",
+        Propriety::Improper => "improper",
+    }
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn csv_field(value: &str) -> String {
+    match value.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+        false => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::schema;
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("partch-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("pentatonic.scl"),
+            "Just, pentatonic\n5\n9/8\n5/4\n3/2\n5/3\n2/1\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("tempered.SCL"),
+            "12 \"tet\"\n3\n400.0\n700.0\n1200.0\n",
+        )
+        .unwrap();
+        fs::write(dir.join("broken.scl"), "broken\n3\n9/8\n").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+        dir
+    }
+
+    #[test]
+    fn analyze_directory_of_scales() {
+        let dir = fixture_dir("analyze");
+
+        let results = analyze_dir(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0], Err(AnalysisError::Scala(_, _))));
+        let pentatonic = results[1].as_ref().unwrap();
+        assert_eq!(pentatonic.name, "pentatonic");
+        assert_eq!(pentatonic.size, 5);
+        assert_eq!(pentatonic.prime_limit, Some(5));
+        assert_eq!(pentatonic.propriety, Propriety::StrictlyProper);
+        let tempered = results[2].as_ref().unwrap();
+        assert_eq!(tempered.prime_limit, None);
+        assert_eq!(tempered.best_edo.map(|(edo, _)| edo), Some(12));
+    }
+
+    #[test]
+    fn csv_and_json_summaries() {
+        let dir = fixture_dir("export");
+        let summaries: Vec<ScaleSummary> = analyze_dir(&dir)
+            .unwrap()
+            .into_iter()
+            .filter_map(Result::ok)
+            .collect();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let csv = to_csv(&summaries);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].starts_with("pentatonic,\"Just, pentatonic\",5,5,strictly proper,"));
+        assert!(lines[2].starts_with("tempered,\"12 \"\"tet\"\"\",3,,"));
+
+        let json = to_json(&summaries);
+        assert!(json.starts_with(
+            "{\"version\":1,\"tables\":[{\"title\":\"Scales\",\"rows\":[{\"name\":\"pentatonic\""
+        ));
+        assert!(json.contains("\"description\":\"12 \\\"tet\\\"\""));
+        assert!(json.contains("\"prime_limit\":null"));
+    }
+
+    #[test]
+    fn summary_schema() {
+        let summaries: &[ScaleSummary] = &[];
+
+        assert_eq!(
+            schema(summaries),
+            ["Scales: name, description, size, prime_limit, propriety, best_edo, edo_error, mean_step, step_variance"]
+        );
+    }
+}
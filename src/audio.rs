@@ -0,0 +1,300 @@
+use std::f64::consts::TAU;
+
+use crate::progression::Progression;
+use crate::spectrum::Spectrum;
+
+pub const SAMPLE_RATE: u32 = 44100;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Waveform {
+    Sine,
+    Triangle,
+    Saw,
+    Square,
+    Spectrum(Spectrum),
+}
+
+impl Waveform {
+    pub fn sample(&self, phase: f64, frequency: f64, sample_rate: u32) -> f64 {
+        let phase = phase.rem_euclid(1.);
+        match self {
+            Waveform::Sine => (TAU * phase).sin(),
+            Waveform::Triangle => 1. - 4. * (phase - 0.5).abs(),
+            Waveform::Saw => 2. * phase - 1.,
+            Waveform::Square => match phase < 0.5 {
+                true => 1.,
+                false => -1.,
+            },
+            Waveform::Spectrum(spectrum) => {
+                let nyquist = sample_rate as f64 / 2.;
+                let total: f64 = spectrum.partials.iter().map(|p| p.amplitude.abs()).sum();
+                let sum: f64 = spectrum
+                    .partials
+                    .iter()
+                    .filter(|p| p.ratio * frequency < nyquist)
+                    .map(|p| p.amplitude * (TAU * phase * p.ratio).sin())
+                    .sum();
+                match total {
+                    0. => 0.,
+                    t => sum / t,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Envelope {
+    pub attack: f64,
+    pub decay: f64,
+    pub sustain: f64,
+    pub release: f64,
+}
+
+impl Envelope {
+    pub fn new(attack: f64, decay: f64, sustain: f64, release: f64) -> Self {
+        Self {
+            attack,
+            decay,
+            sustain,
+            release,
+        }
+    }
+
+    pub fn gain(&self, time: f64, duration: f64) -> f64 {
+        match time - duration {
+            t if t < 0. => self.held(time),
+            t if t < self.release => self.held(duration) * (1. - t / self.release),
+            _ => 0.,
+        }
+    }
+
+    fn held(&self, time: f64) -> f64 {
+        match time {
+            t if t < self.attack => t / self.attack,
+            t if t < self.attack + self.decay => {
+                1. - (1. - self.sustain) * (t - self.attack) / self.decay
+            }
+            _ => self.sustain,
+        }
+    }
+}
+
+impl Default for Envelope {
+    fn default() -> Self {
+        Self::new(0.01, 0.1, 0.8, 0.1)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Voice {
+    pub frequency: f64,
+    pub start: f64,
+    pub duration: f64,
+    pub amplitude: f64,
+    pub envelope: Envelope,
+    pub waveform: Waveform,
+}
+
+impl Voice {
+    pub fn new(frequency: f64, start: f64, duration: f64) -> Self {
+        Self {
+            frequency,
+            start,
+            duration,
+            amplitude: 1.,
+            envelope: Envelope::default(),
+            waveform: Waveform::Sine,
+        }
+    }
+
+    pub fn end(&self) -> f64 {
+        self.start + self.duration + self.envelope.release
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Renderer {
+    pub sample_rate: u32,
+    pub voices: Vec<Voice>,
+}
+
+impl Renderer {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            voices: vec![],
+        }
+    }
+
+    pub fn push(&mut self, voice: Voice) {
+        self.voices.push(voice);
+    }
+
+    pub fn push_progression(
+        &mut self,
+        progression: &Progression,
+        reference: impl Into<f64>,
+        seconds_per_beat: f64,
+        envelope: Envelope,
+        waveform: &Waveform,
+    ) {
+        let reference = reference.into();
+        let mut beat = 0.;
+        for timed in &progression.chords {
+            let amplitude = 1. / timed.chord.len().max(1) as f64;
+            for pitch in &timed.chord.pitches {
+                self.push(Voice {
+                    frequency: pitch.frequency(reference),
+                    start: beat * seconds_per_beat,
+                    duration: timed.duration * seconds_per_beat,
+                    amplitude,
+                    envelope,
+                    waveform: waveform.clone(),
+                });
+            }
+            beat += timed.duration;
+        }
+    }
+
+    pub fn duration(&self) -> f64 {
+        self.voices.iter().map(|v| v.end()).fold(0., f64::max)
+    }
+
+    pub fn render(&self) -> Vec<f64> {
+        let rate = self.sample_rate as f64;
+        let mut samples = vec![0.; (self.duration() * rate).ceil() as usize];
+        for voice in &self.voices {
+            let first = (voice.start * rate).round() as usize;
+            let last = ((voice.end() * rate).ceil() as usize).min(samples.len());
+            let increment = voice.frequency / rate;
+            for (n, sample) in samples[first.min(last)..last].iter_mut().enumerate() {
+                let time = n as f64 / rate;
+                let phase = (n as f64 * increment).fract();
+                *sample += voice.amplitude
+                    * voice.envelope.gain(time, voice.duration)
+                    * voice
+                        .waveform
+                        .sample(phase, voice.frequency, self.sample_rate);
+            }
+        }
+        let peak = samples.iter().fold(0_f64, |p, s| p.max(s.abs()));
+        if peak > 1. {
+            samples.iter_mut().for_each(|s| *s /= peak);
+        }
+        samples
+    }
+
+    pub fn to_wav(&self) -> Vec<u8> {
+        write_wav(&self.render(), self.sample_rate)
+    }
+}
+
+pub fn write_wav(samples: &[f64], sample_rate: u32) -> Vec<u8> {
+    let data: Vec<u8> = samples
+        .iter()
+        .flat_map(|s| ((s.clamp(-1., 1.) * i16::MAX as f64).round() as i16).to_le_bytes())
+        .collect();
+
+    let mut bytes = b"RIFF".to_vec();
+    bytes.extend((36 + data.len() as u32).to_le_bytes());
+    bytes.extend(b"WAVEfmt ");
+    bytes.extend(16_u32.to_le_bytes());
+    bytes.extend(1_u16.to_le_bytes());
+    bytes.extend(1_u16.to_le_bytes());
+    bytes.extend(sample_rate.to_le_bytes());
+    bytes.extend((sample_rate * 2).to_le_bytes());
+    bytes.extend(2_u16.to_le_bytes());
+    bytes.extend(16_u16.to_le_bytes());
+    bytes.extend(b"data");
+    bytes.extend((data.len() as u32).to_le_bytes());
+    bytes.extend(data);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chord::Chord;
+    use crate::ratio::Ratio;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn waveform_samples() {
+        assert!(approx(Waveform::Sine.sample(0.25, 440., SAMPLE_RATE), 1.));
+        assert!(approx(
+            Waveform::Triangle.sample(0.5, 440., SAMPLE_RATE),
+            1.
+        ));
+        assert!(approx(Waveform::Saw.sample(0.75, 440., SAMPLE_RATE), 0.5));
+        assert!(approx(
+            Waveform::Square.sample(0.75, 440., SAMPLE_RATE),
+            -1.
+        ));
+    }
+
+    #[test]
+    fn spectrum_waveform_drops_partials_above_nyquist() {
+        let waveform = Waveform::Spectrum(Spectrum::harmonic(3));
+
+        let low = waveform.sample(0.1, 100., SAMPLE_RATE);
+        let high = waveform.sample(0.1, 15000., SAMPLE_RATE);
+
+        assert!(!approx(low, high));
+        assert!(approx(high, (TAU * 0.1).sin() / (1. + 0.5 + 1. / 3.)));
+    }
+
+    #[test]
+    fn envelope_shape() {
+        let envelope = Envelope::new(0.1, 0.1, 0.5, 0.2);
+
+        assert!(approx(envelope.gain(0.05, 1.), 0.5));
+        assert!(approx(envelope.gain(0.15, 1.), 0.75));
+        assert!(approx(envelope.gain(0.5, 1.), 0.5));
+        assert!(approx(envelope.gain(1.1, 1.), 0.25));
+        assert!(approx(envelope.gain(1.3, 1.), 0.));
+    }
+
+    #[test]
+    fn render_overlapping_voices() {
+        let mut renderer = Renderer::new(1000);
+        renderer.push(Voice::new(100., 0., 1.));
+        renderer.push(Voice::new(150., 0.5, 1.));
+
+        let samples = renderer.render();
+
+        assert_eq!(samples.len(), 1600);
+        assert!(samples.iter().all(|s| s.abs() <= 1.));
+        assert!(approx(samples[0], 0.));
+    }
+
+    #[test]
+    fn render_progression_to_wav() {
+        let mut progression = Progression::new();
+        progression.push(
+            Chord::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]),
+            1.,
+        );
+        progression.push(Chord::new(vec![Ratio::new(4, 3), Ratio::new(5, 3)]), 1.);
+        let mut renderer = Renderer::new(8000);
+        renderer.push_progression(
+            &progression,
+            220.,
+            0.25,
+            Envelope::default(),
+            &Waveform::Saw,
+        );
+
+        assert_eq!(renderer.voices.len(), 5);
+        assert!(approx(renderer.voices[4].start, 0.25));
+        assert!(approx(renderer.duration(), 0.6));
+
+        let wav = renderer.to_wav();
+        assert_eq!(&wav[..4], b"RIFF");
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(wav.len(), 44 + 2 * 4800);
+    }
+}
@@ -0,0 +1,245 @@
+use std::fmt;
+
+use crate::audio::Renderer;
+use crate::scala::ScalaFile;
+use crate::tuning_table::{TuningTable, MIDI_NOTES, MIDI_NOTE_ZERO};
+
+pub const MTS_MAX_FREQUENCY: f64 = 13289.656616;
+pub const AUDIBLE_MIN_FREQUENCY: f64 = 20.;
+pub const SCL_MAX_DEGREES: usize = MIDI_NOTES;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExportWarning {
+    InvalidFrequency { note: usize, frequency: f64 },
+    BelowMidiRange { note: usize, frequency: f64 },
+    AboveMidiRange { note: usize, frequency: f64 },
+    NotMonotonic { note: usize },
+    WrongTableSize { expected: usize, found: usize },
+    TooManyDegrees { count: usize, limit: usize },
+    EmptyScale,
+    InvalidVoiceFrequency { voice: usize, frequency: f64 },
+    AboveNyquist { voice: usize, frequency: f64 },
+    Inaudible { voice: usize, frequency: f64 },
+}
+
+impl fmt::Display for ExportWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExportWarning::InvalidFrequency { note, frequency } => {
+                write!(f, "note {note} has invalid frequency {frequency}")
+            }
+            ExportWarning::BelowMidiRange { note, frequency } => {
+                write!(
+                    f,
+                    "note {note} at {frequency:.3} Hz is below the MIDI tuning range"
+                )
+            }
+            ExportWarning::AboveMidiRange { note, frequency } => {
+                write!(
+                    f,
+                    "note {note} at {frequency:.3} Hz is above the MIDI tuning range"
+                )
+            }
+            ExportWarning::NotMonotonic { note } => {
+                write!(f, "note {note} is not higher than the note below it")
+            }
+            ExportWarning::WrongTableSize { expected, found } => {
+                write!(f, "expected {expected} table entries, found {found}")
+            }
+            ExportWarning::TooManyDegrees { count, limit } => {
+                write!(f, "scale has {count} degrees, more than {limit}")
+            }
+            ExportWarning::EmptyScale => write!(f, "scale has no degrees"),
+            ExportWarning::InvalidVoiceFrequency { voice, frequency } => {
+                write!(f, "voice {voice} has invalid frequency {frequency}")
+            }
+            ExportWarning::AboveNyquist { voice, frequency } => {
+                write!(
+                    f,
+                    "voice {voice} at {frequency:.3} Hz is above the Nyquist frequency"
+                )
+            }
+            ExportWarning::Inaudible { voice, frequency } => {
+                write!(
+                    f,
+                    "voice {voice} at {frequency:.3} Hz is below the audible range"
+                )
+            }
+        }
+    }
+}
+
+pub fn audit_tuning_table(table: &TuningTable) -> Vec<ExportWarning> {
+    let mut warnings = vec![];
+    if table.frequencies.len() != MIDI_NOTES {
+        warnings.push(ExportWarning::WrongTableSize {
+            expected: MIDI_NOTES,
+            found: table.frequencies.len(),
+        });
+    }
+    for (note, &frequency) in table.frequencies.iter().enumerate() {
+        match frequency {
+            f if !f.is_finite() || f <= 0. => {
+                warnings.push(ExportWarning::InvalidFrequency { note, frequency });
+                continue;
+            }
+            f if f < MIDI_NOTE_ZERO - 1e-9 => {
+                warnings.push(ExportWarning::BelowMidiRange { note, frequency })
+            }
+            f if f > MTS_MAX_FREQUENCY => {
+                warnings.push(ExportWarning::AboveMidiRange { note, frequency })
+            }
+            _ => {}
+        }
+        if note > 0 && frequency <= table.frequencies[note - 1] {
+            warnings.push(ExportWarning::NotMonotonic { note });
+        }
+    }
+    warnings
+}
+
+pub fn audit_scala(scl: &ScalaFile) -> Vec<ExportWarning> {
+    match scl.scale.len() {
+        0 => vec![ExportWarning::EmptyScale],
+        n if n > SCL_MAX_DEGREES => vec![ExportWarning::TooManyDegrees {
+            count: n,
+            limit: SCL_MAX_DEGREES,
+        }],
+        _ => vec![],
+    }
+}
+
+pub fn audit_renderer(renderer: &Renderer) -> Vec<ExportWarning> {
+    let nyquist = renderer.sample_rate as f64 / 2.;
+    let mut warnings = vec![];
+    for (voice, v) in renderer.voices.iter().enumerate() {
+        let frequency = v.frequency;
+        match frequency {
+            f if !f.is_finite() || f <= 0. => {
+                warnings.push(ExportWarning::InvalidVoiceFrequency { voice, frequency })
+            }
+            f if f >= nyquist => warnings.push(ExportWarning::AboveNyquist { voice, frequency }),
+            f if f < AUDIBLE_MIN_FREQUENCY => {
+                warnings.push(ExportWarning::Inaudible { voice, frequency })
+            }
+            _ => {}
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::Voice;
+    use crate::scale::Scale;
+    use crate::standard::PitchStandard;
+
+    #[test]
+    fn equal_temperament_table_is_clean() {
+        let table = TuningTable::equal_temperament(PitchStandard::A440);
+
+        assert_eq!(audit_tuning_table(&table), vec![]);
+    }
+
+    #[test]
+    fn table_range_and_monotonicity() {
+        let mut table = TuningTable::equal_temperament(PitchStandard::A440);
+        table.frequencies[0] = 4.;
+        table.frequencies[61] = table.frequencies[60];
+        table.frequencies[100] = 0.;
+        table.frequencies[127] = 20000.;
+
+        assert_eq!(
+            audit_tuning_table(&table),
+            vec![
+                ExportWarning::BelowMidiRange {
+                    note: 0,
+                    frequency: 4.
+                },
+                ExportWarning::NotMonotonic { note: 61 },
+                ExportWarning::InvalidFrequency {
+                    note: 100,
+                    frequency: 0.
+                },
+                ExportWarning::AboveMidiRange {
+                    note: 127,
+                    frequency: 20000.
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn table_size() {
+        let table = TuningTable::new(vec![440.; 12]);
+        let warnings = audit_tuning_table(&table);
+
+        assert_eq!(
+            warnings[0],
+            ExportWarning::WrongTableSize {
+                expected: 128,
+                found: 12
+            }
+        );
+        assert_eq!(warnings.len(), 12);
+    }
+
+    #[test]
+    fn scala_degree_limits() {
+        let edo = |n: usize| {
+            ScalaFile::new(
+                "",
+                Scale::new(
+                    (1..=n)
+                        .map(|k| 1200. * k as f64 / n as f64)
+                        .collect::<Vec<_>>(),
+                ),
+            )
+        };
+
+        assert_eq!(audit_scala(&edo(12)), vec![]);
+        assert_eq!(
+            audit_scala(&edo(200)),
+            vec![ExportWarning::TooManyDegrees {
+                count: 200,
+                limit: SCL_MAX_DEGREES
+            }]
+        );
+    }
+
+    #[test]
+    fn renderer_frequencies() {
+        let mut renderer = Renderer::new(8000);
+        renderer.push(Voice::new(440., 0., 1.));
+        renderer.push(Voice::new(5000., 0., 1.));
+        renderer.push(Voice::new(10., 0., 1.));
+        renderer.push(Voice::new(-1., 0., 1.));
+
+        assert_eq!(
+            audit_renderer(&renderer),
+            vec![
+                ExportWarning::AboveNyquist {
+                    voice: 1,
+                    frequency: 5000.
+                },
+                ExportWarning::Inaudible {
+                    voice: 2,
+                    frequency: 10.
+                },
+                ExportWarning::InvalidVoiceFrequency {
+                    voice: 3,
+                    frequency: -1.
+                },
+            ]
+        );
+        assert_eq!(
+            audit_renderer(&renderer)[0].to_string(),
+            "voice 1 at 5000.000 Hz is above the Nyquist frequency"
+        );
+        assert_eq!(
+            audit_renderer(&renderer)[2].to_string(),
+            "voice 3 has invalid frequency -1"
+        );
+    }
+}
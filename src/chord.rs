@@ -0,0 +1,336 @@
+use crate::pitch::{Pitch, PitchClass};
+use crate::ratio::{gcd, Ratio, Style};
+use crate::spectrum::Spectrum;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chord {
+    pub pitches: Vec<Pitch>,
+}
+
+impl Chord {
+    pub fn new<T: Into<Pitch>>(pitches: Vec<T>) -> Self {
+        let mut pitches: Vec<Pitch> = pitches.into_iter().map(|p| p.into()).collect();
+        pitches.sort();
+        pitches.dedup();
+        Self { pitches }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pitches.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pitches.is_empty()
+    }
+
+    pub fn ratios(&self) -> Vec<Ratio> {
+        self.pitches.iter().map(|p| p.ratio()).collect()
+    }
+
+    pub fn classes(&self) -> Vec<PitchClass> {
+        let mut classes: Vec<PitchClass> = self.pitches.iter().map(|p| p.class).collect();
+        classes.sort();
+        classes.dedup();
+        classes
+    }
+
+    pub fn negative(&self, axis: Ratio) -> Self {
+        Self::new(
+            self.ratios()
+                .into_iter()
+                .map(|r| Pitch::new(axis / r))
+                .collect(),
+        )
+    }
+
+    pub fn fundamental(&self) -> Ratio {
+        let ratios = self.ratios();
+        let numer = ratios.iter().map(|r| r.numer).reduce(gcd).unwrap_or(1);
+        let denom = ratios.iter().fold(1, |l, r| l / gcd(l, r.denom) * r.denom);
+        Ratio::new(numer, denom)
+    }
+
+    pub fn harmonics(&self) -> Vec<i32> {
+        let fundamental = self.fundamental();
+        self.ratios()
+            .iter()
+            .map(|r| (*r / fundamental).numer)
+            .collect()
+    }
+
+    pub fn format(&self, style: Style) -> String {
+        match style {
+            Style::Colon => self
+                .harmonics()
+                .iter()
+                .map(|h| h.to_string())
+                .collect::<Vec<String>>()
+                .join(":"),
+            style => self
+                .ratios()
+                .iter()
+                .map(|r| r.format(style))
+                .collect::<Vec<String>>()
+                .join(" "),
+        }
+    }
+
+    pub fn periodicity(&self) -> i32 {
+        self.harmonics().first().copied().unwrap_or(1)
+    }
+
+    pub fn repetition_rate(&self, reference: impl Into<f64>) -> f64 {
+        let fundamental = self.fundamental();
+        reference.into() * f64::from(fundamental)
+    }
+
+    pub fn period(&self, reference: impl Into<f64>) -> f64 {
+        1. / self.repetition_rate(reference)
+    }
+
+    pub fn optimize_spacing(
+        &self,
+        spectrum: &Spectrum,
+        reference: impl Into<f64>,
+        low: Pitch,
+        high: Pitch,
+    ) -> Option<Self> {
+        let reference: f64 = reference.into();
+        let options: Vec<Vec<Pitch>> = self
+            .classes()
+            .iter()
+            .map(|c| {
+                ((low.equave - 1)..=high.equave)
+                    .map(|e| c.at_equave(e))
+                    .filter(|p| *p >= low && *p <= high)
+                    .collect()
+            })
+            .collect();
+        if options.iter().any(|o| o.is_empty()) {
+            return None;
+        }
+
+        let roughness = |choice: &[usize]| {
+            let frequencies: Vec<f64> = choice
+                .iter()
+                .enumerate()
+                .map(|(i, &k)| options[i][k].frequency(reference))
+                .collect();
+            spectrum.roughness(&frequencies)
+        };
+
+        let mut choice: Vec<usize> = options.iter().map(|_| 0).collect();
+        let mut best = roughness(&choice);
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for voice in 0..options.len() {
+                for k in 0..options[voice].len() {
+                    let mut candidate = choice.clone();
+                    candidate[voice] = k;
+                    let score = roughness(&candidate);
+                    if score < best {
+                        best = score;
+                        choice = candidate;
+                        improved = true;
+                    }
+                }
+            }
+        }
+
+        Some(Self::new(
+            choice
+                .iter()
+                .enumerate()
+                .map(|(i, &k)| options[i][k])
+                .collect(),
+        ))
+    }
+}
+
+pub fn estimate_fundamental(cents: &[f64], tolerance: f64, max_harmonic: u32) -> Option<f64> {
+    let lowest = cents.iter().copied().reduce(f64::min)?;
+    (1..=max_harmonic).find_map(|n| {
+        let fundamental = lowest - 1200. * (n as f64).log2();
+        cents
+            .iter()
+            .all(|c| {
+                let harmonic = 2_f64.powf((c - fundamental) / 1200.);
+                let nearest = harmonic.round().max(1.);
+                (1200. * (harmonic / nearest).log2()).abs() <= tolerance
+                    && nearest <= max_harmonic as f64
+            })
+            .then_some(fundamental)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negative_harmony() {
+        let major = Chord::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+        let minor = major.negative(Ratio::new(3, 2));
+
+        assert_eq!(
+            minor.ratios(),
+            vec![Ratio::new(1, 1), Ratio::new(6, 5), Ratio::new(3, 2)]
+        );
+        let dominant = Chord::new(vec![Ratio::new(3, 2), Ratio::new(15, 8), Ratio::new(9, 4)]);
+        assert_eq!(
+            dominant.negative(Ratio::new(3, 2)).ratios(),
+            vec![Ratio::new(2, 3), Ratio::new(4, 5), Ratio::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn chord_keeps_octave_placement() {
+        let chord = Chord::new(vec![Ratio::new(1, 1), Ratio::new(5, 2), Ratio::new(3, 2)]);
+
+        assert_eq!(
+            chord.ratios(),
+            vec![Ratio::new(1, 1), Ratio::new(3, 2), Ratio::new(5, 2)]
+        );
+    }
+
+    #[test]
+    fn chord_classes_collapse_octaves() {
+        let chord = Chord::new(vec![Ratio::new(1, 1), Ratio::new(2, 1), Ratio::new(3, 1)]);
+
+        assert_eq!(chord.len(), 3);
+        assert_eq!(
+            chord.classes(),
+            vec![
+                PitchClass::new(Ratio::new(1, 1)),
+                PitchClass::new(Ratio::new(3, 2))
+            ]
+        );
+    }
+
+    #[test]
+    fn fundamental_of_major_triad() {
+        let chord = Chord::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+
+        assert_eq!(chord.fundamental(), Ratio::new(1, 4));
+        assert_eq!(chord.harmonics(), vec![4, 5, 6]);
+    }
+
+    #[test]
+    fn fundamental_of_utonal_triad() {
+        let chord = Chord::new(vec![Ratio::new(1, 1), Ratio::new(6, 5), Ratio::new(3, 2)]);
+
+        assert_eq!(chord.fundamental(), Ratio::new(1, 10));
+        assert_eq!(chord.harmonics(), vec![10, 12, 15]);
+    }
+
+    #[test]
+    fn fundamental_above_the_root() {
+        let chord = Chord::new(vec![Ratio::new(2, 1), Ratio::new(3, 1)]);
+
+        assert_eq!(chord.fundamental(), Ratio::new(1, 1));
+    }
+
+    #[test]
+    fn format_chord() {
+        let chord = Chord::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+
+        assert_eq!(chord.format(Style::Colon), "4:5:6");
+        assert_eq!(chord.format(Style::Fraction), "1/1 5/4 3/2");
+        assert_eq!(chord.format(Style::Monzo), "[⟩ [-2 0 1⟩ [-1 1⟩");
+    }
+
+    #[test]
+    fn periodicity_of_triads() {
+        let major = Chord::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+        let minor = Chord::new(vec![Ratio::new(1, 1), Ratio::new(6, 5), Ratio::new(3, 2)]);
+
+        assert_eq!(major.periodicity(), 4);
+        assert_eq!(minor.periodicity(), 10);
+    }
+
+    #[test]
+    fn periodicity_is_relative_to_lowest_pitch() {
+        let chord = Chord::new(vec![Ratio::new(5, 4), Ratio::new(3, 2)]);
+
+        assert_eq!(chord.fundamental(), Ratio::new(1, 4));
+        assert_eq!(chord.periodicity(), 5);
+    }
+
+    #[test]
+    fn repetition_rate_and_period() {
+        let chord = Chord::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+
+        assert_eq!(chord.repetition_rate(440.), 110.);
+        assert_eq!(chord.period(440.), 1. / 110.);
+    }
+
+    #[test]
+    fn estimate_fundamental_of_tempered_triad() {
+        let fundamental = estimate_fundamental(&[0., 400., 700.], 15., 16).unwrap();
+
+        assert!((fundamental - -2400.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn estimate_fundamental_respects_tolerance() {
+        assert_eq!(estimate_fundamental(&[0., 400., 700.], 5., 6), None);
+        assert_eq!(estimate_fundamental(&[], 5., 6), None);
+    }
+
+    #[test]
+    fn optimize_spacing_stays_in_range() {
+        let chord = Chord::new(vec![
+            Ratio::new(1, 1),
+            Ratio::new(9, 8),
+            Ratio::new(5, 4),
+            Ratio::new(3, 2),
+        ]);
+        let low = Pitch::new(Ratio::new(1, 2));
+        let high = Pitch::new(Ratio::new(4, 1));
+
+        let spaced = chord
+            .optimize_spacing(&Spectrum::harmonic(6), 261.63, low, high)
+            .unwrap();
+
+        assert_eq!(spaced.classes(), chord.classes());
+        assert!(spaced.pitches.iter().all(|p| *p >= low && *p <= high));
+    }
+
+    #[test]
+    fn optimize_spacing_reduces_roughness() {
+        let chord = Chord::new(vec![Ratio::new(1, 1), Ratio::new(16, 15), Ratio::new(9, 8)]);
+        let spectrum = Spectrum::harmonic(4);
+        let roughness = |c: &Chord| {
+            spectrum.roughness(
+                &c.pitches
+                    .iter()
+                    .map(|p| p.frequency(220.))
+                    .collect::<Vec<f64>>(),
+            )
+        };
+
+        let spaced = chord
+            .optimize_spacing(
+                &spectrum,
+                220.,
+                Pitch::new(Ratio::new(1, 1)),
+                Pitch::new(Ratio::new(8, 1)),
+            )
+            .unwrap();
+
+        assert!(roughness(&spaced) < roughness(&chord));
+    }
+
+    #[test]
+    fn optimize_spacing_with_empty_range() {
+        let chord = Chord::new(vec![Ratio::new(1, 1), Ratio::new(3, 2)]);
+        let low = Pitch::new(Ratio::new(9, 8));
+        let high = Pitch::new(Ratio::new(5, 4));
+
+        assert_eq!(
+            chord.optimize_spacing(&Spectrum::sine(), 440., low, high),
+            None
+        );
+    }
+}
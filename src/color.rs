@@ -0,0 +1,134 @@
+use crate::ratio::Ratio;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    pub fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    pub fn mix(&self, other: &Color, amount: f64) -> Self {
+        let t = amount.clamp(0., 1.);
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Self::new(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+        )
+    }
+}
+
+pub trait ColorScheme {
+    fn color(&self, ratio: &Ratio) -> Color;
+}
+
+const PRIME_PALETTE: [(i32, Color); 8] = [
+    (2, Color::new(0xee, 0xee, 0xee)),
+    (3, Color::new(0x4c, 0x72, 0xb0)),
+    (5, Color::new(0xdd, 0x84, 0x52)),
+    (7, Color::new(0x55, 0xa8, 0x68)),
+    (11, Color::new(0xc4, 0x4e, 0x52)),
+    (13, Color::new(0x81, 0x72, 0xb3)),
+    (17, Color::new(0x93, 0x78, 0x60)),
+    (19, Color::new(0xda, 0x8b, 0xc3)),
+];
+const OTHER_PRIMES: Color = Color::new(0x8c, 0x8c, 0x8c);
+
+const NEAR: Color = Color::new(0x2c, 0xa0, 0x2c);
+const FAR: Color = Color::new(0xd6, 0x27, 0x28);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ByPrimeLimit;
+
+impl ColorScheme for ByPrimeLimit {
+    fn color(&self, ratio: &Ratio) -> Color {
+        let limit = ratio.prime_limit();
+        PRIME_PALETTE
+            .iter()
+            .find(|(p, _)| *p >= limit)
+            .map(|(_, c)| *c)
+            .unwrap_or(OTHER_PRIMES)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ByOddLimit {
+    pub max: i32,
+}
+
+impl ColorScheme for ByOddLimit {
+    fn color(&self, ratio: &Ratio) -> Color {
+        let scale = (ratio.odd_limit() as f64).ln() / (self.max.max(3) as f64).ln();
+        NEAR.mix(&FAR, scale)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ByEdoDeviation {
+    pub edo: i32,
+}
+
+impl Default for ByEdoDeviation {
+    fn default() -> Self {
+        Self { edo: 12 }
+    }
+}
+
+impl ColorScheme for ByEdoDeviation {
+    fn color(&self, ratio: &Ratio) -> Color {
+        let step = 1200. / self.edo as f64;
+        let cents = ratio.cents();
+        let deviation = (cents - (cents / step).round() * step).abs();
+        NEAR.mix(&FAR, deviation / (step / 2.))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_and_mix() {
+        let black = Color::new(0, 0, 0);
+        let white = Color::new(255, 255, 255);
+
+        assert_eq!(white.hex(), "#ffffff");
+        assert_eq!(black.mix(&white, 0.5).hex(), "#808080");
+        assert_eq!(black.mix(&white, 2.), white);
+    }
+
+    #[test]
+    fn prime_limit_colors() {
+        let scheme = ByPrimeLimit;
+
+        assert_eq!(scheme.color(&Ratio::new(9, 8)), PRIME_PALETTE[1].1);
+        assert_eq!(scheme.color(&Ratio::new(5, 4)), PRIME_PALETTE[2].1);
+        assert_eq!(scheme.color(&Ratio::new(23, 16)), OTHER_PRIMES);
+    }
+
+    #[test]
+    fn odd_limit_gradient() {
+        let scheme = ByOddLimit { max: 15 };
+
+        assert_eq!(scheme.color(&Ratio::new(1, 1)), NEAR);
+        assert_eq!(scheme.color(&Ratio::new(15, 8)), FAR);
+    }
+
+    #[test]
+    fn edo_deviation_gradient() {
+        let scheme = ByEdoDeviation::default();
+
+        assert_eq!(scheme.color(&Ratio::new(2, 1)), NEAR);
+        assert_ne!(scheme.color(&Ratio::new(7, 4)), NEAR);
+        assert_eq!(scheme.color(&Ratio::new(3, 2)), NEAR.mix(&FAR, 1.955 / 50.));
+    }
+}
@@ -0,0 +1,219 @@
+use crate::degree::Degree;
+use crate::report::{Report, Table};
+use crate::scale::Scale;
+use crate::tolerance::Tolerance;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DegreeMatch {
+    pub a: usize,
+    pub b: usize,
+    pub a_degree: Degree,
+    pub b_degree: Degree,
+    pub difference: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaleComparison {
+    pub tolerance: Tolerance,
+    pub matches: Vec<DegreeMatch>,
+    pub unmatched_a: Vec<(usize, Degree)>,
+    pub unmatched_b: Vec<(usize, Degree)>,
+}
+
+impl ScaleComparison {
+    pub fn is_identical(&self) -> bool {
+        self.unmatched_a.is_empty()
+            && self.unmatched_b.is_empty()
+            && self.matches.iter().all(|m| m.a_degree == m.b_degree)
+    }
+}
+
+pub fn compare(a: &Scale, b: &Scale, tolerance: impl Into<Tolerance>) -> ScaleComparison {
+    let tolerance = tolerance.into();
+    let (a_cents, b_cents) = (a.cents(), b.cents());
+    let equave = a.equivalence.equave_cents();
+
+    let mut candidates: Vec<(f64, usize, usize)> = vec![];
+    for (i, ca) in a_cents.iter().enumerate() {
+        for (j, cb) in b_cents.iter().enumerate() {
+            let difference = signed_distance(*ca, *cb, equave);
+            if tolerance.accepts(difference) {
+                candidates.push((difference, i, j));
+            }
+        }
+    }
+    candidates.sort_by(|x, y| x.0.abs().total_cmp(&y.0.abs()));
+
+    let mut matched_a = vec![false; a.len()];
+    let mut matched_b = vec![false; b.len()];
+    let mut matches = vec![];
+    for (difference, i, j) in candidates {
+        if !matched_a[i] && !matched_b[j] {
+            matched_a[i] = true;
+            matched_b[j] = true;
+            matches.push(DegreeMatch {
+                a: i,
+                b: j,
+                a_degree: a.degrees[i],
+                b_degree: b.degrees[j],
+                difference,
+            });
+        }
+    }
+    matches.sort_by_key(|m| m.a);
+
+    let unmatched = |scale: &Scale, matched: &[bool]| {
+        (0..scale.len())
+            .filter(|&i| !matched[i])
+            .map(|i| (i, scale.degrees[i]))
+            .collect()
+    };
+
+    ScaleComparison {
+        tolerance,
+        matches,
+        unmatched_a: unmatched(a, &matched_a),
+        unmatched_b: unmatched(b, &matched_b),
+    }
+}
+
+pub(crate) fn signed_distance(a: f64, b: f64, equave: Option<f64>) -> f64 {
+    match equave {
+        Some(e) => (b - a + e / 2.).rem_euclid(e) - e / 2.,
+        None => b - a,
+    }
+}
+
+impl Report for ScaleComparison {
+    fn tables(&self) -> Vec<Table> {
+        let mut matched = Table::new(
+            "Matched degrees",
+            &["a", "b", "degree_a", "degree_b", "difference"],
+        );
+        for m in &self.matches {
+            matched.push(vec![
+                m.a.into(),
+                m.b.into(),
+                m.a_degree.to_string().into(),
+                m.b_degree.to_string().into(),
+                m.difference.into(),
+            ]);
+        }
+
+        let mut unmatched = Table::new("Unmatched degrees", &["scale", "index", "degree"]);
+        for (label, degrees) in [("a", &self.unmatched_a), ("b", &self.unmatched_b)] {
+            for (i, r) in degrees {
+                unmatched.push(vec![label.into(), (*i).into(), r.to_string().into()]);
+            }
+        }
+
+        vec![matched, unmatched]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ratio::Ratio;
+    use crate::report::schema;
+
+    fn scale(ratios: &[(i32, i32)]) -> Scale {
+        Scale::new(ratios.iter().map(|&(n, d)| Ratio::new(n, d)).collect())
+    }
+
+    #[test]
+    fn identical_scales() {
+        let a = scale(&[(1, 1), (5, 4), (3, 2)]);
+
+        let comparison = compare(&a, &a, 1.);
+
+        assert!(comparison.is_identical());
+        assert_eq!(comparison.matches.len(), 3);
+    }
+
+    #[test]
+    fn compare_just_and_pythagorean_thirds() {
+        let a = scale(&[(1, 1), (5, 4), (3, 2)]);
+        let b = scale(&[(1, 1), (81, 64), (3, 2), (27, 16)]);
+
+        let comparison = compare(&a, &b, 25.);
+
+        assert_eq!(comparison.matches.len(), 3);
+        assert_eq!(comparison.matches[1].a_degree, Ratio::new(5, 4));
+        assert_eq!(comparison.matches[1].b_degree, Ratio::new(81, 64));
+        assert!((comparison.matches[1].difference - 21.506).abs() < 1e-3);
+        assert!(comparison.unmatched_a.is_empty());
+        assert_eq!(
+            comparison.unmatched_b,
+            vec![(3, Degree::from(Ratio::new(27, 16)))]
+        );
+        assert!(!comparison.is_identical());
+    }
+
+    #[test]
+    fn matches_wrap_around_the_octave() {
+        let a = scale(&[(1, 1)]);
+        let b = scale(&[(160, 81)]);
+
+        let comparison = compare(&a, &b, 25.);
+
+        assert_eq!(comparison.matches.len(), 1);
+        assert!(comparison.matches[0].difference < 0.);
+    }
+
+    #[test]
+    fn tolerance_limits_matches() {
+        let a = scale(&[(1, 1), (5, 4)]);
+        let b = scale(&[(1, 1), (81, 64)]);
+
+        let comparison = compare(&a, &b, 5.);
+
+        assert_eq!(comparison.matches.len(), 1);
+        assert_eq!(
+            comparison.unmatched_a,
+            vec![(1, Degree::from(Ratio::new(5, 4)))]
+        );
+        assert_eq!(
+            comparison.unmatched_b,
+            vec![(1, Degree::from(Ratio::new(81, 64)))]
+        );
+    }
+
+    #[test]
+    fn compare_exact_and_tempered_scales() {
+        let a = scale(&[(1, 1), (5, 4), (3, 2)]);
+        let b = Scale::new(vec![0., 400., 700.]);
+
+        let comparison = compare(&a, &b, 20.);
+
+        assert_eq!(comparison.matches.len(), 3);
+        assert_eq!(comparison.matches[2].b_degree, Degree::from(700.));
+        assert!((comparison.matches[1].difference - 13.686).abs() < 1e-3);
+    }
+
+    #[test]
+    fn report_renders_both_tables() {
+        let a = scale(&[(1, 1), (5, 4)]);
+        let b = scale(&[(1, 1), (81, 64)]);
+
+        let rendered = compare(&a, &b, 5.).render();
+
+        assert!(rendered.starts_with("Matched degrees\n"));
+        assert!(rendered.contains("0  0  1/1       1/1       0.000\n"));
+        assert!(rendered.contains("Unmatched degrees\n"));
+        assert!(rendered.contains("b      1      81/64\n"));
+    }
+
+    #[test]
+    fn comparison_schema() {
+        let a = scale(&[(1, 1), (5, 4)]);
+
+        assert_eq!(
+            schema(&compare(&a, &a, 5.)),
+            [
+                "Matched degrees: a, b, degree_a, degree_b, difference",
+                "Unmatched degrees: scale, index, degree",
+            ]
+        );
+    }
+}
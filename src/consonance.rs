@@ -0,0 +1,162 @@
+use std::sync::OnceLock;
+
+use crate::degree::Degree;
+use crate::ratio::Ratio;
+
+const CENTS_APPROXIMATION_DENOM: i32 = 128;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Metric {
+    OddLimit,
+    TenneyHeight,
+    HarmonicEntropy,
+    BarlowHarmonicity,
+    EulerGradus,
+}
+
+impl Metric {
+    pub fn score(&self, interval: &Ratio) -> f64 {
+        match self {
+            Metric::OddLimit => interval.odd_limit() as f64,
+            Metric::TenneyHeight => interval.tenney_height(),
+            Metric::HarmonicEntropy => harmonic_entropy(interval.cents()),
+            Metric::BarlowHarmonicity => 1. / interval.barlow_harmonicity().abs(),
+            Metric::EulerGradus => interval.euler_gradus() as f64,
+        }
+    }
+
+    pub fn score_degree(&self, interval: &Degree) -> f64 {
+        match (self, interval.ratio()) {
+            (_, Some(ratio)) => self.score(&ratio),
+            (Metric::HarmonicEntropy, None) => harmonic_entropy(interval.cents()),
+            (_, None) => self.score(&interval.approximate(CENTS_APPROXIMATION_DENOM)),
+        }
+    }
+
+    pub fn rank(&self, intervals: &[Ratio]) -> Vec<Ratio> {
+        let mut scored: Vec<(f64, Ratio)> = intervals.iter().map(|r| (self.score(r), *r)).collect();
+        scored.sort_by(|a, b| a.0.total_cmp(&b.0));
+        scored.into_iter().map(|(_, r)| r).collect()
+    }
+}
+
+const ENTROPY_SPREAD: f64 = 17.;
+const ENTROPY_MAX_HEIGHT: i64 = 10000;
+
+// Tenney-series harmonic entropy: each candidate n/d with n * d below the
+// height bound is weighted by 1/sqrt(n * d) and a gaussian of the distance
+// to the heard interval.
+pub fn harmonic_entropy(cents: f64) -> f64 {
+    let weights: Vec<f64> = entropy_series()
+        .iter()
+        .map(|&(c, w)| w * (-(cents - c).powi(2) / (2. * ENTROPY_SPREAD.powi(2))).exp())
+        .collect();
+    let total: f64 = weights.iter().sum();
+
+    weights
+        .iter()
+        .map(|w| w / total)
+        .filter(|&p| p > 0.)
+        .map(|p| -p * p.ln())
+        .sum()
+}
+
+fn entropy_series() -> &'static [(f64, f64)] {
+    static SERIES: OnceLock<Vec<(f64, f64)>> = OnceLock::new();
+    SERIES.get_or_init(|| {
+        let mut series = vec![];
+        for denom in 1..=ENTROPY_MAX_HEIGHT {
+            for numer in denom..=(ENTROPY_MAX_HEIGHT / denom) {
+                if numer > denom * 4 {
+                    break;
+                }
+                let r = Ratio::new(numer as i32, denom as i32);
+                if r.numer as i64 == numer {
+                    series.push((r.cents(), 1. / ((numer * denom) as f64).sqrt()));
+                }
+            }
+        }
+        series
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_cents_degrees() {
+        let exact = Degree::from(Ratio::new(3, 2));
+        let tempered = Degree::from(700.);
+
+        assert_eq!(Metric::OddLimit.score_degree(&exact), 3.);
+        assert_eq!(Metric::OddLimit.score_degree(&tempered), 3.);
+        assert!(Metric::OddLimit.score_degree(&Degree::from(400.)) > 5.);
+        assert_eq!(
+            Metric::HarmonicEntropy.score_degree(&tempered),
+            harmonic_entropy(700.)
+        );
+    }
+
+    #[test]
+    fn odd_limit_score() {
+        assert_eq!(Metric::OddLimit.score(&Ratio::new(3, 2)), 3.);
+        assert_eq!(Metric::OddLimit.score(&Ratio::new(15, 8)), 15.);
+        assert_eq!(Metric::OddLimit.score(&Ratio::new(1, 1)), 1.);
+    }
+
+    #[test]
+    fn tenney_height_score() {
+        assert!((Metric::TenneyHeight.score(&Ratio::new(3, 2)) - 6_f64.log2()).abs() < 1e-12);
+        assert_eq!(Metric::TenneyHeight.score(&Ratio::new(1, 1)), 0.);
+    }
+
+    #[test]
+    fn barlow_score_is_disharmonicity() {
+        assert_eq!(Metric::BarlowHarmonicity.score(&Ratio::new(1, 1)), 0.);
+        assert!((Metric::BarlowHarmonicity.score(&Ratio::new(3, 2)) - 11. / 3.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn euler_gradus_score() {
+        assert_eq!(Metric::EulerGradus.score(&Ratio::new(3, 2)), 4.);
+    }
+
+    #[test]
+    fn rank_orders_most_consonant_first() {
+        let intervals = [
+            Ratio::new(16, 15),
+            Ratio::new(3, 2),
+            Ratio::new(5, 4),
+            Ratio::new(4, 3),
+        ];
+
+        for metric in [
+            Metric::OddLimit,
+            Metric::TenneyHeight,
+            Metric::BarlowHarmonicity,
+            Metric::EulerGradus,
+        ] {
+            let ranked = metric.rank(&intervals);
+            assert_eq!(ranked[0], Ratio::new(3, 2));
+            assert_eq!(ranked[3], Ratio::new(16, 15));
+        }
+    }
+
+    #[test]
+    fn harmonic_entropy_favors_simple_intervals() {
+        let fifth = Metric::HarmonicEntropy.score(&Ratio::new(3, 2));
+        let wolf = Metric::HarmonicEntropy.score(&Ratio::new(40, 27));
+        let tritone = Metric::HarmonicEntropy.score(&Ratio::new(45, 32));
+
+        assert!(fifth < wolf);
+        assert!(fifth < tritone);
+    }
+
+    #[test]
+    fn harmonic_entropy_is_nonnegative() {
+        for cents in [0., 111., 386., 600., 1200.] {
+            assert!(harmonic_entropy(cents) >= 0.);
+        }
+    }
+}
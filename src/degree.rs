@@ -0,0 +1,223 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::equivalence::Equivalence;
+use crate::pitch::{Pitch, PitchClass};
+use crate::ratio::{Ratio, Style};
+use crate::report::cents;
+
+pub const CENTS_EPSILON: f64 = 1e-6;
+pub const CENTS_MAX_DENOM: i32 = 10000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Degree {
+    Ratio(Ratio),
+    Cents(f64),
+}
+
+impl Degree {
+    pub fn from_cents(cents: f64) -> Self {
+        Degree::Cents(cents)
+    }
+
+    pub fn ratio(&self) -> Option<Ratio> {
+        match self {
+            Degree::Ratio(ratio) => Some(*ratio),
+            Degree::Cents(_) => None,
+        }
+    }
+
+    pub fn reduce(&self, equivalence: Equivalence) -> Self {
+        match (self, equivalence.period()) {
+            (Degree::Ratio(ratio), Some(Degree::Cents(period)))
+                if period > 0. && *ratio > Ratio::new(0, 1) =>
+            {
+                match (0. ..period).contains(&ratio.cents()) {
+                    true => *self,
+                    false => Degree::Cents(ratio.cents().rem_euclid(period)),
+                }
+            }
+            (Degree::Ratio(ratio), _) => Degree::Ratio(equivalence.reduce(*ratio)),
+            (Degree::Cents(cents), _) => Degree::Cents(equivalence.reduce_cents(*cents)),
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        matches!(self, Degree::Ratio(_))
+    }
+
+    pub fn cents(&self) -> f64 {
+        match self {
+            Degree::Ratio(ratio) => ratio.cents(),
+            Degree::Cents(cents) => *cents,
+        }
+    }
+
+    pub fn multiplier(&self) -> f64 {
+        match self {
+            Degree::Ratio(ratio) => f64::from(ratio),
+            Degree::Cents(cents) => 2_f64.powf(cents / 1200.),
+        }
+    }
+
+    pub fn approximate(&self, max_denom: i32) -> Ratio {
+        match self {
+            Degree::Ratio(ratio) => *ratio,
+            Degree::Cents(cents) => Ratio::from_cents(*cents, max_denom),
+        }
+    }
+
+    pub fn total_cmp(&self, other: &Degree) -> Ordering {
+        match (self, other) {
+            (Degree::Ratio(a), Degree::Ratio(b)) => a.cmp(b),
+            _ => self.cents().total_cmp(&other.cents()),
+        }
+    }
+
+    pub fn interval_to(&self, other: &Degree) -> Degree {
+        match (self, other) {
+            (Degree::Ratio(a), Degree::Ratio(b)) => Degree::Ratio(*b / *a),
+            _ => Degree::Cents(other.cents() - self.cents()),
+        }
+    }
+
+    pub fn is_unison(&self) -> bool {
+        match self {
+            Degree::Ratio(ratio) => *ratio == Ratio::new(1, 1),
+            Degree::Cents(cents) => cents.abs() < CENTS_EPSILON,
+        }
+    }
+
+    pub fn format(&self, style: Style) -> String {
+        match (self, style) {
+            (Degree::Ratio(ratio), style) => ratio.format(style),
+            (Degree::Cents(value), Style::Cents) => cents(*value),
+            (Degree::Cents(_), _) => self.to_string(),
+        }
+    }
+
+    pub fn approx_eq(&self, other: &Degree) -> bool {
+        match (self, other) {
+            (Degree::Ratio(a), Degree::Ratio(b)) => a == b,
+            _ => (self.cents() - other.cents()).abs() < CENTS_EPSILON,
+        }
+    }
+}
+
+impl PartialOrd for Degree {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Degree::Ratio(a), Degree::Ratio(b)) => Some(a.cmp(b)),
+            _ => self.cents().partial_cmp(&other.cents()),
+        }
+    }
+}
+
+impl PartialEq<Ratio> for Degree {
+    fn eq(&self, other: &Ratio) -> bool {
+        *self == Degree::from(*other)
+    }
+}
+
+impl fmt::Display for Degree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Degree::Ratio(ratio) => write!(f, "{ratio}"),
+            Degree::Cents(cents) if cents.fract() == 0. => write!(f, "{cents:.1}"),
+            Degree::Cents(cents) => write!(f, "{cents}"),
+        }
+    }
+}
+
+impl From<PitchClass> for Degree {
+    fn from(value: PitchClass) -> Self {
+        Degree::Ratio(value.ratio())
+    }
+}
+
+impl From<Ratio> for Degree {
+    fn from(value: Ratio) -> Self {
+        Degree::Ratio(value)
+    }
+}
+
+impl From<Pitch> for Degree {
+    fn from(value: Pitch) -> Self {
+        Degree::Ratio(value.ratio())
+    }
+}
+
+impl From<f64> for Degree {
+    fn from(value: f64) -> Self {
+        Degree::from_cents(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn degrees_reduce_by_equivalence() {
+        assert!(Degree::from(1586.314)
+            .reduce(Equivalence::Octave)
+            .approx_eq(&Degree::Cents(386.314)));
+        assert!((Degree::from(-100.).reduce(Equivalence::Octave).cents() - 1100.).abs() < 1e-9);
+        assert_eq!(
+            Degree::from(Ratio::new(9, 2)).reduce(Equivalence::Tritave),
+            Ratio::new(3, 2)
+        );
+        assert_eq!(
+            Degree::from(Ratio::new(9, 2)).reduce(Equivalence::None),
+            Ratio::new(9, 2)
+        );
+    }
+
+    #[test]
+    fn intervals_stay_exact_between_ratios() {
+        let third = Degree::from(Ratio::new(5, 4));
+        let fifth = Degree::from(Ratio::new(3, 2));
+
+        assert_eq!(third.interval_to(&fifth), Ratio::new(6, 5));
+        assert_eq!(fifth.interval_to(&third), Ratio::new(5, 6));
+    }
+
+    #[test]
+    fn intervals_with_cents_are_cents() {
+        let third = Degree::from(Ratio::new(5, 4));
+        let tempered = Degree::from(700.);
+
+        let interval = third.interval_to(&tempered);
+
+        assert!(!interval.is_exact());
+        assert!((interval.cents() - 313.686).abs() < 1e-3);
+    }
+
+    #[test]
+    fn ordering_mixes_kinds() {
+        let third = Degree::from(Ratio::new(5, 4));
+        let tempered = Degree::from(400.);
+
+        assert!(third < tempered);
+        assert!(third.approx_eq(&Degree::from(third.cents())));
+        assert_ne!(third, Degree::from(third.cents()));
+        assert_eq!(third.total_cmp(&tempered), Ordering::Less);
+        assert_eq!(
+            Degree::from(f64::NAN).total_cmp(&tempered),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Degree::from(Ratio::new(3, 2)).to_string(), "3/2");
+        assert_eq!(Degree::from(700.).to_string(), "700.0");
+        assert_eq!(Degree::from(386.314).to_string(), "386.314");
+    }
+
+    #[test]
+    fn multiplier() {
+        assert_eq!(Degree::from(Ratio::new(5, 4)).multiplier(), 1.25);
+        assert!((Degree::from(1200. / 12.).multiplier() - 2_f64.powf(1. / 12.)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,192 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub len: usize,
+}
+
+impl Span {
+    pub fn new(line: usize, column: usize, len: usize) -> Self {
+        Self { line, column, len }
+    }
+
+    pub fn of(line: usize, text: &str, token: &str) -> Self {
+        Self::at(line, text.find(token).unwrap_or(0), token)
+    }
+
+    pub fn at(line: usize, offset: usize, token: &str) -> Self {
+        Self::new(line, offset + 1, token.len().max(1))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub span: Span,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}: {}",
+            self.span.line, self.span.column, self.severity, self.message
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Diagnostics {
+    pub items: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self { items: vec![] }
+    }
+
+    pub fn push(&mut self, severity: Severity, span: Span, message: impl fmt::Display) {
+        self.items.push(Diagnostic {
+            severity,
+            span,
+            message: message.to_string(),
+        });
+    }
+
+    pub fn error(&mut self, span: Span, message: impl fmt::Display) {
+        self.push(Severity::Error, span, message);
+    }
+
+    pub fn warning(&mut self, span: Span, message: impl fmt::Display) {
+        self.push(Severity::Warning, span, message);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.items.iter().any(|d| d.severity == Severity::Error)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.items.iter().filter(|d| d.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.items
+            .iter()
+            .filter(|d| d.severity == Severity::Warning)
+    }
+
+    pub fn render(&self, source: &str) -> String {
+        let lines: Vec<&str> = source.lines().collect();
+        let mut out = String::new();
+        for d in &self.items {
+            out.push_str(&format!("{d}\n"));
+            if let Some(line) = d.span.line.checked_sub(1).and_then(|i| lines.get(i)) {
+                out.push_str(&format!("    {line}\n"));
+                out.push_str(&format!(
+                    "    {}{}\n",
+                    " ".repeat(d.span.column.saturating_sub(1)),
+                    "^".repeat(d.span.len)
+                ));
+            }
+        }
+        out
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Problem<E> {
+    pub span: Span,
+    pub severity: Severity,
+    pub error: E,
+    pub message: String,
+}
+
+impl<E: fmt::Display> Problem<E> {
+    pub fn error(span: Span, error: E) -> Self {
+        let message = error.to_string();
+        Self {
+            span,
+            severity: Severity::Error,
+            error,
+            message,
+        }
+    }
+
+    pub fn warning(span: Span, error: E, message: String) -> Self {
+        Self {
+            span,
+            severity: Severity::Warning,
+            error,
+            message,
+        }
+    }
+}
+
+pub(crate) fn to_diagnostics<E>(problems: Vec<Problem<E>>) -> Diagnostics {
+    Diagnostics {
+        items: problems
+            .into_iter()
+            .map(|p| Diagnostic {
+                severity: p.severity,
+                span: p.span,
+                message: p.message,
+            })
+            .collect(),
+    }
+}
+
+pub fn decimal_comma(token: &str) -> Option<String> {
+    let (whole, fraction) = token.split_once(',')?;
+    let valid = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    (valid(whole.trim_start_matches('-')) && valid(fraction)).then(|| format!("{whole}.{fraction}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_with_caret() {
+        let source = "first\n  bad token\n";
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.error(Span::of(2, "  bad token", "bad"), "unexpected word");
+        diagnostics.warning(Span::new(1, 1, 5), "suspicious");
+
+        assert!(diagnostics.has_errors());
+        assert_eq!(diagnostics.warnings().count(), 1);
+        assert_eq!(
+            diagnostics.render(source),
+            "2:3: error: unexpected word\n      bad token\n      ^^^\n1:1: warning: suspicious\n    first\n    ^^^^^\n"
+        );
+    }
+
+    #[test]
+    fn decimal_commas() {
+        assert_eq!(decimal_comma("701,955"), Some("701.955".to_string()));
+        assert_eq!(decimal_comma("-3,5"), Some("-3.5".to_string()));
+        assert_eq!(decimal_comma("3/2"), None);
+        assert_eq!(decimal_comma("1,2,3"), None);
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+
+use crate::ratio::Ratio;
+use crate::scale::Scale;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CrossSet {
+    pub scale: Scale,
+    pub sources: Vec<Vec<(Ratio, Ratio)>>,
+}
+
+impl CrossSet {
+    pub fn len(&self) -> usize {
+        self.scale.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scale.is_empty()
+    }
+}
+
+pub fn cross_set(a: &[Ratio], b: &[Ratio]) -> CrossSet {
+    let mut products: BTreeMap<Ratio, Vec<(Ratio, Ratio)>> = BTreeMap::new();
+    for &x in a {
+        for &y in b {
+            products
+                .entry((x * y).normalize())
+                .or_default()
+                .push((x, y));
+        }
+    }
+    CrossSet {
+        scale: Scale::new(products.keys().copied().collect()),
+        sources: products.into_values().collect(),
+    }
+}
+
+pub fn diamond_intervals(odd_limit: i32) -> Vec<Ratio> {
+    let odds: Vec<i32> = (1..=odd_limit).step_by(2).collect();
+    diamond(&odds)
+}
+
+pub fn diamond(identities: &[i32]) -> Vec<Ratio> {
+    let identities: Vec<i32> = identities.iter().copied().filter(|&i| i > 0).collect();
+    let mut intervals: Vec<Ratio> = identities
+        .iter()
+        .flat_map(|&n| {
+            identities
+                .iter()
+                .map(move |&d| Ratio::new(n, d).normalize())
+        })
+        .collect();
+    intervals.sort();
+    intervals.dedup();
+    intervals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn five_limit_diamond() {
+        assert_eq!(
+            diamond_intervals(5),
+            vec![
+                Ratio::new(1, 1),
+                Ratio::new(6, 5),
+                Ratio::new(5, 4),
+                Ratio::new(4, 3),
+                Ratio::new(3, 2),
+                Ratio::new(8, 5),
+                Ratio::new(5, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn diamond_over_arbitrary_identities() {
+        let tones = diamond(&[1, 3, 7, 9, 11]);
+
+        assert_eq!(tones.len(), 19);
+        assert!(tones.contains(&Ratio::new(9, 7)));
+        assert!(tones.contains(&Ratio::new(11, 9)));
+        assert!(!tones.contains(&Ratio::new(5, 4)));
+        assert_eq!(diamond(&[1, 3, 9]), diamond(&[9, 3, 1, 3]));
+        assert_eq!(diamond(&[0, 5]), vec![Ratio::new(1, 1)]);
+    }
+
+    #[test]
+    fn cross_set_keeps_provenance() {
+        let a = [Ratio::new(1, 1), Ratio::new(3, 2)];
+        let b = [Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(4, 3)];
+
+        let cross = cross_set(&a, &b);
+
+        assert_eq!(cross.len(), 5);
+        assert_eq!(
+            cross.scale.ratios().unwrap(),
+            vec![
+                Ratio::new(1, 1),
+                Ratio::new(5, 4),
+                Ratio::new(4, 3),
+                Ratio::new(3, 2),
+                Ratio::new(15, 8),
+            ]
+        );
+        assert_eq!(
+            cross.sources[0],
+            vec![
+                (Ratio::new(1, 1), Ratio::new(1, 1)),
+                (Ratio::new(3, 2), Ratio::new(4, 3))
+            ]
+        );
+        assert_eq!(cross.sources[4], vec![(Ratio::new(3, 2), Ratio::new(5, 4))]);
+        assert!(cross_set(&a, &[]).is_empty());
+    }
+
+    #[test]
+    fn eleven_limit_diamond_has_29_tones() {
+        let diamond = diamond_intervals(11);
+
+        assert_eq!(diamond.len(), 29);
+        assert!(diamond.iter().all(|r| r.odd_limit() <= 11));
+    }
+
+    #[test]
+    fn even_limits_round_down() {
+        assert_eq!(diamond_intervals(8), diamond_intervals(7));
+        assert_eq!(diamond_intervals(1), vec![Ratio::new(1, 1)]);
+    }
+}
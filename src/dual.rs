@@ -0,0 +1,122 @@
+use crate::chord::Chord;
+use crate::metadata::Metadata;
+use crate::ratio::Ratio;
+use crate::report::{Report, Table};
+use crate::scala::ScalaFile;
+use crate::scale::Scale;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DualPair {
+    pub name: String,
+    pub otonal: Scale,
+    pub utonal: Scale,
+}
+
+impl DualPair {
+    pub fn new(name: &str, otonal: Scale) -> Self {
+        Self {
+            name: name.to_string(),
+            utonal: otonal.reflect(Ratio::new(1, 1)),
+            otonal,
+        }
+    }
+
+    pub fn from_chord(name: &str, chord: &Chord) -> Self {
+        Self::new(name, Scale::new(chord.ratios()))
+    }
+
+    pub fn from_identities(name: &str, identities: &[i32]) -> Self {
+        Self::new(
+            name,
+            Scale::new(
+                identities
+                    .iter()
+                    .filter(|&&i| i > 0)
+                    .map(|&i| Ratio::new(i, 1))
+                    .collect(),
+            ),
+        )
+    }
+
+    pub fn to_scl(&self) -> (String, String) {
+        let file = |label: &str, scale: &Scale| {
+            ScalaFile::new(&format!("{} ({label})", self.name), scale.clone()).to_scl()
+        };
+        (file("otonal", &self.otonal), file("utonal", &self.utonal))
+    }
+}
+
+impl Report for DualPair {
+    fn tables(&self) -> Vec<Table> {
+        let mut table = Table::new(
+            &self.name,
+            &["otonal", "otonal_cents", "utonal", "utonal_cents"],
+        );
+        for degree in &self.otonal.degrees {
+            let mirror = degree
+                .interval_to(&Ratio::new(1, 1).into())
+                .reduce(self.otonal.equivalence);
+            table.push(vec![
+                degree.to_string().into(),
+                degree.cents().into(),
+                mirror.to_string().into(),
+                mirror.cents().into(),
+            ]);
+        }
+        vec![table]
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        Some(&self.otonal.metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::schema;
+
+    #[test]
+    fn dual_of_identity_row() {
+        let pair = DualPair::from_identities("5-limit", &[1, 5, 3]);
+
+        assert_eq!(
+            pair.otonal.ratios().unwrap(),
+            vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]
+        );
+        assert_eq!(
+            pair.utonal.ratios().unwrap(),
+            vec![Ratio::new(1, 1), Ratio::new(4, 3), Ratio::new(8, 5)]
+        );
+        assert_eq!(
+            pair,
+            DualPair::from_chord(
+                "5-limit",
+                &Chord::new(vec![Ratio::new(4, 1), Ratio::new(5, 1), Ratio::new(6, 1)])
+            )
+        );
+    }
+
+    #[test]
+    fn exports_side_by_side() {
+        let pair = DualPair::from_identities("Hexad", &[1, 3, 5]);
+
+        let (otonal, utonal) = pair.to_scl();
+        assert!(otonal.contains("Hexad (otonal)"));
+        assert!(utonal.contains("Hexad (utonal)\n"));
+        assert!(utonal.contains("8/5"));
+        assert!(pair.to_json().contains(&format!(
+            "{{\"otonal\":\"5/4\",\"otonal_cents\":{},\"utonal\":\"8/5\",\"utonal_cents\":{}}}",
+            Ratio::new(5, 4).cents(),
+            Ratio::new(8, 5).cents()
+        )));
+    }
+
+    #[test]
+    fn dual_schema() {
+        assert_eq!(
+            schema(&DualPair::from_identities("Hexad", &[1, 3, 5])),
+            ["Hexad: otonal, otonal_cents, utonal, utonal_cents"]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use crate::degree::{Degree, CENTS_EPSILON};
+use crate::ratio::Ratio;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Equivalence {
+    #[default]
+    Octave,
+    Tritave,
+    Period(Ratio),
+    CentsPeriod(f64),
+    None,
+}
+
+impl Equivalence {
+    pub fn from_period(period: impl Into<Degree>) -> Self {
+        match period.into() {
+            Degree::Ratio(r) if r == Ratio::new(2, 1) => Equivalence::Octave,
+            Degree::Ratio(r) if r == Ratio::new(3, 1) => Equivalence::Tritave,
+            Degree::Ratio(r) if r > Ratio::new(1, 1) => Equivalence::Period(r),
+            Degree::Cents(c) if (c - 1200.).abs() < CENTS_EPSILON => Equivalence::Octave,
+            Degree::Cents(c) if c > 0. && c.is_finite() => Equivalence::CentsPeriod(c),
+            _ => Equivalence::None,
+        }
+    }
+
+    pub fn period(&self) -> Option<Degree> {
+        match self {
+            Equivalence::Octave => Some(Degree::Ratio(Ratio::new(2, 1))),
+            Equivalence::Tritave => Some(Degree::Ratio(Ratio::new(3, 1))),
+            Equivalence::Period(r) => Some(Degree::Ratio(*r)),
+            Equivalence::CentsPeriod(c) => Some(Degree::Cents(*c)),
+            Equivalence::None => None,
+        }
+    }
+
+    pub fn equave(&self) -> Option<Ratio> {
+        self.period().and_then(|p| p.ratio())
+    }
+
+    pub fn equave_cents(&self) -> Option<f64> {
+        self.period().map(|p| p.cents())
+    }
+
+    pub fn reduce(&self, ratio: Ratio) -> Ratio {
+        let one = Ratio::new(1, 1);
+        let equave = match self.equave() {
+            Some(e) if e > one && ratio > Ratio::new(0, 1) => e,
+            _ => return ratio,
+        };
+        let mut ratio = ratio;
+        while ratio >= equave {
+            ratio = ratio / equave;
+        }
+        while ratio < one {
+            ratio = ratio * equave;
+        }
+        ratio
+    }
+
+    pub fn reduce_cents(&self, cents: f64) -> f64 {
+        match self.equave_cents() {
+            Some(e) if e > 0. => cents.rem_euclid(e),
+            _ => cents,
+        }
+    }
+
+    pub fn complement(&self, ratio: Ratio) -> Ratio {
+        match self.equave() {
+            Some(e) => self.reduce(e / ratio),
+            None => Ratio::new(1, 1) / ratio,
+        }
+    }
+
+    pub fn distance(&self, a: f64, b: f64) -> f64 {
+        let d = (a - b).abs();
+        match self.equave_cents() {
+            Some(e) if e > 0. => {
+                let d = d.rem_euclid(e);
+                d.min(e - d)
+            }
+            _ => d,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn octave_reduction() {
+        let eq = Equivalence::Octave;
+
+        assert_eq!(eq.reduce(Ratio::new(9, 4)), Ratio::new(9, 8));
+        assert_eq!(eq.reduce(Ratio::new(1, 3)), Ratio::new(4, 3));
+        assert_eq!(eq.complement(Ratio::new(3, 2)), Ratio::new(4, 3));
+        assert_eq!(eq.reduce_cents(-100.), 1100.);
+    }
+
+    #[test]
+    fn tritave_reduction() {
+        let eq = Equivalence::Tritave;
+
+        assert_eq!(eq.reduce(Ratio::new(9, 1)), Ratio::new(1, 1));
+        assert_eq!(eq.reduce(Ratio::new(7, 9)), Ratio::new(7, 3));
+        assert_eq!(eq.complement(Ratio::new(5, 3)), Ratio::new(9, 5));
+        assert!((eq.reduce_cents(2000.) - (2000. - Ratio::new(3, 1).cents())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn no_equivalence_keeps_ratios() {
+        let eq = Equivalence::None;
+
+        assert_eq!(eq.equave(), None);
+        assert_eq!(eq.reduce(Ratio::new(9, 4)), Ratio::new(9, 4));
+        assert_eq!(eq.complement(Ratio::new(3, 2)), Ratio::new(2, 3));
+        assert_eq!(eq.reduce_cents(-100.), -100.);
+        assert_eq!(eq.distance(0., 1150.), 1150.);
+        assert_eq!(Equivalence::Octave.distance(0., 1150.), 50.);
+    }
+
+    #[test]
+    fn arbitrary_periods() {
+        let fifth = Equivalence::from_period(Ratio::new(3, 2));
+        let cents = Equivalence::from_period(Degree::Cents(1900.));
+
+        assert_eq!(fifth, Equivalence::Period(Ratio::new(3, 2)));
+        assert_eq!(fifth.reduce(Ratio::new(2, 1)), Ratio::new(4, 3));
+        assert_eq!(cents.equave(), None);
+        assert_eq!(cents.reduce_cents(2000.), 100.);
+        assert_eq!(
+            Equivalence::from_period(Ratio::new(2, 1)),
+            Equivalence::Octave
+        );
+        assert_eq!(Equivalence::from_period(1200.), Equivalence::Octave);
+        assert_eq!(
+            Equivalence::from_period(Ratio::new(1, 1)),
+            Equivalence::None
+        );
+    }
+
+    #[test]
+    fn degenerate_periods_leave_input_unchanged() {
+        let fifth = Ratio::new(3, 2);
+        for eq in [
+            Equivalence::Period(Ratio::new(1, 1)),
+            Equivalence::Period(Ratio::new(1, 2)),
+            Equivalence::Period(Ratio::new(0, 1)),
+            Equivalence::Period(Ratio::new(-2, 1)),
+        ] {
+            assert_eq!(eq.reduce(fifth), fifth);
+        }
+        assert_eq!(
+            Equivalence::Octave.reduce(Ratio::new(0, 1)),
+            Ratio::new(0, 1)
+        );
+        assert_eq!(
+            Equivalence::Octave.reduce(Ratio::new(-3, 2)),
+            Ratio::new(-3, 2)
+        );
+        for eq in [
+            Equivalence::CentsPeriod(0.),
+            Equivalence::CentsPeriod(-700.),
+        ] {
+            assert_eq!(eq.reduce_cents(1900.), 1900.);
+            assert_eq!(eq.distance(0., 1150.), 1150.);
+        }
+    }
+
+    #[test]
+    fn default_is_octave() {
+        assert_eq!(Equivalence::default(), Equivalence::Octave);
+    }
+}
@@ -0,0 +1,299 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::degree::Degree;
+use crate::equivalence::Equivalence;
+use crate::ratio::Ratio;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EvalError {
+    UnexpectedToken { token: String, position: usize },
+    UnexpectedEnd,
+    DivisionByZero,
+    InvalidExponent(String),
+    InvalidEquave(String),
+    Overflow,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvalError::UnexpectedToken { token, position } => {
+                write!(f, "unexpected {token} at position {position}")
+            }
+            EvalError::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            EvalError::DivisionByZero => write!(f, "division by zero"),
+            EvalError::InvalidExponent(s) => write!(f, "invalid exponent: {s}"),
+            EvalError::InvalidEquave(s) => write!(f, "invalid equave: {s}"),
+            EvalError::Overflow => write!(f, "ratio too large"),
+        }
+    }
+}
+
+impl Error for EvalError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Integer(i32),
+    Cents(f64),
+    Operator(char),
+}
+
+pub fn eval(input: &str) -> Result<Degree, EvalError> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser { tokens, next: 0 };
+    let value = parser.reduction()?;
+    match parser.tokens.get(parser.next) {
+        Some((_, position)) => Err(parser.unexpected(*position)),
+        None => Ok(value),
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, EvalError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let is_cents = text.contains('.') || chars.get(i) == Some(&'c');
+            if chars.get(i) == Some(&'c') {
+                i += 1;
+            }
+            let invalid = || EvalError::UnexpectedToken {
+                token: text.clone(),
+                position: start,
+            };
+            let token = match is_cents {
+                true => Token::Cents(text.parse().map_err(|_| invalid())?),
+                false => Token::Integer(text.parse().map_err(|_| EvalError::Overflow)?),
+            };
+            tokens.push((token, start));
+        } else if "*/^%()-".contains(c) {
+            tokens.push((Token::Operator(c), i));
+            i += 1;
+        } else {
+            return Err(EvalError::UnexpectedToken {
+                token: c.to_string(),
+                position: i,
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    next: usize,
+}
+
+impl Parser {
+    fn peek_operator(&self) -> Option<char> {
+        match self.tokens.get(self.next) {
+            Some((Token::Operator(c), _)) => Some(*c),
+            _ => None,
+        }
+    }
+
+    fn unexpected(&self, position: usize) -> EvalError {
+        let token = match &self.tokens[self.next].0 {
+            Token::Integer(n) => n.to_string(),
+            Token::Cents(c) => format!("{c}c"),
+            Token::Operator(c) => c.to_string(),
+        };
+        EvalError::UnexpectedToken { token, position }
+    }
+
+    fn reduction(&mut self) -> Result<Degree, EvalError> {
+        let mut value = self.product()?;
+        while self.peek_operator() == Some('%') {
+            self.next += 1;
+            let equave = self.product()?;
+            value = reduce(value, equave)?;
+        }
+        Ok(value)
+    }
+
+    fn product(&mut self) -> Result<Degree, EvalError> {
+        let mut value = self.power()?;
+        while let Some(op @ ('*' | '/')) = self.peek_operator() {
+            self.next += 1;
+            let rhs = self.power()?;
+            value = match op {
+                '*' => multiply(value, rhs)?,
+                _ => multiply(value, invert(rhs)?)?,
+            };
+        }
+        Ok(value)
+    }
+
+    fn power(&mut self) -> Result<Degree, EvalError> {
+        let base = self.atom()?;
+        if self.peek_operator() != Some('^') {
+            return Ok(base);
+        }
+        self.next += 1;
+        let negative = self.peek_operator() == Some('-');
+        if negative {
+            self.next += 1;
+        }
+        let exponent = self.power()?;
+        let exponent = match exponent.ratio() {
+            Some(r) if negative => Ratio::new(-r.numer, r.denom),
+            Some(r) => r,
+            None => return Err(EvalError::InvalidExponent(exponent.to_string())),
+        };
+        power(base, exponent)
+    }
+
+    fn atom(&mut self) -> Result<Degree, EvalError> {
+        let (token, position) = self
+            .tokens
+            .get(self.next)
+            .cloned()
+            .ok_or(EvalError::UnexpectedEnd)?;
+        match token {
+            Token::Integer(n) => {
+                self.next += 1;
+                Ok(Degree::from(Ratio::new(n, 1)))
+            }
+            Token::Cents(c) => {
+                self.next += 1;
+                Ok(Degree::from_cents(c))
+            }
+            Token::Operator('(') => {
+                self.next += 1;
+                let value = self.reduction()?;
+                match self.peek_operator() {
+                    Some(')') => {
+                        self.next += 1;
+                        Ok(value)
+                    }
+                    _ if self.next >= self.tokens.len() => Err(EvalError::UnexpectedEnd),
+                    _ => Err(self.unexpected(self.tokens[self.next].1)),
+                }
+            }
+            _ => Err(self.unexpected(position)),
+        }
+    }
+}
+
+fn checked(numer: i64, denom: i64) -> Result<Ratio, EvalError> {
+    let (mut a, mut b) = (numer.abs(), denom.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    let (numer, denom) = (numer / a.max(1), denom / a.max(1));
+    match (i32::try_from(numer), i32::try_from(denom)) {
+        (Ok(n), Ok(d)) => Ok(Ratio::new(n, d)),
+        _ => Err(EvalError::Overflow),
+    }
+}
+
+fn multiply(a: Degree, b: Degree) -> Result<Degree, EvalError> {
+    match (a.ratio(), b.ratio()) {
+        (Some(a), Some(b)) => checked(
+            a.numer as i64 * b.numer as i64,
+            a.denom as i64 * b.denom as i64,
+        )
+        .map(Degree::from),
+        _ => Ok(Degree::from_cents(a.cents() + b.cents())),
+    }
+}
+
+fn invert(a: Degree) -> Result<Degree, EvalError> {
+    match a.ratio() {
+        Some(r) if r.numer == 0 => Err(EvalError::DivisionByZero),
+        Some(r) => Ok(Degree::from(Ratio::new(r.denom, r.numer))),
+        None => Ok(Degree::from_cents(-a.cents())),
+    }
+}
+
+fn power(base: Degree, exponent: Ratio) -> Result<Degree, EvalError> {
+    match (base.ratio(), exponent.denom) {
+        (Some(r), 1) => {
+            if r.numer == 0 && exponent.numer < 0 {
+                return Err(EvalError::DivisionByZero);
+            }
+            let n = exponent.numer.unsigned_abs();
+            let numer = (r.numer as i64).checked_pow(n).ok_or(EvalError::Overflow)?;
+            let denom = (r.denom as i64).checked_pow(n).ok_or(EvalError::Overflow)?;
+            match exponent.numer < 0 {
+                true => checked(denom, numer),
+                false => checked(numer, denom),
+            }
+            .map(Degree::from)
+        }
+        _ => Ok(Degree::from_cents(
+            base.cents() * exponent.numer as f64 / exponent.denom as f64,
+        )),
+    }
+}
+
+fn reduce(value: Degree, equave: Degree) -> Result<Degree, EvalError> {
+    if equave.cents() <= 0. {
+        return Err(EvalError::InvalidEquave(equave.to_string()));
+    }
+    Ok(value.reduce(Equivalence::from_period(equave)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ratio(input: &str) -> Option<Ratio> {
+        eval(input).unwrap().ratio()
+    }
+
+    #[test]
+    fn exact_arithmetic() {
+        assert_eq!(ratio("(3/2)^4 / 5 % 2/1"), Some(Ratio::new(81, 80)));
+        assert_eq!(ratio("(9/8)^-1"), Some(Ratio::new(8, 9)));
+        assert_eq!(ratio("7 % 2"), Some(Ratio::new(7, 4)));
+        assert_eq!(ratio("2/3 % 2"), Some(Ratio::new(4, 3)));
+        assert_eq!(ratio("5/4 * 6/5"), Some(Ratio::new(3, 2)));
+        assert_eq!(ratio("3^0"), Some(Ratio::new(1, 1)));
+        assert_eq!(ratio("3/2 * 0"), Some(Ratio::new(0, 1)));
+        assert_eq!(ratio("0 % 2"), Some(Ratio::new(0, 1)));
+    }
+
+    #[test]
+    fn cents_arithmetic() {
+        let value = eval("3/2 * 700c").unwrap();
+        assert!(value.ratio().is_none());
+        assert!((value.cents() - 1401.955).abs() < 1e-3);
+
+        assert!((eval("5^(1/4) % 2").unwrap().cents() - 696.578).abs() < 1e-3);
+        assert!((eval("1901.955 % 1200c").unwrap().cents() - 701.955).abs() < 1e-9);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(eval("3/0"), Err(EvalError::DivisionByZero));
+        assert_eq!(eval("3/(2 * 0)"), Err(EvalError::DivisionByZero));
+        assert_eq!(eval("0^-1"), Err(EvalError::DivisionByZero));
+        assert_eq!(eval("3 *"), Err(EvalError::UnexpectedEnd));
+        assert_eq!(eval("(3/2"), Err(EvalError::UnexpectedEnd));
+        assert_eq!(
+            eval("3/2 + 1"),
+            Err(EvalError::UnexpectedToken {
+                token: "+".to_string(),
+                position: 4
+            })
+        );
+        assert_eq!(eval("3^100"), Err(EvalError::Overflow));
+        assert!(matches!(eval("2^(3c)"), Err(EvalError::InvalidExponent(_))));
+        assert_eq!(
+            eval("3 % 1"),
+            Err(EvalError::InvalidEquave("1/1".to_string()))
+        );
+        assert!(matches!(eval("3 % 0"), Err(EvalError::InvalidEquave(_))));
+    }
+}
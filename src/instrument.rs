@@ -0,0 +1,177 @@
+use crate::musicxml::Spelling;
+use crate::scale::Scale;
+use crate::standard::PitchStandard;
+
+const NOTE_STEPS: [(char, i32); 7] = [
+    ('C', 0),
+    ('D', 2),
+    ('E', 4),
+    ('F', 5),
+    ('G', 7),
+    ('A', 9),
+    ('B', 11),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Instrument {
+    pub name: String,
+    pub low: f64,
+    pub high: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RealizedPitch {
+    pub degree: usize,
+    pub equave: i32,
+    pub frequency: f64,
+    pub spelling: Spelling,
+    pub notation: String,
+}
+
+impl Instrument {
+    pub fn new(name: &str, low: f64, high: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            low,
+            high,
+        }
+    }
+
+    pub fn from_notes(name: &str, low: &str, high: &str) -> Option<Self> {
+        Some(Self::new(name, note_frequency(low)?, note_frequency(high)?))
+    }
+
+    pub fn violin() -> Self {
+        Self::from_notes("violin", "G3", "A7").unwrap()
+    }
+
+    pub fn viola() -> Self {
+        Self::from_notes("viola", "C3", "E6").unwrap()
+    }
+
+    pub fn cello() -> Self {
+        Self::from_notes("cello", "C2", "A5").unwrap()
+    }
+
+    pub fn contrabass() -> Self {
+        Self::from_notes("contrabass", "E1", "G4").unwrap()
+    }
+
+    pub fn flute() -> Self {
+        Self::from_notes("flute", "C4", "D7").unwrap()
+    }
+
+    pub fn contains(&self, frequency: f64) -> bool {
+        frequency >= self.low * (1. - 1e-9) && frequency <= self.high * (1. + 1e-9)
+    }
+
+    pub fn realize(&self, scale: &Scale, reference: impl Into<f64>) -> Vec<RealizedPitch> {
+        let reference: f64 = reference.into();
+        let equaves = match scale.equivalence.period() {
+            Some(e) => {
+                let e = e.multiplier();
+                let lowest = (self.low / reference).log(e).floor() as i32;
+                let highest = (self.high / reference).log(e).ceil() as i32;
+                (lowest..=highest).map(|k| (k, e.powi(k))).collect()
+            }
+            None => vec![(0, 1.)],
+        };
+        let mut pitches: Vec<RealizedPitch> = equaves
+            .iter()
+            .flat_map(|&(equave, multiplier)| {
+                scale.degrees.iter().enumerate().map(move |(degree, d)| {
+                    (degree, equave, reference * d.multiplier() * multiplier)
+                })
+            })
+            .filter(|&(_, _, frequency)| self.contains(frequency))
+            .map(|(degree, equave, frequency)| {
+                let spelling = Spelling::from_frequency(frequency);
+                RealizedPitch {
+                    degree,
+                    equave,
+                    frequency,
+                    spelling,
+                    notation: spelling.to_string(),
+                }
+            })
+            .collect();
+        pitches.sort_by(|a, b| a.frequency.total_cmp(&b.frequency));
+        pitches
+    }
+}
+
+pub fn note_frequency(name: &str) -> Option<f64> {
+    let mut chars = name.trim().chars();
+    let step = chars.next()?.to_ascii_uppercase();
+    let &(_, semitone) = NOTE_STEPS.iter().find(|(s, _)| *s == step)?;
+    let rest: String = chars.collect();
+    let accidentals = rest.chars().take_while(|c| *c == '#' || *c == 'b').count();
+    let alter: i32 = rest[..accidentals]
+        .chars()
+        .map(|c| if c == '#' { 1 } else { -1 })
+        .sum();
+    let octave: i32 = rest[accidentals..].parse().ok()?;
+    let note = 12 * (octave + 1) + semitone + alter;
+    let standard = PitchStandard::A440;
+    Some(standard.frequency * 2_f64.powf((note - standard.note as i32) as f64 / 12.))
+}
+
+pub fn realize_ensemble(
+    scale: &Scale,
+    reference: impl Into<f64>,
+    instruments: &[Instrument],
+) -> Vec<(String, Vec<RealizedPitch>)> {
+    let reference: f64 = reference.into();
+    instruments
+        .iter()
+        .map(|i| (i.name.clone(), i.realize(scale, reference)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ratio::Ratio;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-3
+    }
+
+    #[test]
+    fn note_names() {
+        assert!(approx(note_frequency("A4").unwrap(), 440.));
+        assert!(approx(note_frequency("C3").unwrap(), 130.813));
+        assert!(approx(note_frequency("Eb4").unwrap(), 311.127));
+        assert!(approx(note_frequency("f#2").unwrap(), 92.499));
+        assert!(note_frequency("H2").is_none());
+        assert!(note_frequency("C").is_none());
+    }
+
+    #[test]
+    fn viola_range() {
+        let scale = Scale::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+        let pitches = Instrument::viola().realize(&scale, note_frequency("C4").unwrap());
+
+        assert_eq!(pitches.first().unwrap().notation, "C3");
+        assert_eq!(pitches.last().unwrap().notation, "E6 -13.7c");
+        assert_eq!(pitches.len(), 11);
+        assert_eq!((pitches[1].degree, pitches[1].equave), (1, -1));
+    }
+
+    #[test]
+    fn ensemble_realization() {
+        let scale = Scale::new(vec![Ratio::new(1, 1), Ratio::new(7, 4)]);
+        let ensemble = realize_ensemble(
+            &scale,
+            note_frequency("C4").unwrap(),
+            &[Instrument::cello(), Instrument::violin()],
+        );
+
+        assert_eq!(ensemble[0].0, "cello");
+        assert!(ensemble[1]
+            .1
+            .iter()
+            .all(|p| Instrument::violin().contains(p.frequency)));
+        assert!(ensemble[0].1.iter().any(|p| p.notation == "Bb3 -31.2c"));
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt;
+
+use crate::equivalence::Equivalence;
+use crate::ratio::Ratio;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalStack {
+    pub steps: Vec<(Ratio, i32)>,
+}
+
+impl IntervalStack {
+    pub fn new() -> Self {
+        Self { steps: vec![] }
+    }
+
+    pub fn push(&mut self, step: Ratio, count: i32) {
+        if count == 0 {
+            return;
+        }
+        match self.steps.last_mut() {
+            Some((last, n)) if *last == step => {
+                *n += count;
+                if *n == 0 {
+                    self.steps.pop();
+                }
+            }
+            _ => self.steps.push((step, count)),
+        }
+    }
+
+    pub fn up(mut self, step: Ratio, count: i32) -> Self {
+        self.push(step, count);
+        self
+    }
+
+    pub fn down(self, step: Ratio, count: i32) -> Self {
+        self.up(step, -count)
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn ratio(&self) -> Ratio {
+        self.steps
+            .iter()
+            .fold(Ratio::new(1, 1), |acc, &(step, count)| match count {
+                n if n < 0 => acc / step.pow(-n),
+                n => acc * step.pow(n),
+            })
+    }
+
+    pub fn ratio_with(&self, equivalence: Equivalence) -> Ratio {
+        equivalence.reduce(self.ratio())
+    }
+
+    pub fn cents(&self) -> f64 {
+        self.steps
+            .iter()
+            .map(|&(step, count)| step.cents() * count as f64)
+            .sum()
+    }
+
+    pub fn step_count(&self) -> i32 {
+        self.steps.iter().map(|(_, n)| n.abs()).sum()
+    }
+
+    pub fn inverse(&self) -> Self {
+        Self {
+            steps: self.steps.iter().rev().map(|&(s, n)| (s, -n)).collect(),
+        }
+    }
+
+    pub fn then(&self, other: &IntervalStack) -> Self {
+        let mut stack = self.clone();
+        for &(step, count) in &other.steps {
+            stack.push(step, count);
+        }
+        stack
+    }
+}
+
+impl fmt::Display for IntervalStack {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.steps.is_empty() {
+            return write!(f, "1/1");
+        }
+        let terms: Vec<String> = self
+            .steps
+            .iter()
+            .map(|(step, count)| match count {
+                1 => format!("{step}"),
+                n => format!("{step}^{n}"),
+            })
+            .collect();
+        write!(f, "{}", terms.join(" * "))
+    }
+}
+
+impl From<&IntervalStack> for Ratio {
+    fn from(value: &IntervalStack) -> Self {
+        value.ratio()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluate_stack() {
+        let stack = IntervalStack::new()
+            .up(Ratio::new(3, 2), 2)
+            .down(Ratio::new(5, 4), 1);
+
+        assert_eq!(stack.ratio(), Ratio::new(9, 5));
+        assert_eq!(stack.step_count(), 3);
+        assert!((stack.cents() - Ratio::new(9, 5).cents()).abs() < 1e-9);
+        assert_eq!(stack.to_string(), "3/2^2 * 5/4^-1");
+    }
+
+    #[test]
+    fn stacks_keep_their_derivation() {
+        let fifths = IntervalStack::new().up(Ratio::new(3, 2), 4);
+        let third = IntervalStack::new()
+            .up(Ratio::new(5, 4), 1)
+            .up(Ratio::new(2, 1), 2);
+
+        assert_eq!(fifths.ratio_with(Equivalence::Octave), Ratio::new(81, 64));
+        assert_ne!(fifths, third);
+        assert_eq!(fifths.then(&third.inverse()).ratio(), Ratio::new(81, 80));
+    }
+
+    #[test]
+    fn adjacent_steps_merge() {
+        let mut stack = IntervalStack::new();
+        stack.push(Ratio::new(3, 2), 1);
+        stack.push(Ratio::new(3, 2), 2);
+        stack.push(Ratio::new(7, 4), 0);
+
+        assert_eq!(stack.steps, vec![(Ratio::new(3, 2), 3)]);
+
+        stack.push(Ratio::new(3, 2), -3);
+        assert!(stack.is_empty());
+        assert_eq!(stack.to_string(), "1/1");
+    }
+}
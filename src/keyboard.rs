@@ -0,0 +1,111 @@
+pub const WHITE_KEYS: [usize; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyboardMapping {
+    pub root_note: u8,
+    pub root_frequency: f64,
+    pub pattern: Vec<Option<usize>>,
+    pub period_degrees: usize,
+}
+
+impl KeyboardMapping {
+    pub fn new(
+        root_note: u8,
+        root_frequency: impl Into<f64>,
+        pattern: Vec<Option<usize>>,
+        period_degrees: usize,
+    ) -> Option<Self> {
+        if pattern.is_empty() {
+            return None;
+        }
+        Some(Self {
+            root_note,
+            root_frequency: root_frequency.into(),
+            pattern,
+            period_degrees,
+        })
+    }
+
+    pub fn linear(root_note: u8, root_frequency: impl Into<f64>) -> Self {
+        Self {
+            root_note,
+            root_frequency: root_frequency.into(),
+            pattern: vec![Some(0)],
+            period_degrees: 1,
+        }
+    }
+
+    pub fn white_keys(root_note: u8, root_frequency: impl Into<f64>) -> Self {
+        let mut count = 0;
+        let pattern = (0..12)
+            .map(
+                |k| match WHITE_KEYS.contains(&((root_note as usize + k) % 12)) {
+                    true => {
+                        count += 1;
+                        Some(count - 1)
+                    }
+                    false => None,
+                },
+            )
+            .collect();
+        Self {
+            root_note,
+            root_frequency: root_frequency.into(),
+            pattern,
+            period_degrees: WHITE_KEYS.len(),
+        }
+    }
+
+    pub fn repeating(
+        root_note: u8,
+        root_frequency: impl Into<f64>,
+        keys: usize,
+        size: usize,
+    ) -> Option<Self> {
+        let pattern = (0..keys).map(|k| (k < size).then_some(k)).collect();
+        Self::new(root_note, root_frequency, pattern, size)
+    }
+
+    pub fn step(&self, note: u8) -> Option<i32> {
+        let offset = note as i32 - self.root_note as i32;
+        let size = self.pattern.len() as i32;
+        self.pattern[offset.checked_rem_euclid(size)? as usize]
+            .map(|k| offset.div_euclid(size) * self.period_degrees as i32 + k as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_mapping() {
+        let mapping = KeyboardMapping::linear(60, 261.63);
+
+        assert_eq!(mapping.step(60), Some(0));
+        assert_eq!(mapping.step(73), Some(13));
+        assert_eq!(mapping.step(59), Some(-1));
+    }
+
+    #[test]
+    fn white_keys_only() {
+        let mapping = KeyboardMapping::white_keys(60, 261.63);
+
+        assert_eq!(mapping.step(62), Some(1));
+        assert_eq!(mapping.step(61), None);
+        assert_eq!(mapping.step(71), Some(6));
+        assert_eq!(mapping.step(72), Some(7));
+        assert_eq!(mapping.step(57), Some(-2));
+    }
+
+    #[test]
+    fn repeating_block() {
+        let mapping = KeyboardMapping::repeating(60, 261.63, 12, 7).unwrap();
+
+        assert_eq!(mapping.step(66), Some(6));
+        assert_eq!(mapping.step(67), None);
+        assert_eq!(mapping.step(72), Some(7));
+        assert_eq!(mapping.step(48), Some(-7));
+        assert_eq!(KeyboardMapping::repeating(60, 261.63, 0, 0), None);
+    }
+}
@@ -1,13 +1,26 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+
+use crate::color::ColorScheme;
+use crate::equivalence::Equivalence;
+use crate::interval_stack::IntervalStack;
+use crate::metadata::Metadata;
+use crate::order::{sort_points, SortOrder};
+use crate::progress::{report, Cancelled, Control, Progress};
 use crate::ratio::Ratio;
+use crate::scale::Scale;
+use crate::tolerance::Tolerance;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum DimensionBound {
     Infinity,
     ZeroBounded(i32),
     RangeBounded(i32, i32),
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct LatticeDimension {
     pub ratio: Ratio,
     pub bounds: DimensionBound,
@@ -26,36 +39,786 @@ impl LatticeDimension {
             }
         }
     }
+
+    pub fn range(&self, radius: i32) -> RangeInclusive<i32> {
+        match self.bounds {
+            DimensionBound::Infinity => -radius..=radius,
+            DimensionBound::ZeroBounded(n) if n < 0 => (n + 1).max(-radius)..=0,
+            DimensionBound::ZeroBounded(n) => 0..=(n - 1).min(radius),
+            DimensionBound::RangeBounded(a, b) => a.max(-radius)..=b.min(radius),
+        }
+    }
+
+    pub fn inverted(&self, equivalence: Equivalence) -> Self {
+        let bounds = match self.bounds {
+            DimensionBound::Infinity => DimensionBound::Infinity,
+            DimensionBound::ZeroBounded(n) => DimensionBound::ZeroBounded(-n),
+            DimensionBound::RangeBounded(a, b) => DimensionBound::RangeBounded(-b, -a),
+        };
+        Self {
+            ratio: self.ratio.pow_with(-1, equivalence),
+            bounds,
+        }
+    }
+}
+
+const SVG_UNIT: f64 = 60.;
+
+fn escape_comment(text: &str) -> String {
+    text.replace("--", "- -")
+}
+
+fn edges_between(coordinates: &[Vec<i32>]) -> Vec<(usize, usize)> {
+    let mut edges = vec![];
+    for (i, a) in coordinates.iter().enumerate() {
+        for (j, b) in coordinates.iter().enumerate().skip(i + 1) {
+            let distance: i32 = a.iter().zip(b.iter()).map(|(x, y)| (x - y).abs()).sum();
+            if distance == 1 {
+                edges.push((i, j));
+            }
+        }
+    }
+    edges
 }
 
 fn sign_preserving_mod(a: i32, b: i32) -> i32 {
     (a % b + b) % b
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bridge {
+    pub from: (Vec<i32>, Ratio),
+    pub to: (Vec<i32>, Ratio),
+    pub comma: Ratio,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Respelling {
+    pub coordinates: Vec<i32>,
+    pub stack: IntervalStack,
+    pub ratio: Ratio,
+    pub comma: Ratio,
+    pub complexity: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Region {
+    Radius(i32),
+    Ranges(Vec<RangeInclusive<i32>>),
+    Coordinates(Vec<Vec<i32>>),
+}
+
+impl From<i32> for Region {
+    fn from(radius: i32) -> Self {
+        Region::Radius(radius)
+    }
+}
+
+impl From<&Region> for Region {
+    fn from(region: &Region) -> Self {
+        region.clone()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Lattice {
     pub dimensions: Vec<LatticeDimension>,
+    pub equivalence: Equivalence,
+    pub regions: BTreeMap<String, Region>,
+    pub metadata: Metadata,
 }
 
 impl Lattice {
     pub fn new(dimensions: Vec<LatticeDimension>) -> Self {
-        Self { dimensions }
+        Self::with_equivalence(dimensions, Equivalence::Octave)
+    }
+
+    pub fn with_equivalence(dimensions: Vec<LatticeDimension>, equivalence: Equivalence) -> Self {
+        Self {
+            dimensions,
+            equivalence,
+            regions: BTreeMap::new(),
+            metadata: Metadata::new(),
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn define_region(&mut self, name: &str, region: Region) {
+        self.regions.insert(name.to_string(), region);
+    }
+
+    pub fn region(&self, name: &str) -> Option<&Region> {
+        self.regions.get(name)
+    }
+
+    pub fn canonicalize(&self) -> Self {
+        let mut dimensions: Vec<LatticeDimension> = self
+            .dimensions
+            .iter()
+            .map(|d| match d.ratio < Ratio::new(1, 1) {
+                true => d.inverted(self.equivalence),
+                false => *d,
+            })
+            .collect();
+        dimensions.sort_by_key(|d| (d.ratio.prime_limit(), d.ratio));
+        Self::with_equivalence(dimensions, self.equivalence)
     }
 
     pub fn at(&self, indices: Vec<i32>) -> Ratio {
         self.dimensions
             .iter()
             .zip(indices.iter())
-            .map(|(&dim, &index)| dim.ratio.pow(dim.resolve_index(index)))
+            .map(|(&dim, &index)| {
+                dim.ratio
+                    .pow_with(dim.resolve_index(index), self.equivalence)
+            })
             .fold(Ratio::new(1, 1), |e, acc| acc * e)
     }
+
+    pub fn stack(&self, indices: Vec<i32>) -> IntervalStack {
+        self.dimensions
+            .iter()
+            .zip(indices.iter())
+            .fold(IntervalStack::new(), |stack, (dim, &index)| {
+                stack.up(dim.ratio, dim.resolve_index(index))
+            })
+    }
+
+    /// Coordinates in ascending lexicographic order: the first dimension
+    /// varies slowest and each index runs from low to high.
+    pub fn coordinates(&self, radius: i32) -> Vec<Vec<i32>> {
+        self.dimensions.iter().fold(vec![vec![]], |coords, dim| {
+            coords
+                .iter()
+                .flat_map(|c| {
+                    dim.range(radius).map(move |i| {
+                        let mut c = c.clone();
+                        c.push(i);
+                        c
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Radius and range regions follow the order of [`Lattice::coordinates`].
+    /// Explicit coordinate lists keep the order they were given in. Ranges
+    /// and coordinates that do not match the number of dimensions yield no
+    /// points.
+    pub fn region_coordinates(&self, region: &Region) -> Vec<Vec<i32>> {
+        match region {
+            Region::Radius(radius) => self.coordinates(*radius),
+            Region::Ranges(ranges) if ranges.len() != self.dimensions.len() => vec![],
+            Region::Ranges(ranges) => self.dimensions.iter().zip(ranges.iter()).fold(
+                vec![vec![]],
+                |coords, (dim, range)| {
+                    let extent = range.start().abs().max(range.end().abs());
+                    let indices: Vec<i32> =
+                        dim.range(extent).filter(|i| range.contains(i)).collect();
+                    coords
+                        .iter()
+                        .flat_map(|c| {
+                            indices.iter().map(move |&i| {
+                                let mut c = c.clone();
+                                c.push(i);
+                                c
+                            })
+                        })
+                        .collect()
+                },
+            ),
+            Region::Coordinates(coordinates) => coordinates
+                .iter()
+                .filter(|c| c.len() == self.dimensions.len())
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Points in the order of [`Lattice::region_coordinates`].
+    pub fn points_in(&self, region: impl Into<Region>) -> Vec<(Vec<i32>, Ratio)> {
+        self.region_coordinates(&region.into())
+            .into_iter()
+            .map(|c| {
+                let r = self.at(c.clone());
+                (c, r)
+            })
+            .collect()
+    }
+
+    /// Points in the order of [`Lattice::region_coordinates`].
+    pub fn points_with_progress(
+        &self,
+        region: impl Into<Region>,
+        callback: &mut dyn FnMut(Progress) -> Control,
+    ) -> Result<Vec<(Vec<i32>, Ratio)>, Cancelled> {
+        let coordinates = self.region_coordinates(&region.into());
+        let total = coordinates.len();
+        let mut points = Vec::with_capacity(total);
+        for (i, c) in coordinates.into_iter().enumerate() {
+            let r = self.at(c.clone());
+            points.push((c, r));
+            report(callback, i + 1, total)?;
+        }
+        Ok(points)
+    }
+
+    pub fn to_scale(&self, region: impl Into<Region>) -> Scale {
+        Scale::with_equivalence(
+            self.points_in(region).into_iter().map(|(_, r)| r).collect(),
+            self.equivalence,
+        )
+    }
+
+    /// Points sorted by `order`; see [`SortOrder`].
+    pub fn points_sorted(
+        &self,
+        region: impl Into<Region>,
+        order: SortOrder,
+    ) -> Vec<(Vec<i32>, Ratio)> {
+        let mut points = self.points_in(region);
+        sort_points(&mut points, order, self.equivalence);
+        points
+    }
+
+    /// Points in the order of [`Lattice::coordinates`].
+    pub fn points(&self, radius: i32) -> Vec<(Vec<i32>, Ratio)> {
+        self.coordinates(radius)
+            .into_iter()
+            .map(|c| {
+                let r = self.at(c.clone());
+                (c, r)
+            })
+            .collect()
+    }
+
+    pub fn export_ndjson(
+        &self,
+        region: impl Into<Region>,
+        writer: &mut impl Write,
+    ) -> io::Result<()> {
+        let list = |values: Vec<i32>| {
+            let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+            format!("[{}]", values.join(","))
+        };
+        for (coordinates, ratio) in self.points_in(region) {
+            writeln!(
+                writer,
+                "{{\"coordinates\":{},\"ratio\":\"{}\",\"numer\":{},\"denom\":{},\"monzo\":{},\"cents\":{},\"tenney_height\":{},\"weil_height\":{},\"benedetti_height\":{}}}",
+                list(coordinates),
+                ratio,
+                ratio.numer,
+                ratio.denom,
+                ratio.monzo().map_or("null".to_string(), list),
+                ratio.cents(),
+                ratio.tenney_height(),
+                (ratio.numer.max(ratio.denom) as f64).log2(),
+                ratio.numer as i64 * ratio.denom as i64,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn edges(&self, radius: i32) -> Vec<(usize, usize)> {
+        edges_between(&self.coordinates(radius))
+    }
+
+    pub fn to_dot(&self, region: impl Into<Region>, scheme: &dyn ColorScheme) -> String {
+        let points = self.points_in(region);
+        let edges = edges_between(&points.iter().map(|(c, _)| c.clone()).collect::<Vec<_>>());
+        let mut out = self.metadata.to_comments("//");
+        out.push_str("graph lattice {\n  node [style=filled];\n");
+        for (i, (_, ratio)) in points.iter().enumerate() {
+            let ratio = ratio.normalize_with(self.equivalence);
+            out.push_str(&format!(
+                "  n{i} [label=\"{ratio}\", fillcolor=\"{}\"];\n",
+                scheme.color(&ratio).hex()
+            ));
+        }
+        for (a, b) in edges {
+            out.push_str(&format!("  n{a} -- n{b};\n"));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    pub fn to_svg(&self, region: impl Into<Region>, scheme: &dyn ColorScheme) -> String {
+        let points = self.points_in(region);
+        let edges = edges_between(&points.iter().map(|(c, _)| c.clone()).collect::<Vec<_>>());
+        let n = self.dimensions.len().max(1) as f64;
+        let positions: Vec<(f64, f64)> = points
+            .iter()
+            .map(|(c, _)| {
+                c.iter().enumerate().fold((0., 0.), |(x, y), (k, &i)| {
+                    let angle = std::f64::consts::PI * k as f64 / n;
+                    (x + i as f64 * angle.cos(), y - i as f64 * angle.sin())
+                })
+            })
+            .collect();
+
+        let extent = |f: fn(&(f64, f64)) -> f64| {
+            positions
+                .iter()
+                .map(f)
+                .fold((0_f64, 0_f64), |(lo, hi), v| (lo.min(v), hi.max(v)))
+        };
+        let ((x0, x1), (y0, y1)) = (extent(|p| p.0), extent(|p| p.1));
+        let (unit, margin) = (SVG_UNIT, SVG_UNIT / 2.);
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+            (x1 - x0) * unit + 2. * margin,
+            (y1 - y0) * unit + 2. * margin
+        );
+        for line in self.metadata.to_comments("").lines() {
+            out.push_str(&format!("  <!--{}-->\n", escape_comment(line)));
+        }
+        let place = |(x, y): (f64, f64)| ((x - x0) * unit + margin, (y - y0) * unit + margin);
+
+        for (a, b) in edges {
+            let ((xa, ya), (xb, yb)) = (place(positions[a]), place(positions[b]));
+            out.push_str(&format!(
+                "  <line x1=\"{xa:.1}\" y1=\"{ya:.1}\" x2=\"{xb:.1}\" y2=\"{yb:.1}\" stroke=\"#999999\"/>\n"
+            ));
+        }
+        for ((_, ratio), position) in points.iter().zip(positions.iter()) {
+            let ratio = ratio.normalize_with(self.equivalence);
+            let (x, y) = place(*position);
+            out.push_str(&format!(
+                "  <circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"{:.1}\" fill=\"{}\"/>\n",
+                unit / 4.,
+                scheme.color(&ratio).hex()
+            ));
+            out.push_str(&format!(
+                "  <text x=\"{x:.1}\" y=\"{y:.1}\" text-anchor=\"middle\" dominant-baseline=\"middle\" font-size=\"10\">{ratio}</text>\n"
+            ));
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    pub fn reflect(&self, coordinates: &[i32], axis: &[i32]) -> Vec<i32> {
+        coordinates
+            .iter()
+            .zip(axis.iter())
+            .map(|(c, a)| a - c)
+            .collect()
+    }
+
+    pub fn search<S, P, Q>(
+        &self,
+        start: Vec<i32>,
+        score: S,
+        predicate: P,
+        prune: Q,
+        limit: usize,
+    ) -> LatticeSearch<'_, S, P, Q>
+    where
+        S: FnMut(&[i32], Ratio) -> f64,
+        P: FnMut(&[i32], Ratio) -> bool,
+        Q: FnMut(&[i32], Ratio) -> bool,
+    {
+        let mut search = LatticeSearch {
+            lattice: self,
+            score,
+            predicate,
+            prune,
+            limit,
+            visited: 0,
+            seen: BTreeSet::new(),
+            queue: BinaryHeap::new(),
+        };
+        if start.len() == self.dimensions.len() {
+            search.enqueue(start);
+        }
+        search
+    }
+
+    pub fn find(&self, target: Ratio, radius: i32) -> Vec<Vec<i32>> {
+        let target = target.normalize_with(self.equivalence);
+        self.points(radius)
+            .into_iter()
+            .filter(|(_, r)| r.normalize_with(self.equivalence) == target)
+            .map(|(c, _)| c)
+            .collect()
+    }
+
+    pub fn respellings(
+        &self,
+        target: Ratio,
+        radius: i32,
+        tolerance: impl Into<Tolerance>,
+    ) -> Vec<Respelling> {
+        let tolerance = tolerance.into();
+        let target = target.normalize_with(self.equivalence);
+        let mut respellings: Vec<Respelling> = self
+            .points(radius)
+            .into_iter()
+            .map(|(c, r)| (c, r.normalize_with(self.equivalence)))
+            .filter(|(_, r)| {
+                tolerance.accepts(self.equivalence.distance(r.cents(), target.cents()))
+            })
+            .map(|(coordinates, ratio)| {
+                let comma = match self.equivalence.equave() {
+                    Some(e) if ratio.cents() - target.cents() > e.cents() / 2. => {
+                        ratio / (target * e)
+                    }
+                    Some(e) if target.cents() - ratio.cents() > e.cents() / 2. => {
+                        ratio * e / target
+                    }
+                    _ => ratio / target,
+                };
+                Respelling {
+                    stack: self.stack(coordinates.clone()),
+                    coordinates,
+                    ratio,
+                    comma,
+                    complexity: ratio.tenney_height(),
+                }
+            })
+            .collect();
+        respellings.sort_by(|a, b| {
+            a.complexity
+                .total_cmp(&b.complexity)
+                .then(a.coordinates.cmp(&b.coordinates))
+        });
+        respellings
+    }
+
+    pub fn bridges(&self, radius: i32, tolerance: impl Into<Tolerance>) -> Vec<Bridge> {
+        let tolerance = tolerance.into();
+        let points: Vec<(Vec<i32>, Ratio)> = self
+            .points(radius)
+            .into_iter()
+            .map(|(c, r)| (c, r.normalize_with(self.equivalence)))
+            .collect();
+        let equave = self.equivalence.equave();
+        let equave_cents = self.equivalence.equave_cents();
+
+        let mut bridges = vec![];
+        for (i, (ca, a)) in points.iter().enumerate() {
+            for (cb, b) in points.iter().skip(i + 1) {
+                let (low, high) = if a < b { (a, b) } else { (b, a) };
+                let distance = high.cents() - low.cents();
+                if !tolerance.accepts(self.equivalence.distance(high.cents(), low.cents())) {
+                    continue;
+                }
+                let comma = match (equave, equave_cents) {
+                    (Some(e), Some(c)) if distance > c / 2. => *low * e / *high,
+                    _ => *high / *low,
+                };
+                let equave_prime = equave.map(|e| e.numer);
+                if comma
+                    .primes()
+                    .iter()
+                    .filter(|&&p| Some(p) != equave_prime)
+                    .count()
+                    > 1
+                {
+                    bridges.push(Bridge {
+                        from: (ca.clone(), *a),
+                        to: (cb.clone(), *b),
+                        comma,
+                    });
+                }
+            }
+        }
+        bridges.sort_by_key(|b| b.comma);
+        bridges
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchHit {
+    pub coordinates: Vec<i32>,
+    pub ratio: Ratio,
+    pub score: f64,
+}
+
+struct Candidate(SearchHit);
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .score
+            .total_cmp(&other.0.score)
+            .then_with(|| self.0.coordinates.cmp(&other.0.coordinates))
+    }
+}
+
+pub struct LatticeSearch<'a, S, P, Q> {
+    lattice: &'a Lattice,
+    score: S,
+    predicate: P,
+    prune: Q,
+    limit: usize,
+    visited: usize,
+    seen: BTreeSet<Vec<i32>>,
+    queue: BinaryHeap<Reverse<Candidate>>,
+}
+
+impl<S, P, Q> LatticeSearch<'_, S, P, Q>
+where
+    S: FnMut(&[i32], Ratio) -> f64,
+    P: FnMut(&[i32], Ratio) -> bool,
+    Q: FnMut(&[i32], Ratio) -> bool,
+{
+    pub fn visited(&self) -> usize {
+        self.visited
+    }
+
+    fn enqueue(&mut self, coordinates: Vec<i32>) {
+        if !self.seen.insert(coordinates.clone()) {
+            return;
+        }
+        let ratio = self.lattice.at(coordinates.clone());
+        if (self.prune)(&coordinates, ratio) {
+            return;
+        }
+        let score = (self.score)(&coordinates, ratio);
+        self.queue.push(Reverse(Candidate(SearchHit {
+            coordinates,
+            ratio,
+            score,
+        })));
+    }
+}
+
+impl<S, P, Q> Iterator for LatticeSearch<'_, S, P, Q>
+where
+    S: FnMut(&[i32], Ratio) -> f64,
+    P: FnMut(&[i32], Ratio) -> bool,
+    Q: FnMut(&[i32], Ratio) -> bool,
+{
+    type Item = SearchHit;
+
+    fn next(&mut self) -> Option<SearchHit> {
+        while self.visited < self.limit {
+            let Reverse(Candidate(hit)) = self.queue.pop()?;
+            self.visited += 1;
+            for (k, dim) in self.lattice.dimensions.iter().enumerate() {
+                for delta in [-1, 1] {
+                    let index = hit.coordinates[k] + delta;
+                    if dim.range(i32::MAX).contains(&index) {
+                        let mut next = hit.coordinates.clone();
+                        next[k] = index;
+                        self.enqueue(next);
+                    }
+                }
+            }
+            if (self.predicate)(&hit.coordinates, hit.ratio) {
+                return Some(hit);
+            }
+        }
+        None
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::color::{ByEdoDeviation, ByPrimeLimit};
+    use crate::progress::throttle;
     use DimensionBound::*;
 
+    #[test]
+    fn search_hunts_commas_best_first() {
+        let lattice = Lattice::new(vec![
+            LatticeDimension {
+                ratio: Ratio::new(3, 2),
+                bounds: DimensionBound::Infinity,
+            },
+            LatticeDimension {
+                ratio: Ratio::new(5, 4),
+                bounds: DimensionBound::Infinity,
+            },
+        ]);
+        let equivalence = lattice.equivalence;
+
+        let commas: Vec<SearchHit> = lattice
+            .search(
+                vec![0, 0],
+                |_, r| r.normalize().tenney_height(),
+                |c, r| c != [0, 0] && equivalence.distance(r.cents(), 0.) < 50.,
+                |_, r| r.normalize().tenney_height() > 25.,
+                10_000,
+            )
+            .take(3)
+            .collect();
+
+        assert_eq!(commas[0].ratio.normalize(), Ratio::new(81, 80));
+        assert_eq!(commas[1].ratio.normalize(), Ratio::new(125, 64));
+        assert!(commas.windows(2).all(|w| w[0].score <= w[1].score));
+    }
+
+    #[test]
+    fn search_respects_limit_and_bounds() {
+        let lattice = Lattice::new(vec![LatticeDimension {
+            ratio: Ratio::new(3, 2),
+            bounds: DimensionBound::ZeroBounded(4),
+        }]);
+
+        let mut search =
+            lattice.search(vec![0], |c, _| c[0] as f64, |_, _| true, |_, _| false, 100);
+        let hits: Vec<Vec<i32>> = search.by_ref().map(|h| h.coordinates).collect();
+
+        assert_eq!(hits, vec![vec![0], vec![1], vec![2], vec![3]]);
+        assert_eq!(search.visited(), 4);
+        assert_eq!(
+            lattice
+                .search(vec![0], |_, _| 0., |_, _| true, |_, _| false, 2)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn enumeration_progress() {
+        let lattice = five_limit();
+        let mut seen = vec![];
+        let points = lattice
+            .points_with_progress(
+                2,
+                &mut throttle(10, |p| {
+                    seen.push(p.done);
+                    Control::Continue
+                }),
+            )
+            .unwrap();
+
+        assert_eq!(points, lattice.points(2));
+        assert_eq!(seen, vec![10, 20, 25]);
+        assert!(lattice
+            .points_with_progress(2, &mut |_| Control::Cancel)
+            .is_err());
+    }
+
+    #[test]
+    fn enumeration_order_is_stable() {
+        let lattice = five_limit();
+        let coordinates: Vec<Vec<i32>> = lattice.points(1).into_iter().map(|(c, _)| c).collect();
+
+        assert_eq!(
+            coordinates,
+            vec![
+                vec![-1, -1],
+                vec![-1, 0],
+                vec![-1, 1],
+                vec![0, -1],
+                vec![0, 0],
+                vec![0, 1],
+                vec![1, -1],
+                vec![1, 0],
+                vec![1, 1],
+            ]
+        );
+        let by_cents = lattice.points_sorted(1, SortOrder::Cents);
+        assert_eq!(by_cents[0].1, Ratio::new(1, 1));
+        assert_eq!(by_cents[1].0, vec![-1, -1]);
+    }
+
+    #[test]
+    fn metadata_in_exports() {
+        let lattice = five_limit().with_metadata(Metadata::new().with("author", "Ben Johnston"));
+
+        assert!(lattice
+            .to_dot(1, &ByPrimeLimit)
+            .starts_with("// @META author Ben Johnston\ngraph lattice {"));
+        assert!(lattice
+            .to_svg(1, &ByPrimeLimit)
+            .contains("<!-- @META author Ben Johnston-->"));
+    }
+
+    #[test]
+    fn reflect_about_axis() {
+        let lattice = five_limit();
+        let major_third = vec![0, 1];
+        let fifth = vec![1, 0];
+        let reflected = lattice.reflect(&major_third, &fifth);
+
+        assert_eq!(reflected, vec![1, -1]);
+        assert_eq!(lattice.at(reflected).normalize(), Ratio::new(6, 5));
+    }
+
+    #[test]
+    fn named_regions() {
+        let mut lattice = Lattice::new(vec![
+            LatticeDimension {
+                ratio: Ratio::new(3, 2),
+                bounds: DimensionBound::Infinity,
+            },
+            LatticeDimension {
+                ratio: Ratio::new(5, 4),
+                bounds: DimensionBound::Infinity,
+            },
+        ]);
+        lattice.define_region("otonal", Region::Ranges(vec![0..=1, 0..=1]));
+        lattice.define_region(
+            "triad",
+            Region::Coordinates(vec![vec![0, 0], vec![0, 1], vec![1, 0]]),
+        );
+
+        let otonal = lattice.region("otonal").unwrap();
+        assert_eq!(lattice.points_in(otonal).len(), 4);
+        assert_eq!(
+            lattice.to_scale(lattice.region("triad").unwrap()).ratios(),
+            Some(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)])
+        );
+        assert_eq!(
+            lattice
+                .to_dot(otonal, &ByPrimeLimit)
+                .matches(" -- ")
+                .count(),
+            4
+        );
+        assert!(lattice.region("missing").is_none());
+        assert!(lattice.points_in(Region::Ranges(vec![0..=1])).is_empty());
+        assert!(lattice
+            .points_in(Region::Ranges(vec![0..=1, 0..=1, 0..=1]))
+            .is_empty());
+    }
+
+    #[test]
+    fn export_ndjson_records() {
+        let lattice = Lattice::new(vec![LatticeDimension {
+            ratio: Ratio::new(3, 2),
+            bounds: DimensionBound::Infinity,
+        }]);
+        let mut out = vec![];
+        lattice.export_ndjson(1, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[2],
+            format!(
+                "{{\"coordinates\":[1],\"ratio\":\"3/2\",\"numer\":3,\"denom\":2,\"monzo\":[-1,1],\"cents\":{},\"tenney_height\":{},\"weil_height\":{},\"benedetti_height\":6}}",
+                Ratio::new(3, 2).cents(),
+                6_f64.log2(),
+                3_f64.log2()
+            )
+        );
+        assert!(lines[0].starts_with("{\"coordinates\":[-1],\"ratio\":\"4/3\""));
+    }
+
     #[test]
     fn resolve_unbounded_dimension() {
         let dim = LatticeDimension {
@@ -109,6 +872,19 @@ mod tests {
         assert_eq!(dim.resolve_index(-2), 0);
     }
 
+    #[test]
+    fn dimension_ranges() {
+        let dim = |bounds| LatticeDimension {
+            ratio: Ratio::new(3, 2),
+            bounds,
+        };
+
+        assert_eq!(dim(Infinity).range(2), -2..=2);
+        assert_eq!(dim(ZeroBounded(3)).range(5), 0..=2);
+        assert_eq!(dim(ZeroBounded(-3)).range(5), -2..=0);
+        assert_eq!(dim(RangeBounded(-1, 4)).range(2), -1..=2);
+    }
+
     #[test]
     fn one_dimensional_unbounded_lattice() {
         let dim = LatticeDimension {
@@ -190,4 +966,267 @@ mod tests {
 
         assert_eq!(lattice.at(vec![1, 1]), Ratio::new(15, 8))
     }
+
+    #[test]
+    fn lattice_points() {
+        let lattice = Lattice::new(vec![
+            LatticeDimension {
+                ratio: Ratio::new(3, 2),
+                bounds: Infinity,
+            },
+            LatticeDimension {
+                ratio: Ratio::new(5, 4),
+                bounds: ZeroBounded(2),
+            },
+        ]);
+
+        let points = lattice.points(1);
+
+        assert_eq!(points.len(), 6);
+        assert_eq!(points[0], (vec![-1, 0], Ratio::new(4, 3)));
+        assert_eq!(points[5], (vec![1, 1], Ratio::new(15, 8)));
+    }
+
+    #[test]
+    fn canonicalize_orders_dimensions_by_prime() {
+        let lattice = Lattice::new(vec![
+            LatticeDimension {
+                ratio: Ratio::new(5, 4),
+                bounds: Infinity,
+            },
+            LatticeDimension {
+                ratio: Ratio::new(2, 3),
+                bounds: RangeBounded(-1, 2),
+            },
+        ]);
+
+        let canonical = lattice.canonicalize();
+
+        assert_eq!(canonical.dimensions[0].ratio, Ratio::new(3, 2));
+        assert!(matches!(
+            canonical.dimensions[0].bounds,
+            RangeBounded(-2, 1)
+        ));
+        assert_eq!(canonical.dimensions[1].ratio, Ratio::new(5, 4));
+        assert_eq!(canonical.canonicalize(), canonical);
+        assert_eq!(
+            canonical.at(vec![-2, 1]).normalize(),
+            lattice.at(vec![1, 2]).normalize()
+        );
+    }
+
+    #[test]
+    fn canonicalize_flips_zero_bounds() {
+        let dim = LatticeDimension {
+            ratio: Ratio::new(4, 5),
+            bounds: ZeroBounded(3),
+        };
+        let lattice = Lattice::new(vec![dim]);
+
+        let canonical = lattice.canonicalize();
+
+        assert_eq!(canonical.dimensions[0].ratio, Ratio::new(5, 4));
+        for i in 0..3 {
+            assert_eq!(
+                canonical.at(vec![-i]).normalize(),
+                lattice.at(vec![i]).normalize()
+            );
+        }
+    }
+
+    #[test]
+    fn lattice_edges_connect_neighbours() {
+        let lattice = Lattice::new(vec![
+            LatticeDimension {
+                ratio: Ratio::new(3, 2),
+                bounds: RangeBounded(0, 1),
+            },
+            LatticeDimension {
+                ratio: Ratio::new(5, 4),
+                bounds: RangeBounded(0, 1),
+            },
+        ]);
+
+        assert_eq!(lattice.edges(1), vec![(0, 1), (0, 2), (1, 3), (2, 3)]);
+    }
+
+    #[test]
+    fn dot_export_uses_color_scheme() {
+        let lattice = Lattice::new(vec![LatticeDimension {
+            ratio: Ratio::new(5, 4),
+            bounds: ZeroBounded(2),
+        }]);
+
+        let dot = lattice.to_dot(1, &ByPrimeLimit);
+
+        assert_eq!(
+            dot,
+            format!(
+                "graph lattice {{\n  node [style=filled];\n  n0 [label=\"1/1\", fillcolor=\"{}\"];\n  n1 [label=\"5/4\", fillcolor=\"{}\"];\n  n0 -- n1;\n}}\n",
+                ByPrimeLimit.color(&Ratio::new(1, 1)).hex(),
+                ByPrimeLimit.color(&Ratio::new(5, 4)).hex()
+            )
+        );
+    }
+
+    #[test]
+    fn svg_export_draws_points_and_edges() {
+        let lattice = Lattice::new(vec![
+            LatticeDimension {
+                ratio: Ratio::new(3, 2),
+                bounds: Infinity,
+            },
+            LatticeDimension {
+                ratio: Ratio::new(5, 4),
+                bounds: Infinity,
+            },
+        ]);
+
+        let svg = lattice.to_svg(1, &ByEdoDeviation::default());
+
+        assert!(svg.starts_with("<svg "));
+        assert_eq!(svg.matches("<circle").count(), 9);
+        assert_eq!(svg.matches("<line").count(), 12);
+        assert!(svg.contains(">15/8</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn stack_records_derivation() {
+        let lattice = Lattice::new(vec![
+            LatticeDimension {
+                ratio: Ratio::new(3, 2),
+                bounds: Infinity,
+            },
+            LatticeDimension {
+                ratio: Ratio::new(5, 4),
+                bounds: ZeroBounded(2),
+            },
+        ]);
+
+        let stack = lattice.stack(vec![2, 3]);
+
+        assert_eq!(stack.to_string(), "3/2^2 * 5/4");
+        assert_eq!(
+            stack.ratio_with(Equivalence::Octave),
+            lattice.at(vec![2, 3]).normalize()
+        );
+    }
+
+    fn five_limit() -> Lattice {
+        Lattice::new(vec![
+            LatticeDimension {
+                ratio: Ratio::new(3, 2),
+                bounds: Infinity,
+            },
+            LatticeDimension {
+                ratio: Ratio::new(5, 4),
+                bounds: Infinity,
+            },
+        ])
+    }
+
+    #[test]
+    fn find_exact_coordinates() {
+        let lattice = five_limit();
+
+        assert_eq!(lattice.find(Ratio::new(15, 8), 2), vec![vec![1, 1]]);
+        assert_eq!(lattice.find(Ratio::new(45, 32), 2), vec![vec![2, 1]]);
+        assert!(lattice.find(Ratio::new(7, 4), 2).is_empty());
+    }
+
+    #[test]
+    fn respellings_ranked_by_complexity() {
+        let lattice = five_limit();
+
+        let respellings = lattice.respellings(Ratio::new(5, 4), 4, 25.);
+
+        assert_eq!(respellings[0].ratio, Ratio::new(5, 4));
+        assert_eq!(respellings[0].comma, Ratio::new(1, 1));
+        assert_eq!(respellings[0].stack.to_string(), "5/4");
+
+        let pythagorean = respellings
+            .iter()
+            .find(|r| r.ratio == Ratio::new(81, 64))
+            .unwrap();
+        assert_eq!(pythagorean.coordinates, vec![4, 0]);
+        assert_eq!(pythagorean.comma, Ratio::new(81, 80));
+        assert!(respellings
+            .windows(2)
+            .all(|w| w[0].complexity <= w[1].complexity));
+    }
+
+    #[test]
+    fn respellings_across_the_octave() {
+        let respellings = five_limit().respellings(Ratio::new(1, 1), 3, 45.);
+
+        let diesis = respellings
+            .iter()
+            .find(|r| r.ratio == Ratio::new(125, 64))
+            .unwrap();
+        assert_eq!(diesis.comma, Ratio::new(125, 128));
+    }
+
+    #[test]
+    fn lattice_without_equivalence() {
+        let lattice = Lattice::with_equivalence(
+            vec![LatticeDimension {
+                ratio: Ratio::new(3, 2),
+                bounds: Infinity,
+            }],
+            Equivalence::None,
+        );
+
+        assert_eq!(lattice.at(vec![-2]), Ratio::new(4, 9));
+        assert_eq!(lattice.at(vec![2]), Ratio::new(9, 4));
+        assert!(lattice.to_dot(1, &ByPrimeLimit).contains("label=\"2/3\""));
+        assert_eq!(lattice.canonicalize().dimensions[0].ratio, Ratio::new(3, 2));
+    }
+
+    #[test]
+    fn tritave_lattice_points() {
+        let lattice = Lattice::with_equivalence(
+            vec![LatticeDimension {
+                ratio: Ratio::new(5, 3),
+                bounds: Infinity,
+            }],
+            Equivalence::Tritave,
+        );
+
+        assert_eq!(lattice.at(vec![-1]), Ratio::new(9, 5));
+        assert!(lattice.to_dot(2, &ByPrimeLimit).contains("label=\"25/9\""));
+    }
+
+    #[test]
+    fn syntonic_comma_bridges_three_and_five() {
+        let lattice = Lattice::new(vec![
+            LatticeDimension {
+                ratio: Ratio::new(3, 2),
+                bounds: Infinity,
+            },
+            LatticeDimension {
+                ratio: Ratio::new(5, 4),
+                bounds: Infinity,
+            },
+        ]);
+
+        let bridges = lattice.bridges(4, 22.);
+
+        assert_eq!(bridges[0].comma, Ratio::new(32805, 32768));
+        assert!(bridges.contains(&Bridge {
+            from: (vec![0, 1], Ratio::new(5, 4)),
+            to: (vec![4, 0], Ratio::new(81, 64)),
+            comma: Ratio::new(81, 80),
+        }));
+    }
+
+    #[test]
+    fn bridges_ignore_same_prime_pairs() {
+        let lattice = Lattice::new(vec![LatticeDimension {
+            ratio: Ratio::new(3, 2),
+            bounds: Infinity,
+        }]);
+
+        assert!(lattice.bridges(6, 25.).is_empty());
+    }
 }
@@ -1,2 +1,50 @@
+pub mod analysis;
+pub mod audio;
+pub mod audit;
+pub mod chord;
+pub mod color;
+pub mod comparison;
+pub mod consonance;
+pub mod degree;
+pub mod diagnostics;
+pub mod diamond;
+pub mod dual;
+pub mod equivalence;
+pub mod expression;
+pub mod instrument;
+pub mod interval_stack;
+pub mod keyboard;
 pub mod lattice;
+pub mod lumatone;
+pub mod measurement;
+pub mod metadata;
+pub mod midi;
+pub mod mos;
+pub mod mts;
+pub mod musicxml;
+pub mod order;
+pub mod pitch;
+pub mod pitch_class_set;
+pub mod prelude;
+pub mod primes;
+pub mod progress;
+pub mod progression;
 pub mod ratio;
+pub mod ratio_list;
+pub mod reference;
+pub mod report;
+pub mod rng;
+pub mod scala;
+pub mod scale;
+pub mod session;
+pub mod spectral;
+pub mod spectrum;
+pub mod standard;
+pub mod suggestion;
+pub mod temperament;
+pub mod tolerance;
+pub mod transcription;
+pub mod tun;
+pub mod tuning_table;
+pub mod walk;
+pub mod well_temperament;
@@ -0,0 +1,58 @@
+use crate::color::{Color, ColorScheme};
+use crate::scale::Scale;
+
+pub const BOARDS: usize = 5;
+pub const KEYS_PER_BOARD: usize = 56;
+
+const NOTE_ON_NOTE_OFF: u8 = 1;
+const INEXACT: Color = Color::new(0x8c, 0x8c, 0x8c);
+
+pub fn to_ltn(scale: &Scale, scheme: &dyn ColorScheme) -> String {
+    let mut out = String::new();
+    for board in 0..BOARDS {
+        out.push_str(&format!("[Board{board}]\n"));
+        for key in 0..KEYS_PER_BOARD {
+            let step = board * KEYS_PER_BOARD + key;
+            let color = match scale.degrees.get(step % scale.len().max(1)) {
+                Some(degree) => degree.ratio().map_or(INEXACT, |r| scheme.color(&r)),
+                None => INEXACT,
+            };
+            out.push_str(&format!(
+                "Key_{key}={}\nChan_{key}={}\nCol_{key}={}\nKTyp_{key}={NOTE_ON_NOTE_OFF}\n",
+                step % 128,
+                step / 128 + 1,
+                &color.hex()[1..]
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::ByPrimeLimit;
+    use crate::ratio::Ratio;
+
+    #[test]
+    fn keys_follow_the_scale() {
+        let scale = Scale::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+        let scheme = ByPrimeLimit;
+        let ltn = to_ltn(&scale, &scheme);
+        let fifth = &scheme.color(&Ratio::new(3, 2)).hex()[1..];
+
+        assert!(ltn.starts_with("[Board0]\nKey_0=0\nChan_0=1\n"));
+        assert!(ltn.contains(&format!("Key_2=2\nChan_2=1\nCol_2={fifth}\nKTyp_2=1\n")));
+        assert!(ltn.contains("[Board2]\nKey_0=112\nChan_0=1\n"));
+        assert!(ltn.contains("[Board3]\nKey_0=40\nChan_0=2\n"));
+        assert_eq!(ltn.matches("KTyp_").count(), BOARDS * KEYS_PER_BOARD);
+    }
+
+    #[test]
+    fn cents_degrees_are_grey() {
+        let scale = Scale::new(vec![0., 250.]);
+        let ltn = to_ltn(&scale, &ByPrimeLimit);
+
+        assert!(ltn.contains("Col_1=8c8c8c\n"));
+    }
+}
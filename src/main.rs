@@ -0,0 +1,65 @@
+use std::path::Path;
+use std::process::ExitCode;
+
+use partch::analysis::{analyze_dir, to_csv, to_json, AnalysisError};
+use partch::expression::eval;
+
+const USAGE: &str = "usage: partch analyze <dir> [--json]\n       partch eval <expression>";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(|a| a.as_str()) {
+        Some("analyze") => analyze(&args[1..]),
+        Some("eval") if args.len() > 1 => evaluate(&args[1..].join(" ")),
+        _ => {
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn analyze(args: &[String]) -> ExitCode {
+    let json = args.iter().any(|a| a == "--json");
+    let dir = match args.iter().find(|a| !a.starts_with("--")) {
+        Some(dir) => dir,
+        None => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let results = match analyze_dir(Path::new(dir)) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("{dir}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut summaries = vec![];
+    for result in results {
+        match result {
+            Ok(summary) => summaries.push(summary),
+            Err(AnalysisError::Io(path, e)) => eprintln!("{}: {e}", path.display()),
+            Err(AnalysisError::Scala(path, e)) => eprintln!("{}: {e}", path.display()),
+        }
+    }
+
+    match json {
+        true => print!("{}", to_json(&summaries)),
+        false => print!("{}", to_csv(&summaries)),
+    }
+    ExitCode::SUCCESS
+}
+
+fn evaluate(expression: &str) -> ExitCode {
+    match eval(expression) {
+        Ok(value) => {
+            println!("{value} ({:.3} cents)", value.cents());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{expression}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::comparison::signed_distance;
+use crate::degree::Degree;
+use crate::metadata::Metadata;
+use crate::report::{Report, Table};
+use crate::scale::Scale;
+use crate::tolerance::Tolerance;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MeasurementError {
+    InvalidLine { line: usize, text: String },
+    InvalidFrequency { line: usize, frequency: f64 },
+}
+
+impl fmt::Display for MeasurementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MeasurementError::InvalidLine { line, text } => {
+                write!(f, "line {line}: invalid measurement: {text}")
+            }
+            MeasurementError::InvalidFrequency { line, frequency } => {
+                write!(f, "line {line}: invalid frequency: {frequency}")
+            }
+        }
+    }
+}
+
+impl Error for MeasurementError {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Measurement {
+    pub frequency: f64,
+    pub amplitude: f64,
+}
+
+impl Measurement {
+    pub fn new(frequency: f64, amplitude: f64) -> Self {
+        Self {
+            frequency,
+            amplitude,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.frequency.is_finite() && self.frequency > 0.
+    }
+
+    pub fn cents(&self, reference: f64) -> f64 {
+        1200. * (self.frequency / reference).log2()
+    }
+}
+
+pub fn parse_measurements(input: &str) -> Result<Vec<Measurement>, MeasurementError> {
+    let mut measurements = vec![];
+    let mut header_allowed = true;
+    for (i, line) in input.lines().enumerate() {
+        let text = line.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = text.split(',').map(str::trim).collect();
+        let values: Option<Vec<f64>> = fields.iter().map(|f| f.parse().ok()).collect();
+        let invalid = || MeasurementError::InvalidLine {
+            line: i + 1,
+            text: text.to_string(),
+        };
+        let measurement = match values.as_deref() {
+            Some([frequency]) => Measurement::new(*frequency, 1.),
+            Some([frequency, amplitude]) => Measurement::new(*frequency, *amplitude),
+            None if header_allowed => {
+                header_allowed = false;
+                continue;
+            }
+            _ => return Err(invalid()),
+        };
+        header_allowed = false;
+        if !measurement.is_valid() {
+            return Err(MeasurementError::InvalidFrequency {
+                line: i + 1,
+                frequency: measurement.frequency,
+            });
+        }
+        measurements.push(measurement);
+    }
+    Ok(measurements)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NoteDeviation {
+    pub measurement: Measurement,
+    pub index: usize,
+    pub target: Degree,
+    pub deviation: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeasuredComparison {
+    pub reference: f64,
+    pub tolerance: Tolerance,
+    pub deviations: Vec<NoteDeviation>,
+    pub unmatched: Vec<Measurement>,
+    pub metadata: Metadata,
+}
+
+impl MeasuredComparison {
+    pub fn max_deviation(&self) -> Option<f64> {
+        self.deviations
+            .iter()
+            .map(|d| d.deviation.abs())
+            .reduce(f64::max)
+    }
+
+    pub fn mean_deviation(&self) -> Option<f64> {
+        let weight: f64 = self
+            .deviations
+            .iter()
+            .map(|d| d.measurement.amplitude)
+            .sum();
+        (weight > 0.).then(|| {
+            self.deviations
+                .iter()
+                .map(|d| d.deviation.abs() * d.measurement.amplitude)
+                .sum::<f64>()
+                / weight
+        })
+    }
+}
+
+pub fn compare_measurements(
+    scale: &Scale,
+    measurements: &[Measurement],
+    reference: f64,
+    tolerance: impl Into<Tolerance>,
+) -> MeasuredComparison {
+    let tolerance = tolerance.into();
+    let targets = scale.cents();
+    let equave = scale.equivalence.equave_cents();
+    let mut deviations = vec![];
+    let mut unmatched = vec![];
+    for &measurement in measurements {
+        let measured = measurement.cents(reference);
+        let nearest = targets
+            .iter()
+            .enumerate()
+            .filter(|_| measurement.is_valid())
+            .map(|(i, t)| (i, signed_distance(*t, measured, equave)))
+            .min_by(|a, b| a.1.abs().total_cmp(&b.1.abs()));
+        match nearest {
+            Some((index, deviation)) if tolerance.accepts(deviation) => {
+                deviations.push(NoteDeviation {
+                    measurement,
+                    index,
+                    target: scale.degrees[index],
+                    deviation,
+                })
+            }
+            _ => unmatched.push(measurement),
+        }
+    }
+    MeasuredComparison {
+        reference,
+        tolerance,
+        deviations,
+        unmatched,
+        metadata: scale.metadata.clone(),
+    }
+}
+
+impl Report for MeasuredComparison {
+    fn tables(&self) -> Vec<Table> {
+        let mut matched = Table::new(
+            "Measured deviations",
+            &["frequency", "amplitude", "index", "target", "deviation"],
+        );
+        for d in &self.deviations {
+            matched.push(vec![
+                d.measurement.frequency.into(),
+                d.measurement.amplitude.into(),
+                d.index.into(),
+                d.target.to_string().into(),
+                d.deviation.into(),
+            ]);
+        }
+
+        let mut unmatched = Table::new("Unmatched measurements", &["frequency", "amplitude"]);
+        for m in &self.unmatched {
+            unmatched.push(vec![m.frequency.into(), m.amplitude.into()]);
+        }
+
+        vec![matched, unmatched]
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        Some(&self.metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ratio::Ratio;
+    use crate::report::schema;
+
+    fn otonal() -> Scale {
+        Scale::new(vec![
+            Ratio::new(1, 1),
+            Ratio::new(5, 4),
+            Ratio::new(3, 2),
+            Ratio::new(7, 4),
+        ])
+    }
+
+    #[test]
+    fn parse_csv_measurements() {
+        let input = "frequency,amplitude\n# tuned 2026-10-01\n\n196.0, 0.8\n245.5\n";
+
+        assert_eq!(
+            parse_measurements(input),
+            Ok(vec![
+                Measurement::new(196., 0.8),
+                Measurement::new(245.5, 1.)
+            ])
+        );
+        assert_eq!(
+            parse_measurements("196,1\nabc,1\n"),
+            Err(MeasurementError::InvalidLine {
+                line: 2,
+                text: "abc,1".to_string()
+            })
+        );
+        assert_eq!(
+            parse_measurements("-3,1"),
+            Err(MeasurementError::InvalidFrequency {
+                line: 1,
+                frequency: -3.
+            })
+        );
+    }
+
+    #[test]
+    fn deviations_from_target_scale() {
+        let measurements = [
+            Measurement::new(200., 1.),
+            Measurement::new(251., 0.5),
+            Measurement::new(598., 1.),
+            Measurement::new(280., 1.),
+        ];
+
+        let comparison = compare_measurements(&otonal(), &measurements, 200., 20.);
+
+        assert_eq!(comparison.deviations.len(), 3);
+        assert_eq!(comparison.deviations[1].target, Ratio::new(5, 4));
+        assert!((comparison.deviations[1].deviation - 6.911).abs() < 1e-3);
+        assert_eq!(comparison.deviations[2].index, 2);
+        assert!(comparison.deviations[2].deviation < 0.);
+        assert_eq!(comparison.unmatched, vec![Measurement::new(280., 1.)]);
+        assert!(comparison.max_deviation().unwrap() > 5.);
+        assert!(comparison.render().contains("Unmatched measurements\n"));
+    }
+
+    #[test]
+    fn invalid_measurements_and_targets() {
+        let scale = Scale::new(vec![Degree::from(0.), Degree::Cents(f64::NAN)]);
+        let measurements = [Measurement::new(200., 1.), Measurement::new(0., 1.)];
+
+        let comparison = compare_measurements(&scale, &measurements, 200., 20.);
+
+        assert_eq!(comparison.deviations.len(), 1);
+        assert_eq!(comparison.deviations[0].index, 0);
+        assert_eq!(comparison.unmatched, vec![Measurement::new(0., 1.)]);
+    }
+
+    #[test]
+    fn measured_comparison_schema() {
+        assert_eq!(
+            schema(&compare_measurements(&otonal(), &[], 200., 20.)),
+            [
+                "Measured deviations: frequency, amplitude, index, target, deviation",
+                "Unmatched measurements: frequency, amplitude",
+            ]
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+
+use crate::report::{Report, Table};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Metadata {
+    pub entries: BTreeMap<String, String>,
+    pub tags: Vec<String>,
+}
+
+impl Metadata {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, key: &str, value: &str) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tag(tag);
+        self
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.entries.insert(key.to_string(), value.to_string());
+    }
+
+    pub fn tag(&mut self, tag: &str) {
+        if !self.tags.iter().any(|t| t == tag) {
+            self.tags.push(tag.to_string());
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(|v| v.as_str())
+    }
+
+    pub fn author(&self) -> Option<&str> {
+        self.get("author")
+    }
+
+    pub fn source(&self) -> Option<&str> {
+        self.get("source")
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.get("description")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty() && self.tags.is_empty()
+    }
+
+    pub fn to_comments(&self, prefix: &str) -> String {
+        let mut out = String::new();
+        for (key, value) in &self.entries {
+            out.push_str(&format!(
+                "{prefix} @META {} {}\n",
+                escape(key).replace(' ', "\\s"),
+                escape(value)
+            ));
+        }
+        for tag in &self.tags {
+            out.push_str(&format!("{prefix} @TAG {}\n", escape(tag)));
+        }
+        out
+    }
+
+    pub fn parse_comment(&mut self, comment: &str) -> bool {
+        let comment = comment.trim();
+        if let Some(rest) = comment.strip_prefix("@META ") {
+            let (key, value) = rest.trim().split_once(' ').unwrap_or((rest.trim(), ""));
+            self.set(&unescape(key), &unescape(value.trim()));
+            return true;
+        }
+        match comment.strip_prefix("@TAG ") {
+            Some(tag) => {
+                self.tag(&unescape(tag.trim()));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Report for Metadata {
+    fn tables(&self) -> Vec<Table> {
+        let mut table = Table::new("Metadata", &["key", "value"]);
+        for (key, value) in &self.entries {
+            table.push(vec![escape(key).into(), escape(value).into()]);
+        }
+        if !self.tags.is_empty() {
+            let tags: Vec<String> = self.tags.iter().map(|t| escape(t)).collect();
+            table.push(vec!["tags".into(), tags.join(", ").into()]);
+        }
+        vec![table]
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('r') => out.push('\r'),
+                Some('s') => out.push(' '),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            },
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::schema;
+
+    fn metadata() -> Metadata {
+        Metadata::new()
+            .with("author", "Harry Partch")
+            .with("source", "Genesis of a Music")
+            .with_tag("11-limit")
+            .with_tag("just")
+    }
+
+    #[test]
+    fn comments_round_trip() {
+        let comments = metadata().to_comments("!");
+        let mut parsed = Metadata::new();
+        for line in comments.lines() {
+            assert!(parsed.parse_comment(line.trim_start_matches('!')));
+        }
+
+        assert_eq!(parsed, metadata());
+        assert_eq!(parsed.author(), Some("Harry Partch"));
+        assert!(!parsed.parse_comment(" plain comment"));
+    }
+
+    #[test]
+    fn comments_escape_newlines() {
+        let metadata = Metadata::new()
+            .with("notes", "first line\nsecond \\ line")
+            .with("tuned by", "ear")
+            .with_tag("two\nlines");
+        let comments = metadata.to_comments("!");
+
+        assert_eq!(comments.lines().count(), 3);
+        assert!(comments.contains("! @META notes first line\\nsecond \\\\ line\n"));
+        let mut parsed = Metadata::new();
+        for line in comments.lines() {
+            parsed.parse_comment(line.trim_start_matches('!'));
+        }
+        assert_eq!(parsed, metadata);
+    }
+
+    #[test]
+    fn metadata_report() {
+        assert_eq!(
+            metadata().render(),
+            "Metadata\nkey     value\n------  ------------------\nauthor  Harry Partch\nsource  Genesis of a Music\ntags    11-limit, just\n"
+        );
+    }
+
+    #[test]
+    fn metadata_schema() {
+        assert_eq!(schema(&metadata()), ["Metadata: key, value"]);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::standard::PitchStandard;
+
+pub const TICKS_PER_BEAT: u16 = 480;
+pub const BEND_RANGE: f64 = 2.;
+
+const CHANNELS: [u8; 15] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 10, 11, 12, 13, 14, 15];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MidiEvent {
+    pub tick: u32,
+    pub data: Vec<u8>,
+}
+
+impl MidiEvent {
+    pub fn new(tick: u32, data: Vec<u8>) -> Self {
+        Self { tick, data }
+    }
+
+    pub fn note_on(tick: u32, channel: u8, note: u8, velocity: u8) -> Self {
+        Self::new(tick, vec![0x90 | channel, note, velocity])
+    }
+
+    pub fn note_off(tick: u32, channel: u8, note: u8) -> Self {
+        Self::new(tick, vec![0x80 | channel, note, 0])
+    }
+
+    pub fn sysex(tick: u32, message: &[u8]) -> Self {
+        let body = message.strip_prefix(&[0xf0]).unwrap_or(message);
+        let mut data = vec![0xf0];
+        data.extend(variable_length(body.len() as u32));
+        data.extend(body);
+        Self::new(tick, data)
+    }
+
+    pub fn pitch_bend(tick: u32, channel: u8, bend: u16) -> Self {
+        Self::new(
+            tick,
+            vec![0xe0 | channel, (bend & 0x7f) as u8, (bend >> 7) as u8],
+        )
+    }
+}
+
+pub fn channel(voice: usize) -> u8 {
+    CHANNELS[voice % CHANNELS.len()]
+}
+
+pub fn bend_range_events(channel: u8, semitones: u8) -> Vec<MidiEvent> {
+    [(101, 0), (100, 0), (6, semitones), (38, 0)]
+        .iter()
+        .map(|&(controller, value)| MidiEvent::new(0, vec![0xb0 | channel, controller, value]))
+        .collect()
+}
+
+pub fn note_and_bend(frequency: f64) -> (u8, u16) {
+    let standard = PitchStandard::A440;
+    let position = standard.note as f64 + 12. * (frequency / standard.frequency).log2();
+    let note = position.round().clamp(0., 127.);
+    let offset = ((position - note) / BEND_RANGE).clamp(-1., 1.);
+    let bend = (8192. + offset * 8192.).round().clamp(0., 16383.);
+    (note as u8, bend as u16)
+}
+
+pub fn write_smf(events: &[MidiEvent]) -> Vec<u8> {
+    let mut events = events.to_vec();
+    events.sort_by_key(|e| e.tick);
+
+    let mut track = vec![];
+    let mut last = 0;
+    for event in &events {
+        track.extend(variable_length(event.tick - last));
+        track.extend(&event.data);
+        last = event.tick;
+    }
+    track.extend([0x00, 0xff, 0x2f, 0x00]);
+
+    let mut bytes = b"MThd".to_vec();
+    bytes.extend(6_u32.to_be_bytes());
+    bytes.extend(0_u16.to_be_bytes());
+    bytes.extend(1_u16.to_be_bytes());
+    bytes.extend(TICKS_PER_BEAT.to_be_bytes());
+    bytes.extend(b"MTrk");
+    bytes.extend((track.len() as u32).to_be_bytes());
+    bytes.extend(track);
+    bytes
+}
+
+pub fn ticks(beats: f64) -> u32 {
+    (beats * TICKS_PER_BEAT as f64).round() as u32
+}
+
+fn variable_length(mut value: u32) -> Vec<u8> {
+    let mut bytes = vec![(value & 0x7f) as u8];
+    value >>= 7;
+    while value > 0 {
+        bytes.push((value & 0x7f) as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.reverse();
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variable_length_quantities() {
+        assert_eq!(variable_length(0), vec![0x00]);
+        assert_eq!(variable_length(0x7f), vec![0x7f]);
+        assert_eq!(variable_length(0x80), vec![0x81, 0x00]);
+        assert_eq!(variable_length(0x0fffffff), vec![0xff, 0xff, 0xff, 0x7f]);
+    }
+
+    #[test]
+    fn note_and_bend_for_just_third() {
+        assert_eq!(note_and_bend(440.), (69, 8192));
+
+        let (note, bend) = note_and_bend(261.63 * 1.25);
+        assert_eq!(note, 64);
+        assert!((bend as i32 - 8192 + 560).abs() < 5);
+    }
+
+    #[test]
+    fn sysex_event_carries_length() {
+        let event = MidiEvent::sysex(10, &[0xf0, 0x7e, 0x00, 0xf7]);
+
+        assert_eq!(event.data, vec![0xf0, 0x03, 0x7e, 0x00, 0xf7]);
+        assert_eq!(event.tick, 10);
+    }
+
+    #[test]
+    fn channels_skip_percussion() {
+        assert_eq!(channel(8), 8);
+        assert_eq!(channel(9), 10);
+        assert_eq!(channel(15), 0);
+    }
+
+    #[test]
+    fn write_single_note_file() {
+        let bytes = write_smf(&[
+            MidiEvent::note_off(480, 0, 60),
+            MidiEvent::note_on(0, 0, 60, 100),
+        ]);
+
+        assert_eq!(&bytes[..4], b"MThd");
+        assert_eq!(&bytes[12..14], &480_u16.to_be_bytes());
+        assert_eq!(&bytes[14..18], b"MTrk");
+        assert_eq!(
+            &bytes[22..],
+            &[0x00, 0x90, 60, 100, 0x83, 0x60, 0x80, 60, 0, 0x00, 0xff, 0x2f, 0x00]
+        );
+    }
+}
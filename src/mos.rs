@@ -0,0 +1,412 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EdoGenerator {
+    pub steps: i32,
+    pub edo: i32,
+}
+
+impl EdoGenerator {
+    pub fn new(steps: i32, edo: i32) -> Self {
+        Self { steps, edo }
+    }
+
+    pub fn fraction(&self) -> f64 {
+        self.steps as f64 / self.edo as f64
+    }
+
+    pub fn cents(&self, period: f64) -> f64 {
+        self.fraction() * period
+    }
+
+    pub fn mediant(&self, other: &Self) -> Self {
+        Self::new(self.steps + other.steps, self.edo + other.edo)
+    }
+
+    pub fn metallic_mediant(&self, other: &Self, n: u32) -> f64 {
+        let m = metallic_mean(n);
+        (self.steps as f64 + m * other.steps as f64) / (self.edo as f64 + m * other.edo as f64)
+    }
+
+    pub fn noble_mediant(&self, other: &Self) -> f64 {
+        self.metallic_mediant(other, 1)
+    }
+}
+
+pub fn metallic_mean(n: u32) -> f64 {
+    let n = n as f64;
+    (n + (n * n + 4.).sqrt()) / 2.
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mos {
+    pub generator: f64,
+    pub period: f64,
+    pub size: usize,
+}
+
+impl Mos {
+    pub fn new(generator: f64, period: f64, size: usize) -> Self {
+        Self {
+            generator,
+            period,
+            size,
+        }
+    }
+
+    pub fn metallic(a: EdoGenerator, b: EdoGenerator, n: u32, period: f64, size: usize) -> Self {
+        Self::new(a.metallic_mediant(&b, n) * period, period, size)
+    }
+
+    pub fn noble(a: EdoGenerator, b: EdoGenerator, period: f64, size: usize) -> Self {
+        Self::metallic(a, b, 1, period, size)
+    }
+
+    pub fn degrees(&self) -> Vec<f64> {
+        let mut degrees: Vec<f64> = (0..self.size)
+            .map(|k| (k as f64 * self.generator).rem_euclid(self.period))
+            .collect();
+        degrees.sort_by(f64::total_cmp);
+        degrees
+    }
+
+    pub fn step_sizes(&self) -> Vec<f64> {
+        step_sizes(self.generator, self.period, self.size)
+    }
+
+    pub fn is_mos(&self) -> bool {
+        is_mos(self.generator, self.period, self.size)
+    }
+
+    pub fn step_pattern(&self) -> Option<StepPattern> {
+        let mut positions = self.degrees();
+        positions.push(self.period);
+        let steps: Vec<f64> = positions.windows(2).map(|w| w[1] - w[0]).collect();
+        StepPattern::from_steps(&steps)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpectrumRegion {
+    pub lower: EdoGenerator,
+    pub upper: EdoGenerator,
+    pub sizes: Vec<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Step {
+    Large,
+    Small,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StepCount {
+    pub large: usize,
+    pub small: usize,
+}
+
+impl StepCount {
+    pub fn diatonic(&self) -> usize {
+        self.large + self.small
+    }
+
+    pub fn chromatic(&self) -> usize {
+        self.large
+    }
+
+    pub fn cents(&self, large: f64, small: f64) -> f64 {
+        self.large as f64 * large + self.small as f64 * small
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepPattern {
+    pub steps: Vec<Step>,
+    pub large: f64,
+    pub small: f64,
+}
+
+impl StepPattern {
+    pub fn from_steps(steps: &[f64]) -> Option<Self> {
+        let large = steps.iter().copied().reduce(f64::max)?;
+        let small = steps.iter().copied().reduce(f64::min)?;
+        let tolerance = STEP_EPSILON * steps.iter().sum::<f64>().abs().max(1.);
+        if large - small < tolerance {
+            return None;
+        }
+        let steps = steps
+            .iter()
+            .map(|&s| {
+                if (s - large).abs() < tolerance {
+                    Some(Step::Large)
+                } else if (s - small).abs() < tolerance {
+                    Some(Step::Small)
+                } else {
+                    None
+                }
+            })
+            .collect::<Option<Vec<Step>>>()?;
+        Some(Self {
+            steps,
+            large,
+            small,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn large_count(&self) -> usize {
+        self.steps.iter().filter(|&&s| s == Step::Large).count()
+    }
+
+    pub fn small_count(&self) -> usize {
+        self.len() - self.large_count()
+    }
+
+    pub fn chroma(&self) -> f64 {
+        self.large - self.small
+    }
+
+    pub fn signature(&self) -> String {
+        format!("{}L {}s", self.large_count(), self.small_count())
+    }
+
+    pub fn degree(&self, degree: usize) -> StepCount {
+        let large = self.steps[..degree.min(self.len())]
+            .iter()
+            .filter(|&&s| s == Step::Large)
+            .count();
+        StepCount {
+            large,
+            small: degree.min(self.len()) - large,
+        }
+    }
+
+    pub fn degrees(&self) -> Vec<StepCount> {
+        (0..=self.len()).map(|k| self.degree(k)).collect()
+    }
+}
+
+impl fmt::Display for StepPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for step in &self.steps {
+            match step {
+                Step::Large => write!(f, "L")?,
+                Step::Small => write!(f, "s")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+const STEP_EPSILON: f64 = 1e-9;
+
+pub fn step_sizes(generator: f64, period: f64, size: usize) -> Vec<f64> {
+    let mut positions = Mos::new(generator, period, size).degrees();
+    positions.push(period);
+
+    let mut sizes: Vec<f64> = Vec::new();
+    for step in positions.windows(2).map(|w| w[1] - w[0]) {
+        if !sizes
+            .iter()
+            .any(|s| (s - step).abs() < STEP_EPSILON * period)
+        {
+            sizes.push(step);
+        }
+    }
+    sizes.sort_by(|a, b| b.total_cmp(a));
+    sizes
+}
+
+pub fn is_mos(generator: f64, period: f64, size: usize) -> bool {
+    size >= 2 && step_sizes(generator, period, size).len() == 2
+}
+
+pub fn mos_sizes(generator: f64, period: f64, max_size: usize) -> Vec<usize> {
+    (2..=max_size)
+        .filter(|&n| is_mos(generator, period, n))
+        .collect()
+}
+
+pub fn mos_spectrum(lower: f64, upper: f64, period: f64, max_size: usize) -> Vec<SpectrumRegion> {
+    farey_sequence(max_size as i32)
+        .windows(2)
+        .filter(|w| w[1].cents(period) > lower && w[0].cents(period) < upper)
+        .map(|w| {
+            let midpoint = (w[0].cents(period) + w[1].cents(period)) / 2.;
+            SpectrumRegion {
+                lower: w[0],
+                upper: w[1],
+                sizes: mos_sizes(midpoint, period, max_size),
+            }
+        })
+        .collect()
+}
+
+fn farey_sequence(order: i32) -> Vec<EdoGenerator> {
+    let (mut a, mut b, mut c, mut d) = (0, 1, 1, order);
+    let mut sequence = vec![EdoGenerator::new(a, b)];
+    while c <= order {
+        let k = (order + b) / d;
+        (a, b, c, d) = (c, d, k * c - a, k * d - b);
+        sequence.push(EdoGenerator::new(a, b));
+    }
+    sequence
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diatonic_step_pattern() {
+        let pattern = Mos::new(702., 1200., 7).step_pattern().unwrap();
+
+        assert_eq!(pattern.to_string(), "LLLsLLs");
+        assert_eq!(pattern.signature(), "5L 2s");
+        assert!((pattern.chroma() - 114.).abs() < 1e-9);
+        let fifth = pattern.degree(4);
+        assert_eq!(fifth, StepCount { large: 3, small: 1 });
+        assert_eq!((fifth.diatonic(), fifth.chromatic()), (4, 3));
+        assert!((fifth.cents(pattern.large, pattern.small) - 702.).abs() < 1e-9);
+        assert_eq!(pattern.degrees().len(), 8);
+    }
+
+    #[test]
+    fn step_pattern_needs_two_sizes() {
+        assert_eq!(StepPattern::from_steps(&[100.; 12]), None);
+        assert_eq!(StepPattern::from_steps(&[200., 100., 150.]), None);
+        assert_eq!(
+            Mos::new(702., 1200., 5).step_pattern().unwrap().to_string(),
+            "ssLsL"
+        );
+    }
+
+    #[test]
+    fn edo_generator_cents() {
+        let g = EdoGenerator::new(7, 12);
+
+        assert_eq!(g.cents(1200.), 700.);
+        assert_eq!(g.cents(1902.), 1109.5);
+    }
+
+    #[test]
+    fn mediant() {
+        let g = EdoGenerator::new(3, 5).mediant(&EdoGenerator::new(4, 7));
+
+        assert_eq!(g, EdoGenerator::new(7, 12));
+    }
+
+    #[test]
+    fn metallic_means() {
+        assert!((metallic_mean(1) - 1.618033988749895).abs() < 1e-12);
+        assert!((metallic_mean(2) - 2.414213562373095).abs() < 1e-12);
+        assert_eq!(metallic_mean(0), 1.);
+    }
+
+    #[test]
+    fn golden_meantone_generator() {
+        let fraction = EdoGenerator::new(3, 5).noble_mediant(&EdoGenerator::new(4, 7));
+
+        assert!((fraction * 1200. - 696.2144739545568).abs() < 1e-9);
+    }
+
+    #[test]
+    fn silver_mediant() {
+        let fraction = EdoGenerator::new(3, 5).metallic_mediant(&EdoGenerator::new(4, 7), 2);
+
+        assert!((fraction * 1200. - 693.542254894615).abs() < 1e-9);
+    }
+
+    #[test]
+    fn noble_mos_scale() {
+        let mos = Mos::noble(EdoGenerator::new(3, 5), EdoGenerator::new(4, 7), 1200., 7);
+        let degrees = mos.degrees();
+
+        assert!(mos.is_mos());
+        assert_eq!(degrees.len(), 7);
+        assert_eq!(degrees[0], 0.);
+        assert!((degrees[4] - 696.2144739545568).abs() < 1e-9);
+        assert_eq!(mos.step_sizes().len(), 2);
+    }
+
+    #[test]
+    fn farey_sequence_of_order_five() {
+        let fractions: Vec<(i32, i32)> =
+            farey_sequence(5).iter().map(|g| (g.steps, g.edo)).collect();
+
+        assert_eq!(
+            fractions,
+            vec![
+                (0, 1),
+                (1, 5),
+                (1, 4),
+                (1, 3),
+                (2, 5),
+                (1, 2),
+                (3, 5),
+                (2, 3),
+                (3, 4),
+                (4, 5),
+                (1, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn pentatonic_step_sizes() {
+        let steps = step_sizes(702., 1200., 5);
+
+        assert_eq!(steps.len(), 2);
+        assert!((steps[0] - 294.).abs() < 1e-9);
+        assert!((steps[1] - 204.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pythagorean_mos_sizes() {
+        assert_eq!(mos_sizes(702., 1200., 12), vec![2, 3, 5, 7, 12]);
+    }
+
+    #[test]
+    fn equal_division_is_not_mos() {
+        assert!(!is_mos(700., 1200., 12));
+        assert!(is_mos(700., 1200., 7));
+    }
+
+    #[test]
+    fn spectrum_regions_have_exact_boundaries() {
+        let regions = mos_spectrum(695., 705., 1200., 17);
+
+        assert_eq!(
+            regions,
+            vec![
+                SpectrumRegion {
+                    lower: EdoGenerator::new(4, 7),
+                    upper: EdoGenerator::new(7, 12),
+                    sizes: vec![2, 3, 5, 7, 12],
+                },
+                SpectrumRegion {
+                    lower: EdoGenerator::new(7, 12),
+                    upper: EdoGenerator::new(10, 17),
+                    sizes: vec![2, 3, 5, 7, 12, 17],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn spectrum_over_tritave() {
+        let regions = mos_spectrum(1100., 1120., 1902., 4);
+
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].lower, EdoGenerator::new(1, 2));
+        assert_eq!(regions[0].upper, EdoGenerator::new(2, 3));
+        assert_eq!(regions[0].sizes, vec![2, 3]);
+    }
+}
@@ -0,0 +1,479 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::midi::{self, MidiEvent};
+use crate::standard::PitchStandard;
+use crate::tuning_table::{TuningTable, MIDI_NOTES};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MtsError {
+    NotSysex,
+    Unsupported(Vec<u8>),
+    WrongLength { expected: usize, found: usize },
+    BadChecksum { expected: u8, found: u8 },
+    InvalidBeat(f64),
+}
+
+impl fmt::Display for MtsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MtsError::NotSysex => write!(f, "message is not a sysex message"),
+            MtsError::Unsupported(header) => write!(f, "unsupported sysex header {header:02X?}"),
+            MtsError::WrongLength { expected, found } => {
+                write!(f, "expected {expected} bytes, found {found}")
+            }
+            MtsError::BadChecksum { expected, found } => {
+                write!(f, "checksum {found:02X} does not match {expected:02X}")
+            }
+            MtsError::InvalidBeat(beat) => write!(f, "invalid beat: {beat}"),
+        }
+    }
+}
+
+impl Error for MtsError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MtsMessage {
+    BulkDump {
+        device: u8,
+        program: u8,
+        name: String,
+        table: TuningTable,
+    },
+    SingleNote {
+        device: u8,
+        bank: Option<u8>,
+        program: u8,
+        changes: Vec<(u8, f64)>,
+    },
+}
+
+impl MtsMessage {
+    pub fn apply(&self, table: &mut TuningTable) {
+        match self {
+            MtsMessage::BulkDump { table: dump, .. } => {
+                for (slot, frequency) in table.frequencies.iter_mut().zip(&dump.frequencies) {
+                    if !frequency.is_nan() {
+                        *slot = *frequency;
+                    }
+                }
+            }
+            MtsMessage::SingleNote { changes, .. } => {
+                for &(note, frequency) in changes {
+                    if let Some(slot) = table.frequencies.get_mut(note as usize) {
+                        *slot = frequency;
+                    }
+                }
+            }
+        }
+    }
+}
+
+const NO_CHANGE: [u8; 3] = [0x7f, 0x7f, 0x7f];
+const BULK_DUMP_LENGTH: usize = 408;
+const MAX_CHANGES: usize = 0x7f;
+
+pub fn parse_sysex(bytes: &[u8]) -> Result<MtsMessage, MtsError> {
+    if bytes.len() < 6 || bytes[0] != 0xf0 || bytes[bytes.len() - 1] != 0xf7 {
+        return Err(MtsError::NotSysex);
+    }
+    match bytes[1..5] {
+        [0x7e, device, 0x08, 0x01] => parse_bulk_dump(device, bytes),
+        [0x7f, device, 0x08, 0x02] => parse_single_note(device, None, &bytes[5..]),
+        [0x7f, device, 0x08, 0x07] => parse_single_note(device, Some(bytes[5]), &bytes[6..]),
+        _ => Err(MtsError::Unsupported(bytes[..5].to_vec())),
+    }
+}
+
+fn parse_bulk_dump(device: u8, bytes: &[u8]) -> Result<MtsMessage, MtsError> {
+    if bytes.len() != BULK_DUMP_LENGTH {
+        return Err(MtsError::WrongLength {
+            expected: BULK_DUMP_LENGTH,
+            found: bytes.len(),
+        });
+    }
+    let expected = checksum(&bytes[1..BULK_DUMP_LENGTH - 2]);
+    let found = bytes[BULK_DUMP_LENGTH - 2];
+    if expected != found {
+        return Err(MtsError::BadChecksum { expected, found });
+    }
+
+    let name = bytes[6..22]
+        .iter()
+        .map(|&b| b as char)
+        .collect::<String>()
+        .trim_end()
+        .to_string();
+    let frequencies = bytes[22..22 + 3 * MIDI_NOTES]
+        .chunks(3)
+        .map(|c| decode_frequency([c[0], c[1], c[2]]).unwrap_or(f64::NAN))
+        .collect();
+
+    Ok(MtsMessage::BulkDump {
+        device,
+        program: bytes[5],
+        name,
+        table: TuningTable::new(frequencies),
+    })
+}
+
+fn parse_single_note(device: u8, bank: Option<u8>, body: &[u8]) -> Result<MtsMessage, MtsError> {
+    let count = *body.get(1).ok_or(MtsError::WrongLength {
+        expected: 2,
+        found: body.len(),
+    })? as usize;
+    let expected = 2 + 4 * count + 1;
+    if body.len() != expected {
+        return Err(MtsError::WrongLength {
+            expected,
+            found: body.len(),
+        });
+    }
+
+    let changes = body[2..2 + 4 * count]
+        .chunks(4)
+        .filter_map(|c| decode_frequency([c[1], c[2], c[3]]).map(|f| (c[0], f)))
+        .collect();
+    Ok(MtsMessage::SingleNote {
+        device,
+        bank,
+        program: body[0],
+        changes,
+    })
+}
+
+pub fn decode_frequency(data: [u8; 3]) -> Option<f64> {
+    if data == NO_CHANGE {
+        return None;
+    }
+    let fraction = ((data[1] as u32) << 7 | data[2] as u32) as f64 / 16384.;
+    let standard = PitchStandard::A440;
+    Some(standard.frequency * 2_f64.powf((data[0] as f64 + fraction - standard.note as f64) / 12.))
+}
+
+pub fn encode_frequency(frequency: f64) -> [u8; 3] {
+    let standard = PitchStandard::A440;
+    let note = standard.note as f64 + 12. * (frequency / standard.frequency).log2();
+    if !(0. ..128.).contains(&note) {
+        return NO_CHANGE;
+    }
+    let mut semitone = note.floor() as u32;
+    let mut fraction = ((note - semitone as f64) * 16384.).round() as u32;
+    if fraction >= 16384 {
+        semitone += 1;
+        fraction = 0;
+    }
+    if semitone > 127 {
+        return [0x7f, 0x7f, 0x7e];
+    }
+    [
+        semitone as u8,
+        (fraction >> 7) as u8,
+        (fraction & 0x7f) as u8,
+    ]
+}
+
+pub fn bulk_dump(
+    table: &TuningTable,
+    device: u8,
+    program: u8,
+    name: &str,
+) -> Result<Vec<u8>, MtsError> {
+    if table.frequencies.len() != MIDI_NOTES {
+        return Err(MtsError::WrongLength {
+            expected: MIDI_NOTES,
+            found: table.frequencies.len(),
+        });
+    }
+    let mut bytes = vec![0xf0, 0x7e, device & 0x7f, 0x08, 0x01, program & 0x7f];
+    let mut name: Vec<u8> = name.bytes().filter(|b| b.is_ascii()).take(16).collect();
+    name.resize(16, b' ');
+    bytes.extend(name);
+    for &frequency in &table.frequencies {
+        bytes.extend(encode_frequency(frequency));
+    }
+    bytes.push(checksum(&bytes[1..]));
+    bytes.push(0xf7);
+    Ok(bytes)
+}
+
+pub fn single_note(changes: &[(u8, f64)], device: u8, program: u8) -> Vec<Vec<u8>> {
+    changes
+        .chunks(MAX_CHANGES)
+        .map(|chunk| {
+            let mut bytes = vec![0xf0, 0x7f, device & 0x7f, 0x08, 0x02, program & 0x7f];
+            bytes.push(chunk.len() as u8);
+            for &(note, frequency) in chunk {
+                bytes.push(note & 0x7f);
+                bytes.extend(encode_frequency(frequency));
+            }
+            bytes.push(0xf7);
+            bytes
+        })
+        .collect()
+}
+
+pub fn tuning_automation(
+    changes: &[(f64, TuningTable)],
+    device: u8,
+    program: u8,
+    name: &str,
+) -> Result<Vec<MidiEvent>, MtsError> {
+    if let Some((beat, _)) = changes.iter().find(|(beat, _)| !beat.is_finite()) {
+        return Err(MtsError::InvalidBeat(*beat));
+    }
+    let mut changes: Vec<&(f64, TuningTable)> = changes.iter().collect();
+    changes.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut events = vec![];
+    let mut current: Option<&TuningTable> = None;
+    for (beat, table) in changes {
+        let tick = midi::ticks(*beat);
+        match current {
+            None => events.push(MidiEvent::sysex(
+                tick,
+                &bulk_dump(table, device, program, name)?,
+            )),
+            Some(previous) => {
+                let retuned: Vec<(u8, f64)> = table
+                    .frequencies
+                    .iter()
+                    .zip(&previous.frequencies)
+                    .enumerate()
+                    .filter(|(_, (f, p))| encode_frequency(**f) != encode_frequency(**p))
+                    .map(|(note, (f, _))| (note as u8, *f))
+                    .collect();
+                for message in single_note(&retuned, device, program) {
+                    events.push(MidiEvent::sysex(tick, &message));
+                }
+            }
+        }
+        current = Some(table);
+    }
+    Ok(events)
+}
+
+pub fn write_tuning_automation(
+    changes: &[(f64, TuningTable)],
+    device: u8,
+    program: u8,
+    name: &str,
+) -> Result<Vec<u8>, MtsError> {
+    Ok(midi::write_smf(&tuning_automation(
+        changes, device, program, name,
+    )?))
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0, |sum, b| sum ^ b) & 0x7f
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ratio::Ratio;
+    use crate::scale::Scale;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (1200. * (a / b).log2()).abs() < 0.01
+    }
+
+    #[test]
+    fn decode_reference_frequencies() {
+        assert!(approx(decode_frequency([69, 0, 0]).unwrap(), 440.));
+        assert!(approx(decode_frequency([60, 0, 0]).unwrap(), 261.6256));
+        assert!(approx(decode_frequency([69, 0x40, 0]).unwrap(), 452.893));
+        assert_eq!(decode_frequency([0x7f, 0x7f, 0x7f]), None);
+    }
+
+    #[test]
+    fn encode_decode_round_trip() {
+        for frequency in [8.2, 261.63, 440., 452.893, 1000., 12000.] {
+            assert!(approx(
+                decode_frequency(encode_frequency(frequency)).unwrap(),
+                frequency
+            ));
+        }
+    }
+
+    #[test]
+    fn unencodable_frequencies_mean_no_change() {
+        for frequency in [f64::NAN, f64::INFINITY, 0., -440., 5., 20000.] {
+            assert_eq!(encode_frequency(frequency), NO_CHANGE);
+        }
+        let top = 440. * 2_f64.powf(59. / 12.) * (1. - 1e-6);
+        assert_eq!(encode_frequency(top), [0x7f, 0x7f, 0x7e]);
+    }
+
+    #[test]
+    fn parse_bulk_dump() {
+        let scale = Scale::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+        let table = TuningTable::from_scale(&scale, 60, 261.63).unwrap();
+        let bytes = bulk_dump(&table, 0, 3, "triad").unwrap();
+
+        match parse_sysex(&bytes).unwrap() {
+            MtsMessage::BulkDump {
+                program,
+                name,
+                table: parsed,
+                ..
+            } => {
+                assert_eq!(program, 3);
+                assert_eq!(name, "triad");
+                assert!(approx(parsed.frequency(60), 261.63));
+                assert!(approx(parsed.frequency(61), 327.0375));
+                for (c, e) in parsed
+                    .to_scale(60, 3)
+                    .unwrap()
+                    .cents()
+                    .iter()
+                    .zip(scale.cents())
+                {
+                    assert!((c - e).abs() < 0.01);
+                }
+            }
+            m => panic!("unexpected message {m:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_bulk_dump_with_bad_checksum() {
+        let mut bytes = bulk_dump(
+            &TuningTable::equal_temperament(PitchStandard::A440),
+            0,
+            0,
+            "",
+        )
+        .unwrap();
+        bytes[100] ^= 1;
+
+        assert!(matches!(
+            parse_sysex(&bytes),
+            Err(MtsError::BadChecksum { .. })
+        ));
+    }
+
+    #[test]
+    fn bulk_dump_needs_a_full_table() {
+        assert_eq!(
+            bulk_dump(&TuningTable::new(vec![440.; 12]), 0, 0, ""),
+            Err(MtsError::WrongLength {
+                expected: MIDI_NOTES,
+                found: 12
+            })
+        );
+        assert_eq!(
+            bulk_dump(
+                &TuningTable::equal_temperament(PitchStandard::A440),
+                0,
+                0,
+                ""
+            )
+            .unwrap()
+            .len(),
+            BULK_DUMP_LENGTH
+        );
+    }
+
+    #[test]
+    fn automation_sends_dump_then_changed_notes() {
+        let equal = TuningTable::equal_temperament(PitchStandard::A440);
+        let mut retuned = equal.clone();
+        retuned.frequencies[64] = 327.0375;
+        retuned.frequencies[71] = 490.5;
+
+        let events =
+            tuning_automation(&[(4., retuned), (0., equal.clone())], 0x7f, 0, "piece").unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].tick, 0);
+        assert_eq!(&events[0].data[..2], &[0xf0, 0x83]);
+        assert_eq!(events[1].tick, 1920);
+        let mut table = equal;
+        parse_sysex(&[&[0xf0], &events[1].data[2..]].concat())
+            .unwrap()
+            .apply(&mut table);
+        assert!(approx(table.frequency(64), 327.0375));
+        assert!(approx(table.frequency(71), 490.5));
+        assert_eq!(events[1].data[7], 2);
+    }
+
+    #[test]
+    fn automation_rejects_non_finite_beats() {
+        let equal = TuningTable::equal_temperament(PitchStandard::A440);
+
+        assert!(matches!(
+            tuning_automation(&[(0., equal.clone()), (f64::NAN, equal)], 0x7f, 0, "piece"),
+            Err(MtsError::InvalidBeat(beat)) if beat.is_nan()
+        ));
+    }
+
+    #[test]
+    fn parse_single_note_change() {
+        let bytes = single_note(&[(60, 261.63), (64, 327.0375)], 0x7f, 0);
+
+        assert_eq!(bytes.len(), 1);
+        let message = parse_sysex(&bytes[0]).unwrap();
+        let mut table = TuningTable::equal_temperament(PitchStandard::A440);
+        message.apply(&mut table);
+
+        assert!(approx(table.frequency(64), 327.0375));
+        assert!(approx(table.frequency(69), 440.));
+    }
+
+    #[test]
+    fn single_note_changes_are_chunked() {
+        let changes: Vec<(u8, f64)> = (0..200).map(|i| ((i % 128) as u8, 440.)).collect();
+
+        let messages = single_note(&changes, 0x7f, 0);
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0][6], 127);
+        assert_eq!(messages[1][6], 73);
+        assert!(messages.iter().all(|m| parse_sysex(m).is_ok()));
+    }
+
+    #[test]
+    fn apply_ignores_notes_past_a_short_table() {
+        let bytes = single_note(&[(1, 20.), (100, 3000.)], 0x7f, 0);
+        let mut table = TuningTable::new(vec![10., 11.]);
+
+        parse_sysex(&bytes[0]).unwrap().apply(&mut table);
+
+        assert_eq!(table.frequencies.len(), 2);
+        assert!(approx(table.frequency(1), 20.));
+    }
+
+    #[test]
+    fn parse_banked_single_note_change() {
+        let bytes = [
+            0xf0, 0x7f, 0x00, 0x08, 0x07, 0x02, 0x05, 0x01, 0x45, 0x45, 0x00, 0x00, 0xf7,
+        ];
+
+        assert_eq!(
+            parse_sysex(&bytes),
+            Ok(MtsMessage::SingleNote {
+                device: 0,
+                bank: Some(2),
+                program: 5,
+                changes: vec![(0x45, 440.)],
+            })
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse_sysex(&[0x90, 60, 100]), Err(MtsError::NotSysex));
+        assert_eq!(
+            parse_sysex(&[0xf0, 0x43, 0x10, 0x4c, 0x00, 0xf7]),
+            Err(MtsError::Unsupported(vec![0xf0, 0x43, 0x10, 0x4c, 0x00]))
+        );
+        assert_eq!(
+            parse_sysex(&[0xf0, 0x7f, 0x00, 0x08, 0x02, 0x00, 0x02, 0xf7]),
+            Err(MtsError::WrongLength {
+                expected: 11,
+                found: 3
+            })
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt;
+
+use crate::standard::PitchStandard;
+
+pub const DIVISIONS: u32 = 480;
+
+const SPELLINGS: [(char, i32); 12] = [
+    ('C', 0),
+    ('C', 1),
+    ('D', 0),
+    ('E', -1),
+    ('E', 0),
+    ('F', 0),
+    ('F', 1),
+    ('G', 0),
+    ('A', -1),
+    ('A', 0),
+    ('B', -1),
+    ('B', 0),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spelling {
+    pub step: char,
+    pub alter: f64,
+    pub octave: i32,
+}
+
+impl Spelling {
+    pub fn from_frequency(frequency: f64) -> Self {
+        let standard = PitchStandard::A440;
+        let position = standard.note as f64 + 12. * (frequency / standard.frequency).log2();
+        let nearest = position.round() as i32;
+        let (step, alter) = SPELLINGS[nearest.rem_euclid(12) as usize];
+        Self {
+            step,
+            alter: alter as f64 + position - nearest as f64,
+            octave: nearest.div_euclid(12) - 1,
+        }
+    }
+
+    pub fn to_xml(&self) -> String {
+        let alter = match self.alter.abs() < 5e-5 {
+            true => String::new(),
+            false => format!("<alter>{:.4}</alter>", self.alter),
+        };
+        format!(
+            "<pitch><step>{}</step>{alter}<octave>{}</octave></pitch>",
+            self.step, self.octave
+        )
+    }
+}
+
+impl fmt::Display for Spelling {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let accidental = self.alter.round();
+        let deviation = (self.alter - accidental) * 100.;
+        let accidental = match accidental as i32 {
+            n if n < 0 => "b".repeat(n.unsigned_abs() as usize),
+            n => "#".repeat(n as usize),
+        };
+        write!(f, "{}{accidental}{}", self.step, self.octave)?;
+        match deviation.abs() < 0.05 {
+            true => Ok(()),
+            false => write!(f, " {deviation:+.1}c"),
+        }
+    }
+}
+
+pub fn divisions(beats: f64) -> u32 {
+    (beats * DIVISIONS as f64).round() as u32
+}
+
+pub fn score(title: &str, measures: &[String]) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<score-partwise version=\"4.0\">\n");
+    out.push_str(&format!(
+        "  <work><work-title>{}</work-title></work>\n",
+        escape(title)
+    ));
+    out.push_str("  <part-list><score-part id=\"P1\"><part-name>Music</part-name></score-part></part-list>\n");
+    out.push_str("  <part id=\"P1\">\n");
+    for (i, measure) in measures.iter().enumerate() {
+        out.push_str(&format!("    <measure number=\"{}\">\n", i + 1));
+        if i == 0 {
+            out.push_str(&format!(
+                "      <attributes><divisions>{DIVISIONS}</divisions></attributes>\n"
+            ));
+        }
+        out.push_str(measure);
+        out.push_str("    </measure>\n");
+    }
+    out.push_str("  </part>\n</score-partwise>\n");
+    out
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spelling_names() {
+        assert_eq!(Spelling::from_frequency(440.).to_string(), "A4");
+        assert_eq!(
+            Spelling::from_frequency(261.63 * 1.25).to_string(),
+            "E4 -13.7c"
+        );
+        assert_eq!(Spelling::from_frequency(311.127).to_string(), "Eb4");
+    }
+
+    #[test]
+    fn spell_concert_a() {
+        let spelling = Spelling::from_frequency(440.);
+
+        assert_eq!(spelling.step, 'A');
+        assert!(spelling.alter.abs() < 1e-9);
+        assert_eq!(spelling.octave, 4);
+        assert_eq!(
+            spelling.to_xml(),
+            "<pitch><step>A</step><octave>4</octave></pitch>"
+        );
+    }
+
+    #[test]
+    fn spell_just_third_with_microtonal_alter() {
+        let spelling = Spelling::from_frequency(261.6256 * 1.25);
+
+        assert_eq!(spelling.step, 'E');
+        assert!((spelling.alter + 0.13686).abs() < 1e-3);
+        assert_eq!(
+            spelling.to_xml(),
+            "<pitch><step>E</step><alter>-0.1369</alter><octave>4</octave></pitch>"
+        );
+    }
+
+    #[test]
+    fn spell_flat_below_middle_c() {
+        let spelling = Spelling::from_frequency(233.082);
+
+        assert_eq!((spelling.step, spelling.octave), ('B', 3));
+        assert!((spelling.alter + 1.).abs() < 1e-3);
+    }
+
+    #[test]
+    fn escape_markup() {
+        assert_eq!(escape("<7&11>"), "&lt;7&amp;11&gt;");
+    }
+}
@@ -0,0 +1,91 @@
+use std::cmp::Ordering;
+
+use crate::degree::Degree;
+use crate::equivalence::Equivalence;
+use crate::ratio::Ratio;
+
+/// A deterministic ordering. Every order ends in a total tie-break, so the
+/// same input always sorts the same way.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Degrees by pitch; lattice points by coordinates, lexicographically.
+    #[default]
+    Natural,
+    /// Ascending cents, then coordinates for lattice points.
+    Cents,
+    /// Ascending Tenney height, then cents for degrees or coordinates for
+    /// lattice points. Cents-only degrees sort after every ratio.
+    Complexity,
+}
+
+/// Compares degrees by `order`. `Natural` and `Cents` are the same for
+/// degrees.
+pub fn compare_degrees(a: &Degree, b: &Degree, order: SortOrder) -> Ordering {
+    let by_cents = a.cents().total_cmp(&b.cents());
+    match order {
+        SortOrder::Natural | SortOrder::Cents => by_cents,
+        SortOrder::Complexity => {
+            let height = |d: &Degree| {
+                d.ratio()
+                    .map(|r| r.tenney_height())
+                    .unwrap_or(f64::INFINITY)
+            };
+            height(a).total_cmp(&height(b)).then(by_cents)
+        }
+    }
+}
+
+/// Sorts lattice points in place by `order`. `Cents` compares ratios after
+/// reduction by `equivalence`.
+pub fn sort_points(points: &mut [(Vec<i32>, Ratio)], order: SortOrder, equivalence: Equivalence) {
+    match order {
+        SortOrder::Natural => points.sort_by(|a, b| a.0.cmp(&b.0)),
+        SortOrder::Cents => points.sort_by(|a, b| {
+            let cents = |r: &Ratio| r.normalize_with(equivalence).cents();
+            cents(&a.1).total_cmp(&cents(&b.1)).then(a.0.cmp(&b.0))
+        }),
+        SortOrder::Complexity => points.sort_by(|a, b| {
+            a.1.tenney_height()
+                .total_cmp(&b.1.tenney_height())
+                .then(a.0.cmp(&b.0))
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn degree_orders() {
+        let mut degrees = [
+            Degree::from(Ratio::new(15, 8)),
+            Degree::from_cents(100.),
+            Degree::from(Ratio::new(3, 2)),
+            Degree::from(Ratio::new(5, 4)),
+        ];
+        degrees.sort_by(|a, b| compare_degrees(a, b, SortOrder::Complexity));
+
+        assert_eq!(
+            degrees.iter().map(|d| d.to_string()).collect::<Vec<_>>(),
+            vec!["3/2", "5/4", "15/8", "100.0"]
+        );
+    }
+
+    #[test]
+    fn point_orders_break_ties_by_coordinates() {
+        let mut points = [
+            (vec![1, 0], Ratio::new(3, 2)),
+            (vec![-1, 0], Ratio::new(2, 3)),
+            (vec![0, 0], Ratio::new(1, 1)),
+        ];
+        sort_points(&mut points, SortOrder::Complexity, Equivalence::Octave);
+        assert_eq!(points[1].0, vec![-1, 0]);
+
+        sort_points(&mut points, SortOrder::Cents, Equivalence::Octave);
+        assert_eq!(points[1].1, Ratio::new(2, 3));
+
+        sort_points(&mut points, SortOrder::Natural, Equivalence::Octave);
+        assert_eq!(points[0].0, vec![-1, 0]);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::ratio::Ratio;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PitchClass {
+    ratio: Ratio,
+}
+
+impl PitchClass {
+    pub fn new(ratio: Ratio) -> Self {
+        Self {
+            ratio: ratio.normalize(),
+        }
+    }
+
+    pub fn ratio(&self) -> Ratio {
+        self.ratio
+    }
+
+    pub fn cents(&self) -> f64 {
+        self.ratio.cents()
+    }
+
+    pub fn at_equave(&self, equave: i32) -> Pitch {
+        Pitch {
+            equave,
+            class: *self,
+        }
+    }
+}
+
+impl From<Ratio> for PitchClass {
+    fn from(value: Ratio) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<Pitch> for PitchClass {
+    fn from(value: Pitch) -> Self {
+        value.class
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pitch {
+    pub equave: i32,
+    pub class: PitchClass,
+}
+
+impl Pitch {
+    pub fn new(ratio: Ratio) -> Self {
+        let mut class = ratio;
+        let mut equave = 0;
+        while class >= Ratio::new(2, 1) {
+            class = class / Ratio::new(2, 1);
+            equave += 1;
+        }
+        while class < Ratio::new(1, 1) {
+            class = class * Ratio::new(2, 1);
+            equave -= 1;
+        }
+        Self {
+            equave,
+            class: PitchClass { ratio: class },
+        }
+    }
+
+    pub fn ratio(&self) -> Ratio {
+        let offset = 2_i32.pow(self.equave.unsigned_abs());
+        match self.equave {
+            n if n < 0 => self.class.ratio / Ratio::new(offset, 1),
+            _ => self.class.ratio * Ratio::new(offset, 1),
+        }
+    }
+
+    pub fn cents(&self) -> f64 {
+        self.class.cents() + 1200. * self.equave as f64
+    }
+
+    pub fn frequency(&self, reference: impl Into<f64>) -> f64 {
+        let ratio = self.ratio();
+        reference.into() * f64::from(ratio)
+    }
+}
+
+impl From<Ratio> for Pitch {
+    fn from(value: Ratio) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<PitchClass> for Pitch {
+    fn from(value: PitchClass) -> Self {
+        value.at_equave(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standard::PitchStandard;
+
+    #[test]
+    fn pitch_class_is_equave_reduced() {
+        let pc = PitchClass::new(Ratio::new(9, 4));
+
+        assert_eq!(pc.ratio(), Ratio::new(9, 8));
+    }
+
+    #[test]
+    fn pitch_keeps_equave_offset() {
+        let p = Pitch::new(Ratio::new(9, 4));
+
+        assert_eq!(p.equave, 1);
+        assert_eq!(p.class.ratio(), Ratio::new(9, 8));
+        assert_eq!(p.ratio(), Ratio::new(9, 4));
+    }
+
+    #[test]
+    fn pitch_below_unison() {
+        let p = Pitch::new(Ratio::new(3, 8));
+
+        assert_eq!(p.equave, -2);
+        assert_eq!(p.class.ratio(), Ratio::new(3, 2));
+        assert_eq!(p.ratio(), Ratio::new(3, 8));
+    }
+
+    #[test]
+    fn pitch_class_at_equave() {
+        let p = PitchClass::new(Ratio::new(5, 4)).at_equave(-1);
+
+        assert_eq!(p.ratio(), Ratio::new(5, 8));
+        assert!((p.cents() - -813.686).abs() < 1e-3);
+    }
+
+    #[test]
+    fn frequency() {
+        assert_eq!(Pitch::new(Ratio::new(3, 2)).frequency(440.), 660.);
+        assert_eq!(Pitch::new(Ratio::new(1, 2)).frequency(440.), 220.);
+    }
+
+    #[test]
+    fn frequency_at_pitch_standard() {
+        let p = Pitch::new(Ratio::new(3, 2));
+
+        assert_eq!(p.frequency(PitchStandard::PARTCH_G392), 588.);
+    }
+
+    #[test]
+    fn conversions() {
+        let p: Pitch = Ratio::new(7, 2).into();
+        let pc: PitchClass = p.into();
+
+        assert_eq!(pc, PitchClass::new(Ratio::new(7, 4)));
+        assert_eq!(Pitch::from(pc), Pitch::new(Ratio::new(7, 4)));
+    }
+
+    #[test]
+    fn pitches_order_by_height() {
+        let mut pitches = [
+            Pitch::new(Ratio::new(5, 2)),
+            Pitch::new(Ratio::new(3, 4)),
+            Pitch::new(Ratio::new(3, 2)),
+        ];
+        pitches.sort();
+
+        assert_eq!(
+            pitches.iter().map(|p| p.ratio()).collect::<Vec<Ratio>>(),
+            vec![Ratio::new(3, 4), Ratio::new(3, 2), Ratio::new(5, 2)]
+        );
+    }
+}
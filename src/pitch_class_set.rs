@@ -0,0 +1,200 @@
+use crate::degree::Degree;
+use crate::pitch::PitchClass;
+use crate::ratio::Ratio;
+use crate::scale::Scale;
+use crate::tolerance::Tolerance;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PitchClassSet {
+    pub classes: Vec<PitchClass>,
+}
+
+impl PitchClassSet {
+    pub fn new<T: Into<PitchClass>>(classes: Vec<T>) -> Self {
+        let mut classes: Vec<PitchClass> = classes.into_iter().map(|c| c.into()).collect();
+        classes.sort();
+        classes.dedup();
+        Self { classes }
+    }
+
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    pub fn contains(&self, class: &PitchClass, tolerance: impl Into<Tolerance>) -> bool {
+        let tolerance = tolerance.into();
+        self.classes
+            .iter()
+            .any(|c| tolerance.accepts(circular_distance(c, class)))
+    }
+
+    pub fn union(&self, other: &Self, tolerance: impl Into<Tolerance>) -> Self {
+        let tolerance = tolerance.into();
+        let mut classes = self.classes.clone();
+        for class in &other.classes {
+            if !self.contains(class, tolerance) {
+                classes.push(*class);
+            }
+        }
+        Self::new(classes)
+    }
+
+    pub fn intersection(&self, other: &Self, tolerance: impl Into<Tolerance>) -> Self {
+        let tolerance = tolerance.into();
+        Self::new(
+            self.classes
+                .iter()
+                .filter(|c| other.contains(c, tolerance))
+                .copied()
+                .collect(),
+        )
+    }
+
+    pub fn difference(&self, other: &Self, tolerance: impl Into<Tolerance>) -> Self {
+        let tolerance = tolerance.into();
+        Self::new(
+            self.classes
+                .iter()
+                .filter(|c| !other.contains(c, tolerance))
+                .copied()
+                .collect(),
+        )
+    }
+
+    pub fn transpose(&self, interval: Ratio) -> Self {
+        Self::new(self.classes.iter().map(|c| c.ratio() * interval).collect())
+    }
+
+    pub fn canonical(&self) -> Self {
+        self.classes
+            .iter()
+            .map(|root| self.transpose(Ratio::new(1, 1) / root.ratio()))
+            .min_by(|a, b| {
+                let (a, b) = (a.cents(), b.cents());
+                let last = |v: &[f64]| v.last().copied().unwrap_or(0.);
+                last(&a).total_cmp(&last(&b)).then_with(|| {
+                    a.iter()
+                        .zip(&b)
+                        .map(|(x, y)| x.total_cmp(y))
+                        .find(|o| o.is_ne())
+                        .unwrap_or(a.len().cmp(&b.len()))
+                })
+            })
+            .unwrap_or_else(|| self.clone())
+    }
+
+    pub fn cents(&self) -> Vec<f64> {
+        self.classes.iter().map(|c| c.cents()).collect()
+    }
+}
+
+impl TryFrom<&Scale> for PitchClassSet {
+    type Error = Degree;
+
+    fn try_from(value: &Scale) -> Result<Self, Degree> {
+        let classes = value
+            .degrees
+            .iter()
+            .map(|d| d.ratio().ok_or(*d))
+            .collect::<Result<Vec<Ratio>, Degree>>()?;
+        Ok(Self::new(classes))
+    }
+}
+
+impl From<PitchClassSet> for Scale {
+    fn from(value: PitchClassSet) -> Self {
+        Scale::new(value.classes)
+    }
+}
+
+fn circular_distance(a: &PitchClass, b: &PitchClass) -> f64 {
+    let d = (a.cents() - b.cents()).abs();
+    d.min(1200. - d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ratios: &[(i32, i32)]) -> PitchClassSet {
+        PitchClassSet::new(ratios.iter().map(|&(n, d)| Ratio::new(n, d)).collect())
+    }
+
+    #[test]
+    fn new_set_is_sorted() {
+        let s = set(&[(3, 2), (5, 4), (1, 1), (3, 1)]);
+
+        assert_eq!(s, set(&[(1, 1), (5, 4), (3, 2)]));
+        assert_eq!(s.len(), 3);
+    }
+
+    #[test]
+    fn contains_with_tolerance() {
+        let s = set(&[(1, 1), (81, 64)]);
+
+        assert!(s.contains(&PitchClass::new(Ratio::new(5, 4)), 22.));
+        assert!(!s.contains(&PitchClass::new(Ratio::new(5, 4)), 20.));
+    }
+
+    #[test]
+    fn contains_wraps_around_the_equave() {
+        let s = set(&[(1, 1)]);
+
+        assert!(s.contains(&PitchClass::new(Ratio::new(160, 81)), 25.));
+    }
+
+    #[test]
+    fn union() {
+        let a = set(&[(1, 1), (5, 4)]);
+        let b = set(&[(81, 64), (3, 2)]);
+
+        assert_eq!(a.union(&b, 0.), set(&[(1, 1), (5, 4), (81, 64), (3, 2)]));
+        assert_eq!(a.union(&b, 25.), set(&[(1, 1), (5, 4), (3, 2)]));
+    }
+
+    #[test]
+    fn intersection() {
+        let a = set(&[(1, 1), (5, 4), (3, 2)]);
+        let b = set(&[(81, 64), (3, 2)]);
+
+        assert_eq!(a.intersection(&b, 0.), set(&[(3, 2)]));
+        assert_eq!(a.intersection(&b, 25.), set(&[(5, 4), (3, 2)]));
+    }
+
+    #[test]
+    fn difference() {
+        let a = set(&[(1, 1), (5, 4), (3, 2)]);
+        let b = set(&[(81, 64), (3, 2)]);
+
+        assert_eq!(a.difference(&b, 0.), set(&[(1, 1), (5, 4)]));
+        assert_eq!(a.difference(&b, 25.), set(&[(1, 1)]));
+    }
+
+    #[test]
+    fn canonical_form_is_transposition_invariant() {
+        let major = set(&[(1, 1), (5, 4), (3, 2)]);
+        let transposed = major.transpose(Ratio::new(9, 8));
+
+        assert_eq!(major.canonical(), transposed.canonical());
+    }
+
+    #[test]
+    fn canonical_form_is_most_compact_rotation() {
+        let s = set(&[(1, 1), (4, 3), (5, 3)]);
+
+        assert_eq!(s.canonical(), set(&[(1, 1), (5, 4), (3, 2)]));
+    }
+
+    #[test]
+    fn scale_with_cents_degrees_is_rejected() {
+        let exact = Scale::new(vec![Ratio::new(1, 1), Ratio::new(3, 2)]);
+        let tempered = Scale::new(vec![Degree::Ratio(Ratio::new(1, 1)), Degree::Cents(700.)]);
+
+        assert_eq!(PitchClassSet::try_from(&exact), Ok(set(&[(1, 1), (3, 2)])));
+        assert_eq!(PitchClassSet::try_from(&tempered), Err(Degree::Cents(700.)));
+    }
+}
@@ -0,0 +1,46 @@
+pub use crate::chord::Chord;
+pub use crate::consonance::Metric;
+pub use crate::degree::Degree;
+pub use crate::equivalence::Equivalence;
+pub use crate::lattice::DimensionBound::{self, Infinity, RangeBounded, ZeroBounded};
+pub use crate::lattice::{Lattice, LatticeDimension, Region};
+pub use crate::order::SortOrder;
+pub use crate::pitch::{Pitch, PitchClass};
+pub use crate::ratio::{Ratio, Style};
+pub use crate::scale::Scale;
+pub use crate::tolerance::Tolerance;
+pub use crate::{ratio, scale};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn macros_build_ratios_and_scales() {
+        assert_eq!(ratio!(3 / 2), Ratio::new(3, 2));
+        assert_eq!(ratio!(2 + 1, 2), Ratio::new(3, 2));
+        assert_eq!(ratio!(5), Ratio::new(5, 1));
+        assert_eq!(
+            scale![1 / 1, 5 / 4, 3 / 2],
+            Scale::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)])
+        );
+        assert_eq!(scale![0., 700.].cents(), vec![0., 700.]);
+    }
+
+    #[test]
+    fn prelude_covers_lattice_construction() {
+        let lattice = Lattice::new(vec![
+            LatticeDimension {
+                ratio: ratio!(3 / 2),
+                bounds: Infinity,
+            },
+            LatticeDimension {
+                ratio: ratio!(5 / 4),
+                bounds: ZeroBounded(2),
+            },
+        ]);
+
+        assert_eq!(lattice.at(vec![1, 1]), ratio!(15 / 8));
+        assert_eq!(lattice.to_scale(1).len(), 6);
+    }
+}
@@ -0,0 +1,112 @@
+use std::sync::OnceLock;
+
+const SIEVE_LIMIT: usize = 1 << 16;
+
+static PRIMES: OnceLock<Vec<i32>> = OnceLock::new();
+
+pub fn primes() -> &'static [i32] {
+    PRIMES.get_or_init(|| {
+        let mut composite = vec![false; SIEVE_LIMIT + 1];
+        let mut primes = vec![];
+        for n in 2..=SIEVE_LIMIT {
+            if composite[n] {
+                continue;
+            }
+            primes.push(n as i32);
+            for multiple in (n * n..=SIEVE_LIMIT).step_by(n) {
+                composite[multiple] = true;
+            }
+        }
+        primes
+    })
+}
+
+pub fn is_prime(n: i32) -> bool {
+    match n {
+        n if n < 2 => false,
+        n if n as usize <= SIEVE_LIMIT => primes().binary_search(&n).is_ok(),
+        n => primes()
+            .iter()
+            .take_while(|&&p| p as i64 * p as i64 <= n as i64)
+            .all(|&p| n % p != 0),
+    }
+}
+
+pub fn primes_up_to(n: i32) -> Vec<i32> {
+    if n < 2 {
+        return vec![];
+    }
+    match n as usize {
+        n if n <= SIEVE_LIMIT => primes()
+            .iter()
+            .copied()
+            .take_while(|&p| p as usize <= n)
+            .collect(),
+        _ => {
+            let mut primes = primes().to_vec();
+            primes.extend((SIEVE_LIMIT as i32 + 1..=n).filter(|&k| is_prime(k)));
+            primes
+        }
+    }
+}
+
+pub fn nth_prime(index: usize) -> Option<i32> {
+    primes().get(index).copied()
+}
+
+/// Returns `None` for primes above the sieve limit of 65536.
+pub fn prime_index(p: i32) -> Option<usize> {
+    primes().binary_search(&p).ok()
+}
+
+pub fn factorize(n: i32) -> Vec<(i32, i32)> {
+    let mut n = n.abs();
+    let mut factors = vec![];
+    for &p in primes() {
+        if p as i64 * p as i64 > n as i64 {
+            break;
+        }
+        let mut e = 0;
+        while n % p == 0 {
+            n /= p;
+            e += 1;
+        }
+        if e > 0 {
+            factors.push((p, e));
+        }
+    }
+    if n > 1 {
+        factors.push((n, 1));
+    }
+    factors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prime_queries() {
+        assert_eq!(primes_up_to(20), vec![2, 3, 5, 7, 11, 13, 17, 19]);
+        assert_eq!(primes_up_to(1), vec![]);
+        assert_eq!(primes_up_to(-1), vec![]);
+        assert_eq!(nth_prime(0), Some(2));
+        assert_eq!(nth_prime(4), Some(11));
+        assert_eq!(prime_index(13), Some(5));
+        assert_eq!(prime_index(15), None);
+        assert_eq!(prime_index(-7), None);
+        assert_eq!(prime_index(65521), Some(6541));
+        assert_eq!(prime_index(65537), None);
+        assert!(is_prime(65537));
+        assert!(!is_prime(65539 * 3));
+        assert!(!is_prime(1));
+    }
+
+    #[test]
+    fn factorization() {
+        assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize(-49), vec![(7, 2)]);
+        assert_eq!(factorize(2147483647), vec![(2147483647, 1)]);
+        assert_eq!(factorize(1), vec![]);
+    }
+}
@@ -0,0 +1,95 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Progress {
+    pub done: usize,
+    pub total: usize,
+}
+
+impl Progress {
+    pub fn fraction(&self) -> f64 {
+        match self.total {
+            0 => 1.,
+            total => self.done as f64 / total as f64,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    Cancel,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cancelled {
+    pub progress: Progress,
+}
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cancelled after {} of {} steps",
+            self.progress.done, self.progress.total
+        )
+    }
+}
+
+impl Error for Cancelled {}
+
+pub fn throttle(
+    every: usize,
+    mut callback: impl FnMut(Progress) -> Control,
+) -> impl FnMut(Progress) -> Control {
+    let every = every.max(1);
+    move |progress| match progress.done % every == 0 || progress.done == progress.total {
+        true => callback(progress),
+        false => Control::Continue,
+    }
+}
+
+pub(crate) fn report(
+    callback: &mut dyn FnMut(Progress) -> Control,
+    done: usize,
+    total: usize,
+) -> Result<(), Cancelled> {
+    let progress = Progress { done, total };
+    match callback(progress) {
+        Control::Continue => Ok(()),
+        Control::Cancel => Err(Cancelled { progress }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throttled_callbacks() {
+        let mut seen = vec![];
+        let mut callback = throttle(3, |p: Progress| {
+            seen.push(p.done);
+            Control::Continue
+        });
+        for done in 1..=7 {
+            report(&mut callback, done, 7).unwrap();
+        }
+        drop(callback);
+
+        assert_eq!(seen, vec![3, 6, 7]);
+    }
+
+    #[test]
+    fn cancellation() {
+        let mut callback = |p: Progress| match p.fraction() >= 0.5 {
+            true => Control::Cancel,
+            false => Control::Continue,
+        };
+
+        assert!(report(&mut callback, 1, 4).is_ok());
+        let cancelled = report(&mut callback, 2, 4).unwrap_err();
+        assert_eq!(cancelled.to_string(), "cancelled after 2 of 4 steps");
+    }
+}
@@ -0,0 +1,298 @@
+use crate::chord::Chord;
+use crate::midi::{self, MidiEvent};
+use crate::musicxml::{self, Spelling};
+use crate::ratio::Ratio;
+use crate::report::{Report, Table};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimedChord {
+    pub chord: Chord,
+    pub duration: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DriftStep {
+    pub from: usize,
+    pub motion: Ratio,
+    pub deviation: f64,
+    pub cumulative: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DriftReport {
+    pub edo: i32,
+    pub steps: Vec<DriftStep>,
+    pub total: Ratio,
+}
+
+impl DriftReport {
+    pub fn total_cents(&self) -> f64 {
+        self.steps.last().map(|s| s.cumulative).unwrap_or(0.)
+    }
+
+    pub fn accumulations(&self, threshold: f64) -> Vec<DriftStep> {
+        self.steps
+            .iter()
+            .filter(|s| s.deviation.abs() > threshold)
+            .copied()
+            .collect()
+    }
+}
+
+impl Report for DriftReport {
+    fn tables(&self) -> Vec<Table> {
+        let mut table = Table::new(
+            &format!("Drift against {}-EDO", self.edo),
+            &["from", "to", "motion", "deviation", "cumulative"],
+        );
+        for step in &self.steps {
+            table.push(vec![
+                step.from.into(),
+                (step.from + 1).into(),
+                step.motion.to_string().into(),
+                step.deviation.into(),
+                step.cumulative.into(),
+            ]);
+        }
+        vec![table]
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Progression {
+    pub chords: Vec<TimedChord>,
+}
+
+impl Progression {
+    pub fn new() -> Self {
+        Self { chords: vec![] }
+    }
+
+    pub fn push(&mut self, chord: Chord, duration: f64) {
+        self.chords.push(TimedChord { chord, duration });
+    }
+
+    pub fn len(&self) -> usize {
+        self.chords.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chords.is_empty()
+    }
+
+    pub fn total_duration(&self) -> f64 {
+        self.chords.iter().map(|c| c.duration).sum()
+    }
+
+    pub fn roots(&self) -> Vec<Ratio> {
+        self.chords
+            .iter()
+            .filter_map(|c| c.chord.pitches.first().map(|p| p.ratio()))
+            .collect()
+    }
+
+    pub fn root_motions(&self) -> Vec<Ratio> {
+        self.roots().windows(2).map(|w| w[1] / w[0]).collect()
+    }
+
+    pub fn drift(&self) -> Ratio {
+        self.root_motions()
+            .into_iter()
+            .fold(Ratio::new(1, 1), |acc, r| acc * r)
+    }
+
+    pub fn track_drift(&self, edo: i32) -> DriftReport {
+        let step = 1200. / edo as f64;
+        let mut cumulative = 0.;
+        let steps = self
+            .root_motions()
+            .into_iter()
+            .enumerate()
+            .map(|(from, motion)| {
+                let c = motion.cents();
+                let deviation = c - (c / step).round() * step;
+                cumulative += deviation;
+                DriftStep {
+                    from,
+                    motion,
+                    deviation,
+                    cumulative,
+                }
+            })
+            .collect();
+        DriftReport {
+            edo,
+            steps,
+            total: self.drift(),
+        }
+    }
+
+    pub fn common_tones(&self) -> Vec<usize> {
+        self.chords
+            .windows(2)
+            .map(|w| {
+                let next = w[1].chord.classes();
+                w[0].chord
+                    .classes()
+                    .iter()
+                    .filter(|c| next.contains(c))
+                    .count()
+            })
+            .collect()
+    }
+
+    pub fn to_midi(&self, reference: impl Into<f64>) -> Vec<u8> {
+        let reference = reference.into();
+        let voices = self.chords.iter().map(|c| c.chord.len()).max().unwrap_or(0);
+
+        let mut events: Vec<MidiEvent> = (0..voices.min(15))
+            .flat_map(|v| midi::bend_range_events(midi::channel(v), midi::BEND_RANGE as u8))
+            .collect();
+        let mut beat = 0.;
+        for timed in &self.chords {
+            let (start, end) = (midi::ticks(beat), midi::ticks(beat + timed.duration));
+            for (voice, pitch) in timed.chord.pitches.iter().enumerate() {
+                let channel = midi::channel(voice);
+                let (note, bend) = midi::note_and_bend(pitch.frequency(reference));
+                events.push(MidiEvent::pitch_bend(start, channel, bend));
+                events.push(MidiEvent::note_on(start, channel, note, 96));
+                events.push(MidiEvent::note_off(end, channel, note));
+            }
+            beat += timed.duration;
+        }
+        midi::write_smf(&events)
+    }
+
+    pub fn to_musicxml(&self, title: &str, reference: impl Into<f64>) -> String {
+        let reference = reference.into();
+        let measures: Vec<String> = self
+            .chords
+            .iter()
+            .map(|timed| {
+                let duration = musicxml::divisions(timed.duration);
+                let mut measure = String::new();
+                for (i, pitch) in timed.chord.pitches.iter().enumerate() {
+                    let chord = if i > 0 { "<chord/>" } else { "" };
+                    measure.push_str(&format!(
+                        "      <note>{chord}{}<duration>{duration}</duration></note>\n",
+                        Spelling::from_frequency(pitch.frequency(reference)).to_xml()
+                    ));
+                }
+                if timed.chord.is_empty() {
+                    measure.push_str(&format!(
+                        "      <note><rest/><duration>{duration}</duration></note>\n"
+                    ));
+                }
+                measure
+            })
+            .collect();
+        musicxml::score(title, &measures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::schema;
+    use crate::standard::PitchStandard;
+
+    fn chord(ratios: &[(i32, i32)]) -> Chord {
+        Chord::new(ratios.iter().map(|&(n, d)| Ratio::new(n, d)).collect())
+    }
+
+    fn comma_pump() -> Progression {
+        let mut progression = Progression::new();
+        progression.push(chord(&[(1, 1), (5, 4), (3, 2)]), 1.);
+        progression.push(chord(&[(5, 3), (2, 1), (5, 2)]), 1.);
+        progression.push(chord(&[(10, 9), (4, 3), (5, 3)]), 1.);
+        progression.push(chord(&[(40, 27), (50, 27), (20, 9)]), 1.);
+        progression.push(chord(&[(80, 81), (100, 81), (40, 27)]), 2.);
+        progression
+    }
+
+    #[test]
+    fn drift_is_product_of_root_motions() {
+        let progression = comma_pump();
+
+        assert_eq!(
+            progression.root_motions(),
+            vec![
+                Ratio::new(5, 3),
+                Ratio::new(2, 3),
+                Ratio::new(4, 3),
+                Ratio::new(2, 3)
+            ]
+        );
+        assert_eq!(progression.drift(), Ratio::new(80, 81));
+        assert_eq!(progression.total_duration(), 6.);
+    }
+
+    #[test]
+    fn common_tones_per_transition() {
+        assert_eq!(comma_pump().common_tones(), vec![2, 1, 1, 1]);
+    }
+
+    #[test]
+    fn drift_accumulates_to_syntonic_comma() {
+        let report = comma_pump().track_drift(12);
+
+        assert_eq!(report.steps.len(), 4);
+        assert!((report.steps[0].deviation + 15.641).abs() < 1e-3);
+        assert!((report.steps[1].deviation + 1.955).abs() < 1e-3);
+        assert!((report.total_cents() - Ratio::new(80, 81).cents()).abs() < 1e-9);
+        assert_eq!(report.total, Ratio::new(80, 81));
+
+        let accumulations = report.accumulations(5.);
+        assert_eq!(accumulations.len(), 1);
+        assert_eq!(accumulations[0].motion, Ratio::new(5, 3));
+    }
+
+    #[test]
+    fn drift_report_renders() {
+        let rendered = comma_pump().track_drift(12).render();
+
+        assert!(rendered.starts_with("Drift against 12-EDO\n"));
+        assert!(rendered.contains("0     1   5/3     -15.641    -15.641\n"));
+    }
+
+    #[test]
+    fn empty_progression_has_no_drift() {
+        let progression = Progression::new();
+
+        assert!(progression.is_empty());
+        assert_eq!(progression.drift(), Ratio::new(1, 1));
+        assert!(progression.common_tones().is_empty());
+        assert_eq!(progression.track_drift(12).total_cents(), 0.);
+    }
+
+    #[test]
+    fn midi_export_has_one_note_on_per_pitch() {
+        let bytes = comma_pump().to_midi(PitchStandard::VERDI_C256);
+
+        assert_eq!(&bytes[..4], b"MThd");
+        let note_ons = bytes
+            .windows(3)
+            .filter(|w| w[0] & 0xf0 == 0x90 && w[2] == 96)
+            .count();
+        assert_eq!(note_ons, 15);
+    }
+
+    #[test]
+    fn musicxml_export() {
+        let xml = comma_pump().to_musicxml("Comma pump", 261.6256);
+
+        assert!(xml.contains("<work-title>Comma pump</work-title>"));
+        assert_eq!(xml.matches("<measure ").count(), 5);
+        assert_eq!(xml.matches("<chord/>").count(), 10);
+        assert!(xml.contains("<step>C</step><octave>4</octave>"));
+        assert!(xml.contains("<duration>960</duration>"));
+    }
+
+    #[test]
+    fn drift_schema() {
+        assert_eq!(
+            schema(&comma_pump().track_drift(12)),
+            ["Drift against 12-EDO: from, to, motion, deviation, cumulative"]
+        );
+    }
+}
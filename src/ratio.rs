@@ -1,6 +1,35 @@
+use std::cmp::Ordering;
+use std::fmt;
 use std::ops::{Div, Mul};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+use crate::equivalence::Equivalence;
+use crate::primes::{factorize, prime_index};
+use crate::report::cents;
+use crate::rng::Rng;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Style {
+    #[default]
+    Fraction,
+    Colon,
+    Monzo,
+    Cents,
+}
+
+#[macro_export]
+macro_rules! ratio {
+    ($numer:literal / $denom:literal) => {
+        $crate::ratio::Ratio::new($numer, $denom)
+    };
+    ($numer:expr, $denom:expr) => {
+        $crate::ratio::Ratio::new($numer, $denom)
+    };
+    ($numer:expr) => {
+        $crate::ratio::Ratio::new($numer, 1)
+    };
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Ratio {
     pub numer: i32,
     pub denom: i32,
@@ -10,7 +39,12 @@ impl Mul<Ratio> for Ratio {
     type Output = Ratio;
 
     fn mul(self, rhs: Ratio) -> Self::Output {
-        Self::new(self.numer * rhs.numer, self.denom * rhs.denom)
+        let g1 = gcd(self.numer, rhs.denom);
+        let g2 = gcd(rhs.numer, self.denom);
+        Self::new(
+            (self.numer / g1) * (rhs.numer / g2),
+            (self.denom / g2) * (rhs.denom / g1),
+        )
     }
 }
 
@@ -18,7 +52,44 @@ impl Div<Ratio> for Ratio {
     type Output = Ratio;
 
     fn div(self, rhs: Ratio) -> Self::Output {
-        Self::new(self.numer * rhs.denom, rhs.numer * self.denom)
+        let g1 = gcd(self.numer, rhs.numer);
+        let g2 = gcd(rhs.denom, self.denom);
+        Self::new(
+            (self.numer / g1) * (rhs.denom / g2),
+            (self.denom / g2) * (rhs.numer / g1),
+        )
+    }
+}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numer as i64 * other.denom as i64).cmp(&(other.numer as i64 * self.denom as i64))
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.format(Style::Fraction))
+    }
+}
+
+// i32 terms convert to f64 exactly, so the quotient is correctly rounded
+// (relative error below 2^-53); f32 keeps only 24 bits of either term.
+impl From<&Ratio> for f64 {
+    fn from(value: &Ratio) -> Self {
+        value.numer as f64 / value.denom as f64
+    }
+}
+
+impl From<Ratio> for f64 {
+    fn from(value: Ratio) -> Self {
+        f64::from(&value)
     }
 }
 
@@ -28,18 +99,64 @@ impl From<&Ratio> for f32 {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RandomConstraints {
+    pub prime_limit: Option<i32>,
+    pub odd_limit: Option<i32>,
+    pub min_cents: f64,
+    pub max_cents: f64,
+}
+
+impl Default for RandomConstraints {
+    fn default() -> Self {
+        Self {
+            prime_limit: None,
+            odd_limit: None,
+            min_cents: 0.,
+            max_cents: 1200.,
+        }
+    }
+}
+
+const RANDOM_ODD_BOUND: i32 = 255;
+const RANDOM_ATTEMPTS: usize = 1000;
+const CF_MAX_TERMS: usize = 32;
+const CF_EPSILON: f64 = 1e-12;
+
 impl Ratio {
     pub fn new(numer: i32, denom: i32) -> Self {
         let (numer, denom) = reduce(numer, denom);
         Self { numer, denom }
     }
 
+    pub fn from_cents(cents: f64, max_denom: i32) -> Self {
+        let x = 2_f64.powf(cents / 1200.);
+        let (mut lo, mut hi) = ((0_i64, 1_i64), (1_i64, 0_i64));
+        let mut best = (1_i64, 1_i64);
+        loop {
+            let m = (lo.0 + hi.0, lo.1 + hi.1);
+            if m.1 > max_denom as i64 {
+                break;
+            }
+            let v = m.0 as f64 / m.1 as f64;
+            if (v - x).abs() < (best.0 as f64 / best.1 as f64 - x).abs() {
+                best = m;
+            }
+            match v.partial_cmp(&x) {
+                Some(Ordering::Less) => lo = m,
+                Some(Ordering::Greater) => hi = m,
+                _ => break,
+            }
+        }
+        Self::new(best.0 as i32, best.1 as i32)
+    }
+
     pub fn normalize(&self) -> Self {
-        let f: f32 = self.into();
+        let (numer, denom) = (self.numer as i64, self.denom as i64);
 
-        match f {
-            n if n < 1. => Self::new(self.numer * 2, self.denom).normalize(),
-            n if n >= 2. => Self::new(self.numer, self.denom * 2).normalize(),
+        match numer {
+            n if n < denom => Self::new(self.numer * 2, self.denom).normalize(),
+            n if n >= 2 * denom => Self::new(self.numer, self.denom * 2).normalize(),
             _ => Self::new(self.numer, self.denom),
         }
     }
@@ -48,35 +165,260 @@ impl Ratio {
         (Self::new(2, 1) / *self).normalize()
     }
 
+    pub fn normalize_with(&self, equivalence: Equivalence) -> Self {
+        equivalence.reduce(*self)
+    }
+
+    pub fn complement_with(&self, equivalence: Equivalence) -> Self {
+        equivalence.complement(*self)
+    }
+
+    pub fn cents(&self) -> f64 {
+        1200. * f64::from(self).log2()
+    }
+
+    pub fn odd_limit(&self) -> i32 {
+        odd_part(self.numer).max(odd_part(self.denom))
+    }
+
+    pub fn tenney_height(&self) -> f64 {
+        (self.numer as f64 * self.denom as f64).log2()
+    }
+
+    pub fn format(&self, style: Style) -> String {
+        match style {
+            Style::Fraction => format!("{}/{}", self.numer, self.denom),
+            Style::Colon => format!("{}:{}", self.numer, self.denom),
+            Style::Monzo => match self.monzo() {
+                Some(monzo) => {
+                    let exponents: Vec<String> = monzo.iter().map(|e| e.to_string()).collect();
+                    format!("[{}⟩", exponents.join(" "))
+                }
+                None => self.format(Style::Fraction),
+            },
+            Style::Cents => cents(self.cents()),
+        }
+    }
+
+    pub fn monzo(&self) -> Option<Vec<i32>> {
+        let mut monzo: Vec<i32> = vec![];
+        let mut add = |n: i32, sign: i32| {
+            for (p, e) in factorize(n) {
+                let index = prime_index(p)?;
+                if monzo.len() <= index {
+                    monzo.resize(index + 1, 0);
+                }
+                monzo[index] += sign * e;
+            }
+            Some(())
+        };
+        add(self.numer, 1)?;
+        add(self.denom, -1)?;
+        Some(monzo)
+    }
+
+    pub fn primes(&self) -> Vec<i32> {
+        let mut primes: Vec<i32> = factorize(self.numer)
+            .iter()
+            .chain(factorize(self.denom).iter())
+            .map(|&(p, _)| p)
+            .collect();
+        primes.sort();
+        primes
+    }
+
+    pub fn prime_limit(&self) -> i32 {
+        self.primes().last().copied().unwrap_or(1)
+    }
+
+    pub fn random(constraints: &RandomConstraints, rng: &mut Rng) -> Option<Self> {
+        let bound = constraints.odd_limit.unwrap_or(RANDOM_ODD_BOUND);
+        let terms: Vec<i32> = (1..=bound)
+            .step_by(2)
+            .filter(|&n| match constraints.prime_limit {
+                Some(limit) => factorize(n).iter().all(|&(p, _)| p <= limit),
+                None => true,
+            })
+            .collect();
+
+        for _ in 0..RANDOM_ATTEMPTS {
+            let odd = Self::new(*rng.choose(&terms)?, *rng.choose(&terms)?);
+            let base = odd.cents();
+            let lowest = ((constraints.min_cents - base) / 1200.).ceil() as i32;
+            let highest = ((constraints.max_cents - base) / 1200.).floor() as i32;
+            if lowest > highest {
+                continue;
+            }
+            let octaves = lowest + rng.below((highest - lowest + 1) as usize) as i32;
+            let octave = 2_i32.checked_pow(octaves.unsigned_abs())?;
+            let (numer, denom) = match octaves {
+                n if n < 0 => (Some(odd.numer), odd.denom.checked_mul(octave)),
+                _ => (odd.numer.checked_mul(octave), Some(odd.denom)),
+            };
+            return Some(Self::new(numer?, denom?));
+        }
+        None
+    }
+
+    pub fn barlow_harmonicity(&self) -> f64 {
+        let (xn, xd) = (indigestibility(self.numer), indigestibility(self.denom));
+        match xn + xd {
+            0. => f64::INFINITY,
+            total => (xn - xd).signum() / total,
+        }
+    }
+
+    pub fn euler_gradus(&self) -> i32 {
+        1 + factorize(self.numer)
+            .iter()
+            .chain(factorize(self.denom).iter())
+            .map(|&(p, e)| e * (p - 1))
+            .sum::<i32>()
+    }
+
+    pub fn mediant(&self, other: &Self) -> Self {
+        Self::new(self.numer + other.numer, self.denom + other.denom)
+    }
+
+    pub fn continued_fraction(&self) -> Vec<i32> {
+        let (mut numer, mut denom) = (self.numer, self.denom);
+        let mut terms = vec![];
+        while denom != 0 {
+            terms.push(numer.div_euclid(denom));
+            (numer, denom) = (denom, numer.rem_euclid(denom));
+        }
+        terms
+    }
+
+    pub fn convergents(&self) -> Vec<Self> {
+        convergents_from_terms(&self.continued_fraction())
+    }
+
+    pub fn cents_convergents(cents: f64, max_denom: i32) -> Vec<Self> {
+        let terms = continued_fraction_of(2_f64.powf(cents / 1200.), CF_MAX_TERMS);
+        convergents_from_terms(&terms)
+            .into_iter()
+            .take_while(|r| r.denom <= max_denom)
+            .collect()
+    }
+
     pub fn pow(&self, exp: i32) -> Self {
         match exp {
-            n if n == 0 => Self::new(1, 1),
+            0 => Self::new(1, 1),
             n if n < 0 => self.complement().pow(-exp),
             _ => Self::new(self.numer.pow(exp as u32), self.denom.pow(exp as u32)),
         }
     }
+
+    pub fn pow_with(&self, exp: i32, equivalence: Equivalence) -> Self {
+        match exp {
+            n if n < 0 => self.complement_with(equivalence).pow(-exp),
+            _ => self.pow(exp),
+        }
+    }
+}
+
+pub fn continued_fraction_of(value: f64, max_terms: usize) -> Vec<i32> {
+    let mut x = value;
+    let mut terms = vec![];
+    while terms.len() < max_terms && x.is_finite() && x.abs() < i32::MAX as f64 {
+        let a = x.floor();
+        terms.push(a as i32);
+        if x - a < CF_EPSILON {
+            break;
+        }
+        x = 1. / (x - a);
+    }
+    terms
+}
+
+fn convergents_from_terms(terms: &[i32]) -> Vec<Ratio> {
+    let (mut h, mut k) = ((1_i64, 0_i64), (0_i64, 1_i64));
+    let mut convergents = vec![];
+    for &a in terms {
+        let next = (a as i64 * h.0 + h.1, a as i64 * k.0 + k.1);
+        if next.0.abs() > i32::MAX as i64 || next.1 > i32::MAX as i64 {
+            break;
+        }
+        convergents.push(Ratio::new(next.0 as i32, next.1 as i32));
+        h = (next.0, h.0);
+        k = (next.1, k.0);
+    }
+    convergents
+}
+
+fn indigestibility(n: i32) -> f64 {
+    factorize(n)
+        .iter()
+        .map(|&(p, e)| {
+            let p = p as f64;
+            2. * e as f64 * (p - 1.).powi(2) / p
+        })
+        .sum()
+}
+
+fn odd_part(n: i32) -> i32 {
+    if n == 0 {
+        return 0;
+    }
+    let mut n = n;
+    while n % 2 == 0 {
+        n /= 2;
+    }
+    n
 }
 
 fn reduce(a: i32, b: i32) -> (i32, i32) {
+    if b == 1 || (a == 1 && b > 0) {
+        return (a, b);
+    }
     let g = gcd(a, b);
     (a / g, b / g)
 }
 
-fn gcd(a: i32, b: i32) -> i32 {
-    let mut a = a;
-    let mut b = b;
-    while a % b > 0 {
-        let t = a % b;
-        a = b;
-        b = t;
+pub(crate) fn gcd(a: i32, b: i32) -> i32 {
+    let (mut u, mut v) = (a.unsigned_abs(), b.unsigned_abs());
+    if u == 0 || v == 0 {
+        return (u | v) as i32 * if b < 0 { -1 } else { 1 };
+    }
+    let shift = (u | v).trailing_zeros();
+    u >>= u.trailing_zeros();
+    loop {
+        v >>= v.trailing_zeros();
+        if u > v {
+            std::mem::swap(&mut u, &mut v);
+        }
+        v -= u;
+        if v == 0 {
+            break;
+        }
+    }
+    let g = (u << shift) as i32;
+    match b < 0 {
+        true => -g,
+        false => g,
     }
-    b
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn format_styles() {
+        let comma = Ratio::new(81, 80);
+
+        assert_eq!(comma.format(Style::Fraction), comma.to_string());
+        assert_eq!(comma.format(Style::Colon), "81:80");
+        assert_eq!(comma.format(Style::Monzo), "[-4 4 -1⟩");
+        assert_eq!(comma.format(Style::Cents), "21.506");
+        assert_eq!(Ratio::new(1, 1).format(Style::Monzo), "[⟩");
+        assert_eq!(
+            Ratio::new(100_000_007, 1).format(Style::Monzo),
+            "100000007/1"
+        );
+    }
+
     #[test]
     fn new_ratio() {
         let r = Ratio::new(3, 2);
@@ -85,6 +427,44 @@ mod tests {
         assert_eq!(r.denom, 2);
     }
 
+    #[test]
+    fn normalizes_sign_of_unit_terms() {
+        let r = Ratio::new(1, -2);
+
+        assert_eq!((r.numer, r.denom), (-1, 2));
+        assert_eq!(r, Ratio::new(2, -4));
+        assert_eq!(Ratio::new(-3, 1), Ratio::new(3, -1));
+    }
+
+    #[test]
+    fn f64_conversion() {
+        let near_octave = Ratio::new(33554431, 16777216);
+
+        assert_eq!(f64::from(Ratio::new(3, 2)), 1.5);
+        assert!(f64::from(&near_octave) < 2.);
+        assert_eq!(f32::from(&near_octave), 2.);
+        assert_eq!(near_octave.normalize(), near_octave);
+        assert!(near_octave.cents() < 1200.);
+    }
+
+    #[test]
+    fn binary_gcd() {
+        assert_eq!(gcd(48, 18), 6);
+        assert_eq!(gcd(17, 5), 1);
+        assert_eq!(gcd(1024, 96), 32);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(-3, 2), 1);
+        assert_eq!(gcd(4, -2), -2);
+        assert_eq!(Ratio::new(4, -2), Ratio::new(-2, 1));
+        assert_eq!(
+            Ratio::new(1, 12),
+            Ratio {
+                numer: 1,
+                denom: 12
+            }
+        );
+    }
+
     #[test]
     fn reduces_ratio() {
         let r = Ratio::new(3, 6);
@@ -93,6 +473,20 @@ mod tests {
         assert_eq!(r.denom, 2);
     }
 
+    #[test]
+    fn display() {
+        assert_eq!(Ratio::new(6, 4).to_string(), "3/2");
+    }
+
+    #[test]
+    fn from_cents() {
+        assert_eq!(Ratio::from_cents(701.955, 100), Ratio::new(3, 2));
+        assert_eq!(Ratio::from_cents(1200., 100), Ratio::new(2, 1));
+        assert_eq!(Ratio::from_cents(-1200., 100), Ratio::new(1, 2));
+        assert_eq!(Ratio::from_cents(400., 10), Ratio::new(5, 4));
+        assert!((Ratio::from_cents(100., 10000).cents() - 100.).abs() < 0.01);
+    }
+
     #[test]
     fn normalize() {
         let r = Ratio::new(1, 2);
@@ -123,6 +517,202 @@ mod tests {
         assert_eq!(r1.complement(), Ratio::new(4, 3))
     }
 
+    #[test]
+    fn ordering() {
+        let mut ratios = vec![Ratio::new(3, 2), Ratio::new(5, 4), Ratio::new(1, 1)];
+        ratios.sort();
+
+        assert_eq!(
+            ratios,
+            vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]
+        );
+    }
+
+    #[test]
+    fn cents() {
+        assert_eq!(Ratio::new(2, 1).cents(), 1200.);
+        assert_eq!(Ratio::new(1, 1).cents(), 0.);
+        assert!((Ratio::new(3, 2).cents() - 701.955).abs() < 1e-3);
+    }
+
+    #[test]
+    fn odd_limit() {
+        assert_eq!(Ratio::new(3, 2).odd_limit(), 3);
+        assert_eq!(Ratio::new(8, 5).odd_limit(), 5);
+        assert_eq!(Ratio::new(9, 7).odd_limit(), 9);
+        assert_eq!(Ratio::new(2, 1).odd_limit(), 1);
+        assert_eq!(Ratio::new(0, 1).odd_limit(), 1);
+    }
+
+    #[test]
+    fn tenney_height() {
+        assert_eq!(Ratio::new(1, 1).tenney_height(), 0.);
+        assert_eq!(Ratio::new(2, 1).tenney_height(), 1.);
+        assert!((Ratio::new(5, 4).tenney_height() - 20_f64.log2()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn monzo() {
+        assert_eq!(Ratio::new(81, 80).monzo(), Some(vec![-4, 4, -1]));
+        assert_eq!(Ratio::new(7, 4).monzo(), Some(vec![-2, 0, 0, 1]));
+        assert_eq!(Ratio::new(1, 1).monzo(), Some(vec![]));
+        assert_eq!(Ratio::new(100_000_007, 1).monzo(), None);
+    }
+
+    #[test]
+    fn primes_and_prime_limit() {
+        let r = Ratio::new(385, 384);
+
+        assert_eq!(r.primes(), vec![2, 3, 5, 7, 11]);
+        assert_eq!(r.prime_limit(), 11);
+        assert_eq!(Ratio::new(1, 1).prime_limit(), 1);
+    }
+
+    #[test]
+    fn random_respects_constraints() {
+        let constraints = RandomConstraints {
+            prime_limit: Some(7),
+            odd_limit: Some(15),
+            min_cents: 0.,
+            max_cents: 1200.,
+        };
+        let mut rng = Rng::seeded(3);
+
+        for _ in 0..200 {
+            let r = Ratio::random(&constraints, &mut rng).unwrap();
+            assert!(r.prime_limit() <= 7);
+            assert!(r.odd_limit() <= 15);
+            assert!((0. ..=1200.).contains(&r.cents()));
+        }
+    }
+
+    #[test]
+    fn random_places_ratio_in_cents_range() {
+        let constraints = RandomConstraints {
+            min_cents: -2400.,
+            max_cents: -1200.,
+            ..Default::default()
+        };
+        let mut rng = Rng::seeded(11);
+
+        for _ in 0..200 {
+            let c = Ratio::random(&constraints, &mut rng).unwrap().cents();
+            assert!((-2400. ..=-1200.).contains(&c));
+        }
+    }
+
+    #[test]
+    fn random_is_reproducible() {
+        let constraints = RandomConstraints::default();
+        let mut a = Rng::seeded(5);
+        let mut b = Rng::seeded(5);
+
+        assert_eq!(
+            Ratio::random(&constraints, &mut a),
+            Ratio::random(&constraints, &mut b)
+        );
+    }
+
+    #[test]
+    fn random_with_impossible_constraints() {
+        let constraints = RandomConstraints {
+            odd_limit: Some(1),
+            min_cents: 100.,
+            max_cents: 200.,
+            ..Default::default()
+        };
+
+        assert_eq!(Ratio::random(&constraints, &mut Rng::seeded(1)), None);
+    }
+
+    #[test]
+    fn random_beyond_i32_octaves() {
+        let constraints = RandomConstraints {
+            min_cents: 1200. * 40.,
+            max_cents: 1200. * 41.,
+            ..Default::default()
+        };
+
+        assert_eq!(Ratio::random(&constraints, &mut Rng::seeded(1)), None);
+    }
+
+    #[test]
+    fn barlow_harmonicity() {
+        assert_eq!(Ratio::new(1, 1).barlow_harmonicity(), f64::INFINITY);
+        assert!((Ratio::new(3, 2).barlow_harmonicity() - 0.2727).abs() < 1e-4);
+        assert!((Ratio::new(4, 3).barlow_harmonicity() - -0.2143).abs() < 1e-4);
+        assert!((Ratio::new(5, 4).barlow_harmonicity() - 0.1190).abs() < 1e-4);
+    }
+
+    #[test]
+    fn euler_gradus() {
+        assert_eq!(Ratio::new(1, 1).euler_gradus(), 1);
+        assert_eq!(Ratio::new(2, 1).euler_gradus(), 2);
+        assert_eq!(Ratio::new(3, 2).euler_gradus(), 4);
+        assert_eq!(Ratio::new(5, 4).euler_gradus(), 7);
+        assert_eq!(Ratio::new(16, 15).euler_gradus(), 11);
+    }
+
+    #[test]
+    fn mediant() {
+        let r1 = Ratio::new(3, 2);
+        let r2 = Ratio::new(5, 4);
+
+        assert_eq!(r1.mediant(&r2), Ratio::new(4, 3));
+    }
+
+    #[test]
+    fn continued_fraction() {
+        assert_eq!(Ratio::new(3, 2).continued_fraction(), vec![1, 2]);
+        assert_eq!(Ratio::new(81, 64).continued_fraction(), vec![1, 3, 1, 3, 4]);
+        assert_eq!(Ratio::new(1, 1).continued_fraction(), vec![1]);
+        assert_eq!(Ratio::new(5, 8).continued_fraction(), vec![0, 1, 1, 1, 2]);
+    }
+
+    #[test]
+    fn convergents_of_a_ratio() {
+        assert_eq!(
+            Ratio::new(81, 64).convergents(),
+            vec![
+                Ratio::new(1, 1),
+                Ratio::new(4, 3),
+                Ratio::new(5, 4),
+                Ratio::new(19, 15),
+                Ratio::new(81, 64)
+            ]
+        );
+    }
+
+    #[test]
+    fn convergents_of_irrational_values() {
+        let golden = continued_fraction_of((1. + 5_f64.sqrt()) / 2., 10);
+        assert_eq!(golden, vec![1; 10]);
+
+        assert_eq!(
+            Ratio::cents_convergents(700., 100),
+            vec![Ratio::new(1, 1), Ratio::new(3, 2), Ratio::new(442, 295)]
+                .into_iter()
+                .filter(|r| r.denom <= 100)
+                .collect::<Vec<Ratio>>()
+        );
+        assert_eq!(
+            Ratio::cents_convergents(1200. * 1.5_f64.log2(), 1000).last(),
+            Some(&Ratio::new(3, 2))
+        );
+    }
+
+    #[test]
+    fn equivalence_aware_operations() {
+        let r = Ratio::new(3, 2);
+
+        assert_eq!(r.normalize_with(Equivalence::Octave), r.normalize());
+        assert_eq!(r.complement_with(Equivalence::Octave), r.complement());
+        assert_eq!(Ratio::new(9, 2).normalize_with(Equivalence::Tritave), r);
+        assert_eq!(r.complement_with(Equivalence::None), Ratio::new(2, 3));
+        assert_eq!(r.pow_with(-2, Equivalence::None), Ratio::new(4, 9));
+        assert_eq!(r.pow_with(-2, Equivalence::Octave), r.pow(-2));
+    }
+
     #[test]
     fn pow() {
         let r = Ratio::new(3, 2);
@@ -0,0 +1,141 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::degree::Degree;
+use crate::ratio::{Ratio, Style};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RatioListError {
+    InvalidToken(String),
+}
+
+impl fmt::Display for RatioListError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RatioListError::InvalidToken(s) => write!(f, "invalid ratio: {s}"),
+        }
+    }
+}
+
+impl Error for RatioListError {}
+
+pub fn parse_degree(token: &str) -> Option<Degree> {
+    if token.contains('.') {
+        return token.parse().ok().map(Degree::from_cents);
+    }
+    let (numer, denom) = token.split_once(['/', ':']).unwrap_or((token, "1"));
+    match (numer.parse::<i32>(), denom.parse::<i32>()) {
+        (Ok(n), Ok(d)) if n > 0 && d > 0 => Some(Degree::from(Ratio::new(n, d))),
+        _ => None,
+    }
+}
+
+pub fn parse_ratios(input: &str) -> Result<Vec<Degree>, RatioListError> {
+    let mut degrees = vec![];
+    for token in input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+    {
+        let invalid = || RatioListError::InvalidToken(token.to_string());
+        let parts: Vec<&str> = token.split(':').collect();
+        match parts.len() {
+            n if n > 2 => {
+                let terms = parts
+                    .iter()
+                    .map(|p| p.parse::<i32>().ok().filter(|&t| t > 0))
+                    .collect::<Option<Vec<i32>>>()
+                    .ok_or_else(invalid)?;
+                degrees.extend(terms.iter().map(|&t| Degree::from(Ratio::new(t, terms[0]))));
+            }
+            _ => degrees.push(parse_degree(token).ok_or_else(invalid)?),
+        }
+    }
+    Ok(degrees)
+}
+
+pub fn format_ratio(ratio: &Ratio, style: Style) -> String {
+    ratio.format(style)
+}
+
+pub fn format_ratios(ratios: &[Ratio], style: Style) -> String {
+    ratios
+        .iter()
+        .map(|r| format_ratio(r, style))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mixed_separators() {
+        assert_eq!(
+            parse_ratios("1/1, 9/8  5/4,\n3:2 7").unwrap(),
+            vec![
+                Degree::from(Ratio::new(1, 1)),
+                Degree::from(Ratio::new(9, 8)),
+                Degree::from(Ratio::new(5, 4)),
+                Degree::from(Ratio::new(3, 2)),
+                Degree::from(Ratio::new(7, 1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_cents_fallback() {
+        assert_eq!(
+            parse_ratios("100.0 386.314").unwrap(),
+            vec![Degree::Cents(100.), Degree::Cents(386.314)]
+        );
+    }
+
+    #[test]
+    fn parse_enumerated_chord() {
+        assert_eq!(
+            parse_ratios("4:5:6").unwrap(),
+            vec![
+                Degree::from(Ratio::new(1, 1)),
+                Degree::from(Ratio::new(5, 4)),
+                Degree::from(Ratio::new(3, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            parse_ratios("3/2 three"),
+            Err(RatioListError::InvalidToken("three".to_string()))
+        );
+        assert_eq!(
+            parse_ratios("0/1"),
+            Err(RatioListError::InvalidToken("0/1".to_string()))
+        );
+        assert_eq!(
+            parse_ratios("4:x:6"),
+            Err(RatioListError::InvalidToken("4:x:6".to_string()))
+        );
+        assert_eq!(parse_ratios("  ,, "), Ok(vec![]));
+    }
+
+    #[test]
+    fn colon_round_trip() {
+        let ratios = [Ratio::new(7, 4), Ratio::new(11, 8)];
+
+        let parsed = parse_ratios(&format_ratios(&ratios, Style::Colon)).unwrap();
+
+        assert_eq!(parsed, ratios.map(Degree::from).to_vec());
+    }
+
+    #[test]
+    fn format_styles() {
+        let ratios = [Ratio::new(3, 2), Ratio::new(5, 4)];
+
+        assert_eq!(format_ratios(&ratios, Style::Fraction), "3/2 5/4");
+        assert_eq!(format_ratios(&ratios, Style::Colon), "3:2 5:4");
+        assert_eq!(format_ratios(&ratios, Style::Cents), "701.955 386.314");
+        assert_eq!(format_ratios(&ratios, Style::Monzo), "[-1 1⟩ [-2 0 1⟩");
+    }
+}
@@ -0,0 +1,134 @@
+use crate::ratio::{Ratio, Style};
+use crate::report::{Report, Table};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Comma {
+    pub name: &'static str,
+    pub ratio: Ratio,
+    pub cents: f64,
+}
+
+const fn ratio(numer: i32, denom: i32) -> Ratio {
+    Ratio { numer, denom }
+}
+
+const fn comma(name: &'static str, ratio: Ratio, cents: f64) -> Comma {
+    Comma { name, ratio, cents }
+}
+
+pub const SYNTONIC_COMMA: Ratio = ratio(81, 80);
+pub const DIDYMUS_COMMA: Ratio = SYNTONIC_COMMA;
+pub const PYTHAGOREAN_COMMA: Ratio = ratio(531441, 524288);
+pub const SCHISMA: Ratio = ratio(32805, 32768);
+pub const DIASCHISMA: Ratio = ratio(2048, 2025);
+pub const LESSER_DIESIS: Ratio = ratio(128, 125);
+pub const MAGIC_COMMA: Ratio = ratio(3125, 3072);
+pub const KLEISMA: Ratio = ratio(15625, 15552);
+pub const SEPTIMAL_COMMA: Ratio = ratio(64, 63);
+pub const SEPTIMAL_KLEISMA: Ratio = ratio(225, 224);
+pub const SEPTIMAL_DIESIS: Ratio = ratio(49, 48);
+pub const SEPTIMAL_SEMICOMMA: Ratio = ratio(126, 125);
+pub const BREEDSMA: Ratio = ratio(2401, 2400);
+pub const UNDECIMAL_DIESIS: Ratio = ratio(33, 32);
+pub const PYTHAGOREAN_LIMMA: Ratio = ratio(256, 243);
+pub const APOTOME: Ratio = ratio(2187, 2048);
+
+pub const COMMAS: [Comma; 15] = [
+    comma("syntonic comma", SYNTONIC_COMMA, 21.506),
+    comma("Pythagorean comma", PYTHAGOREAN_COMMA, 23.460),
+    comma("schisma", SCHISMA, 1.954),
+    comma("diaschisma", DIASCHISMA, 19.553),
+    comma("lesser diesis", LESSER_DIESIS, 41.059),
+    comma("magic comma", MAGIC_COMMA, 29.614),
+    comma("kleisma", KLEISMA, 8.107),
+    comma("septimal comma", SEPTIMAL_COMMA, 27.264),
+    comma("septimal kleisma", SEPTIMAL_KLEISMA, 7.712),
+    comma("septimal diesis", SEPTIMAL_DIESIS, 35.697),
+    comma("septimal semicomma", SEPTIMAL_SEMICOMMA, 13.795),
+    comma("breedsma", BREEDSMA, 0.721),
+    comma("undecimal diesis", UNDECIMAL_DIESIS, 53.273),
+    comma("Pythagorean limma", PYTHAGOREAN_LIMMA, 90.225),
+    comma("apotome", APOTOME, 113.685),
+];
+
+pub fn comma_named(name: &str) -> Option<Comma> {
+    let key = name.trim().to_lowercase().replace(['-', '_'], " ");
+    let key = match key.as_str() {
+        "didymus comma" | "didymus" => "syntonic comma",
+        "archytas comma" => "septimal comma",
+        "ditonic comma" => "Pythagorean comma",
+        k => k,
+    }
+    .to_lowercase();
+    COMMAS.into_iter().find(|c| c.name.to_lowercase() == key)
+}
+
+impl Report for [Comma] {
+    fn tables(&self) -> Vec<Table> {
+        let mut table = Table::new("Commas", &["name", "ratio", "monzo", "cents"]);
+        for comma in self {
+            table.push(vec![
+                comma.name.into(),
+                comma.ratio.to_string().into(),
+                comma.ratio.format(Style::Monzo).into(),
+                comma.cents.into(),
+            ]);
+        }
+        vec![table]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::schema;
+
+    #[test]
+    fn constants_are_reduced_and_match_cents() {
+        for c in COMMAS {
+            assert_eq!(
+                Ratio::new(c.ratio.numer, c.ratio.denom),
+                c.ratio,
+                "{}",
+                c.name
+            );
+            assert!((c.ratio.cents() - c.cents).abs() < 5e-4, "{}", c.name);
+        }
+    }
+
+    #[test]
+    fn comma_relationships() {
+        assert_eq!(PYTHAGOREAN_COMMA / SYNTONIC_COMMA, SCHISMA);
+        assert_eq!(SYNTONIC_COMMA / SCHISMA, DIASCHISMA);
+        assert_eq!(APOTOME / PYTHAGOREAN_LIMMA, PYTHAGOREAN_COMMA);
+        assert_eq!(SEPTIMAL_SEMICOMMA * SEPTIMAL_KLEISMA, SYNTONIC_COMMA);
+        assert_eq!(PYTHAGOREAN_COMMA.monzo(), Some(vec![-19, 12]));
+    }
+
+    #[test]
+    fn comma_list_json() {
+        let json = COMMAS[..2].to_json();
+
+        assert!(json.starts_with("{\"version\":1,\"tables\":[{\"title\":\"Commas\""));
+        assert!(json.contains("{\"name\":\"syntonic comma\",\"ratio\":\"81/80\",\"monzo\":\"[-4 4 -1⟩\",\"cents\":21.506}"));
+    }
+
+    #[test]
+    fn lookup_by_name() {
+        assert_eq!(comma_named("Didymus comma").unwrap().ratio, SYNTONIC_COMMA);
+        assert_eq!(
+            comma_named("septimal-kleisma").unwrap().ratio,
+            SEPTIMAL_KLEISMA
+        );
+        assert_eq!(
+            comma_named("ditonic comma").unwrap().ratio,
+            PYTHAGOREAN_COMMA
+        );
+        assert!(comma_named("wolf").is_none());
+    }
+
+    #[test]
+    fn comma_list_schema() {
+        assert_eq!(schema(&COMMAS[..]), ["Commas: name, ratio, monzo, cents"]);
+    }
+}
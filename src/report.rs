@@ -0,0 +1,270 @@
+use std::fmt;
+
+use crate::metadata::Metadata;
+
+pub const JSON_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Integer(i64),
+    Number(f64),
+    Text(String),
+}
+
+impl Value {
+    pub fn to_json(&self) -> String {
+        match self {
+            Value::Integer(n) => n.to_string(),
+            Value::Number(x) if x.is_finite() => x.to_string(),
+            Value::Text(text) => json_string(text),
+            Value::Null | Value::Number(_) => "null".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "-"),
+            Value::Integer(n) => write!(f, "{n}"),
+            Value::Number(x) => write!(f, "{x:.3}"),
+            Value::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(text: &str) -> Self {
+        Value::Text(text.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(text: String) -> Self {
+        Value::Text(text)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(n: i32) -> Self {
+        Value::Integer(n.into())
+    }
+}
+
+impl From<usize> for Value {
+    fn from(n: usize) -> Self {
+        Value::Integer(n as i64)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(x: f64) -> Self {
+        Value::Number(x)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Table {
+    pub title: String,
+    pub fields: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+    pub fn new(title: &str, fields: &[&str]) -> Self {
+        Self {
+            title: title.to_string(),
+            fields: fields.iter().map(|f| f.to_string()).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<Value>) {
+        self.rows.push(row);
+    }
+
+    pub fn render(&self) -> String {
+        let headers: Vec<String> = self.fields.iter().map(|f| f.replace('_', " ")).collect();
+        let rows: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|r| r.iter().map(|c| c.to_string()).collect())
+            .collect();
+        let widths: Vec<usize> = (0..headers.len())
+            .map(|i| {
+                rows.iter()
+                    .filter_map(|r| r.get(i))
+                    .chain(std::iter::once(&headers[i]))
+                    .map(|c| c.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let line = |cells: &[String]| {
+            cells
+                .iter()
+                .zip(widths.iter())
+                .map(|(c, &w)| format!("{c:<w$}"))
+                .collect::<Vec<String>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+
+        let mut out = String::new();
+        if !self.title.is_empty() {
+            out.push_str(&format!("{}\n", self.title));
+        }
+        out.push_str(&format!("{}\n", line(&headers)));
+        let rule: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+        out.push_str(&format!("{}\n", line(&rule)));
+        for row in &rows {
+            out.push_str(&format!("{}\n", line(row)));
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        let rows: Vec<String> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let values: Vec<String> = self
+                    .fields
+                    .iter()
+                    .zip(row)
+                    .map(|(field, cell)| format!("\"{field}\":{}", cell.to_json()))
+                    .collect();
+                format!("{{{}}}", values.join(","))
+            })
+            .collect();
+        format!(
+            "{{\"title\":{},\"rows\":[{}]}}",
+            json_string(&self.title),
+            rows.join(",")
+        )
+    }
+}
+
+pub trait Report {
+    fn tables(&self) -> Vec<Table>;
+
+    fn metadata(&self) -> Option<&Metadata> {
+        None
+    }
+
+    fn render(&self) -> String {
+        with_metadata(self)
+            .iter()
+            .map(|t| t.render())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Writes `{"version":JSON_VERSION,"tables":[...]}`, where each table is
+    /// `{"title":...,"rows":[...]}` and each row is an object keyed by the
+    /// table's field names. A non-empty `Metadata` table comes first. Ratios,
+    /// degrees and labels are strings, counts and indices are integers, and
+    /// measurements are numbers at full precision; missing and non-finite
+    /// values are `null`. Field names and types only change along with
+    /// `JSON_VERSION`.
+    fn to_json(&self) -> String {
+        let tables: Vec<String> = with_metadata(self).iter().map(|t| t.to_json()).collect();
+        format!(
+            "{{\"version\":{JSON_VERSION},\"tables\":[{}]}}\n",
+            tables.join(",")
+        )
+    }
+}
+
+fn with_metadata<R: Report + ?Sized>(report: &R) -> Vec<Table> {
+    let mut tables = match report.metadata() {
+        Some(metadata) if !metadata.is_empty() => metadata.tables(),
+        _ => vec![],
+    };
+    tables.extend(report.tables());
+    tables
+}
+
+#[cfg(test)]
+pub fn schema(report: &(impl Report + ?Sized)) -> Vec<String> {
+    with_metadata(report)
+        .iter()
+        .map(|t| format!("{}: {}", t.title, t.fields.join(", ")))
+        .collect()
+}
+
+pub fn cents(value: f64) -> String {
+    format!("{value:.3}")
+}
+
+pub fn json_string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_aligns_columns() {
+        let mut table = Table::new("Steps", &["degree", "step_ratio"]);
+        table.push(vec![0.into(), "1/1".into()]);
+        table.push(vec![1.into(), "256/243".into()]);
+
+        assert_eq!(
+            table.render(),
+            "Steps\ndegree  step ratio\n------  ----------\n0       1/1\n1       256/243\n"
+        );
+    }
+
+    #[test]
+    fn table_json_uses_typed_values() {
+        let mut table = Table::new("Steps", &["degree", "step", "cents"]);
+        table.push(vec![0.into(), "256/243".into(), 90.225.into()]);
+        table.push(vec![1.into(), f64::INFINITY.into(), None::<f64>.into()]);
+
+        assert_eq!(
+            table.to_json(),
+            "{\"title\":\"Steps\",\"rows\":[{\"degree\":0,\"step\":\"256/243\",\"cents\":90.225},{\"degree\":1,\"step\":null,\"cents\":null}]}"
+        );
+        assert!(table.render().contains("1       inf      -"));
+        assert_eq!(
+            Value::Number(8.175798915643707).to_json(),
+            "8.175798915643707"
+        );
+        assert_eq!(Value::Number(8.175798915643707).to_string(), "8.176");
+    }
+
+    #[test]
+    fn render_without_title() {
+        let table = Table::new("", &["a"]);
+
+        assert_eq!(table.render(), "a\n-\n");
+    }
+
+    #[test]
+    fn cents_formatting() {
+        assert_eq!(cents(701.95500086), "701.955");
+        assert_eq!(cents(-13.6863), "-13.686");
+    }
+}
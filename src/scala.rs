@@ -0,0 +1,517 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::degree::Degree;
+use crate::diagnostics::{decimal_comma, to_diagnostics, Diagnostics, Problem, Span};
+use crate::equivalence::Equivalence;
+use crate::metadata::Metadata;
+use crate::ratio::Ratio;
+use crate::ratio_list::parse_degree;
+use crate::scale::Scale;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScalaError {
+    MissingDescription,
+    MissingCount,
+    InvalidCount(String),
+    InvalidPitch(String),
+    WrongPitchCount { expected: usize, found: usize },
+    InvalidDirective(String),
+}
+
+impl fmt::Display for ScalaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScalaError::MissingDescription => write!(f, "missing description line"),
+            ScalaError::MissingCount => write!(f, "missing note count line"),
+            ScalaError::InvalidCount(s) => write!(f, "invalid note count: {s}"),
+            ScalaError::InvalidPitch(s) => write!(f, "invalid pitch: {s}"),
+            ScalaError::WrongPitchCount { expected, found } => {
+                write!(f, "expected {expected} pitches, found {found}")
+            }
+            ScalaError::InvalidDirective(s) => write!(f, "invalid directive: {s}"),
+        }
+    }
+}
+
+impl Error for ScalaError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScalaFile {
+    pub description: String,
+    pub scale: Scale,
+}
+
+impl ScalaFile {
+    pub fn new(description: &str, scale: Scale) -> Self {
+        Self {
+            description: description.to_string(),
+            scale,
+        }
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ScalaError> {
+        let (file, problems) = Self::parse_recovering(input);
+        match problems.into_iter().next() {
+            Some(problem) => Err(problem.error),
+            None => file.ok_or(ScalaError::MissingDescription),
+        }
+    }
+
+    pub fn parse_with_diagnostics(input: &str) -> (Option<Self>, Diagnostics) {
+        let (file, problems) = Self::parse_recovering(input);
+        (file, to_diagnostics(problems))
+    }
+
+    fn parse_recovering(input: &str) -> (Option<Self>, Vec<Problem<ScalaError>>) {
+        let mut problems = vec![];
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l))
+            .filter(|(_, l)| !l.starts_with('!'));
+        let end = Span::new(input.lines().count().max(1), 1, 1);
+
+        let Some((_, description)) = lines.next() else {
+            problems.push(Problem::error(end, ScalaError::MissingDescription));
+            return (None, problems);
+        };
+        let Some((count_number, count_line)) = lines.next() else {
+            problems.push(Problem::error(end, ScalaError::MissingCount));
+            return (None, problems);
+        };
+        let count_token = first_token(count_line);
+        let count_span = Span::of(count_number, count_line, count_token);
+        let count = match count_token.parse::<usize>() {
+            Ok(count) => Some(count),
+            Err(_) => {
+                let error = ScalaError::InvalidCount(count_line.trim().to_string());
+                problems.push(Problem::error(count_span, error));
+                None
+            }
+        };
+
+        let mut pitches = vec![];
+        for (number, line) in lines.filter(|(_, l)| !l.trim().is_empty()) {
+            let token = first_token(line);
+            let span = Span::of(number, line, token);
+            let error = ScalaError::InvalidPitch(line.trim().to_string());
+            match parse_pitch(line) {
+                Ok(degree) => pitches.push(degree),
+                Err(_) => match decimal_comma(token).and_then(|t| parse_degree(&t)) {
+                    Some(degree) => {
+                        let message = format!("decimal comma in {token}, read as {degree}");
+                        problems.push(Problem::warning(span, error, message));
+                        pitches.push(degree);
+                    }
+                    None => problems.push(Problem::error(span, error)),
+                },
+            }
+        }
+        if let Some(expected) = count.filter(|&c| c != pitches.len()) {
+            let error = ScalaError::WrongPitchCount {
+                expected,
+                found: pitches.len(),
+            };
+            let message = format!("{error}, using the pitches found");
+            problems.push(Problem::warning(count_span, error, message));
+        }
+
+        let equivalence = pitches
+            .last()
+            .map_or(Equivalence::Octave, |p| Equivalence::from_period(*p));
+        let mut metadata = Metadata::new();
+        for comment in input.lines().filter_map(|l| l.strip_prefix('!')) {
+            metadata.parse_comment(comment);
+        }
+        let mut degrees = vec![Degree::from(Ratio::new(1, 1))];
+        degrees.extend(pitches);
+        let file = Self::new(
+            description.trim(),
+            Scale::with_equivalence(degrees, equivalence).with_metadata(metadata),
+        );
+        (Some(file), problems)
+    }
+
+    pub fn to_scl(&self) -> String {
+        let pitches: Vec<String> = self
+            .scale
+            .degrees
+            .iter()
+            .filter(|d| !d.is_unison())
+            .map(|d| d.to_string())
+            .chain(self.scale.equivalence.period().map(|e| e.to_string()))
+            .collect();
+
+        let description = match self.description.is_empty() {
+            true => self.scale.metadata.description().unwrap_or(""),
+            false => &self.description,
+        };
+        let description = description.replace(['\n', '\r'], " ");
+        let mut out = self.scale.metadata.to_comments("!");
+        out.push_str(&format!("{description}\n {}\n!\n", pitches.len()));
+        for pitch in pitches {
+            out.push_str(&format!(" {pitch}\n"));
+        }
+        out
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AsclReference {
+    pub octave: i32,
+    pub degree: usize,
+    pub frequency: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AsclFile {
+    pub scala: ScalaFile,
+    pub reference: Option<AsclReference>,
+    pub note_names: Vec<String>,
+    pub source: Option<String>,
+    pub link: Option<String>,
+}
+
+impl AsclFile {
+    pub fn new(scala: ScalaFile) -> Self {
+        Self {
+            scala,
+            reference: None,
+            note_names: vec![],
+            source: None,
+            link: None,
+        }
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ScalaError> {
+        let mut ascl = Self::new(ScalaFile::parse(input)?);
+
+        for line in input.lines() {
+            let directive = match line.strip_prefix('!').map(|l| l.trim_start()) {
+                Some(l) => match l.strip_prefix("@ABL") {
+                    Some(d) => d.trim(),
+                    None => continue,
+                },
+                None => continue,
+            };
+            let (name, rest) = directive.split_once(' ').unwrap_or((directive, ""));
+            let rest = rest.trim();
+            match name {
+                "REFERENCE_PITCH" => ascl.reference = Some(parse_reference(rest)?),
+                "NOTE_NAMES" => ascl.note_names = split_quoted(rest),
+                "SOURCE" => ascl.source = Some(rest.to_string()),
+                "LINK" => ascl.link = Some(rest.to_string()),
+                _ => {}
+            }
+        }
+        Ok(ascl)
+    }
+
+    pub fn to_ascl(&self) -> String {
+        let mut out = self.scala.to_scl();
+        if let Some(r) = self.reference {
+            out.push_str(&format!(
+                "! @ABL REFERENCE_PITCH {} {} {}\n",
+                r.octave, r.degree, r.frequency
+            ));
+        }
+        if !self.note_names.is_empty() {
+            let names: Vec<String> = self
+                .note_names
+                .iter()
+                .map(|n| match n.contains(' ') {
+                    true => format!("\"{n}\""),
+                    false => n.clone(),
+                })
+                .collect();
+            out.push_str(&format!("! @ABL NOTE_NAMES {}\n", names.join(" ")));
+        }
+        if let Some(source) = &self.source {
+            out.push_str(&format!("! @ABL SOURCE {source}\n"));
+        }
+        if let Some(link) = &self.link {
+            out.push_str(&format!("! @ABL LINK {link}\n"));
+        }
+        out
+    }
+}
+
+fn first_token(line: &str) -> &str {
+    line.split_whitespace().next().unwrap_or("")
+}
+
+fn parse_pitch(line: &str) -> Result<Degree, ScalaError> {
+    parse_degree(first_token(line)).ok_or_else(|| ScalaError::InvalidPitch(line.trim().to_string()))
+}
+
+fn parse_reference(rest: &str) -> Result<AsclReference, ScalaError> {
+    let invalid = || ScalaError::InvalidDirective(format!("REFERENCE_PITCH {rest}"));
+    let parts: Vec<&str> = rest.split_whitespace().collect();
+    match parts[..] {
+        [octave, degree, frequency] => Ok(AsclReference {
+            octave: octave.parse().map_err(|_| invalid())?,
+            degree: degree.parse().map_err(|_| invalid())?,
+            frequency: frequency.parse().map_err(|_| invalid())?,
+        }),
+        _ => Err(invalid()),
+    }
+}
+
+fn split_quoted(rest: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in rest.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PENTATONIC: &str = "! pentatonic.scl
+!
+Just pentatonic
+ 5
+!
+ 9/8
+ 5/4
+ 3/2
+ 5/3
+ 2/1
+";
+
+    #[test]
+    fn parse_scl() {
+        let scl = ScalaFile::parse(PENTATONIC).unwrap();
+
+        assert_eq!(scl.description, "Just pentatonic");
+        assert_eq!(
+            scl.scale.ratios(),
+            Some(vec![
+                Ratio::new(1, 1),
+                Ratio::new(9, 8),
+                Ratio::new(5, 4),
+                Ratio::new(3, 2),
+                Ratio::new(5, 3)
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_scl_with_cents_and_integers() {
+        let scl = ScalaFile::parse("cents\n3\n386.314 major third\n701.955\n2\n").unwrap();
+
+        assert_eq!(
+            scl.scale.degrees,
+            vec![
+                Degree::from(Ratio::new(1, 1)),
+                Degree::Cents(386.314),
+                Degree::Cents(701.955)
+            ]
+        );
+    }
+
+    #[test]
+    fn scl_round_trip_with_cents() {
+        let scl = ScalaFile::new(
+            "mixed",
+            Scale::new(vec![
+                Degree::from(Ratio::new(1, 1)),
+                Degree::from(100.),
+                Degree::from(Ratio::new(5, 4)),
+                Degree::from(701.955),
+            ]),
+        );
+        let written = scl.to_scl();
+
+        assert_eq!(written, "mixed\n 4\n!\n 100.0\n 5/4\n 701.955\n 2/1\n");
+        assert_eq!(ScalaFile::parse(&written).unwrap(), scl);
+    }
+
+    #[test]
+    fn parse_tritave_scl() {
+        let scl = ScalaFile::parse("bp\n3\n9/7\n5/3\n3/1\n").unwrap();
+
+        assert_eq!(scl.scale.equivalence, Equivalence::Tritave);
+        assert_eq!(scl.scale.len(), 3);
+        assert_eq!(scl.to_scl(), "bp\n 3\n!\n 9/7\n 5/3\n 3/1\n");
+    }
+
+    #[test]
+    fn arbitrary_periods_round_trip() {
+        let fifths = ScalaFile::parse("fifths\n2\n6/5\n3/2\n").unwrap();
+
+        assert_eq!(
+            fifths.scale.equivalence,
+            Equivalence::Period(Ratio::new(3, 2))
+        );
+        assert_eq!(fifths.scale.len(), 2);
+        assert_eq!(fifths.to_scl(), "fifths\n 2\n!\n 6/5\n 3/2\n");
+
+        let stretched = ScalaFile::parse("stretched\n2\n602.0\n1204.0\n").unwrap();
+
+        assert_eq!(stretched.scale.equivalence, Equivalence::CentsPeriod(1204.));
+        assert_eq!(stretched.to_scl(), "stretched\n 2\n!\n 602.0\n 1204.0\n");
+        assert_eq!(ScalaFile::parse(&stretched.to_scl()).unwrap(), stretched);
+    }
+
+    #[test]
+    fn parse_scl_errors() {
+        assert_eq!(
+            ScalaFile::parse("! only\n"),
+            Err(ScalaError::MissingDescription)
+        );
+        assert_eq!(ScalaFile::parse("name\n"), Err(ScalaError::MissingCount));
+        assert_eq!(
+            ScalaFile::parse("name\nfive\n"),
+            Err(ScalaError::InvalidCount("five".to_string()))
+        );
+        assert_eq!(
+            ScalaFile::parse("name\n1\nthree halves\n"),
+            Err(ScalaError::InvalidPitch("three halves".to_string()))
+        );
+        assert_eq!(
+            ScalaFile::parse("name\n2\n3/2\n"),
+            Err(ScalaError::WrongPitchCount {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn scl_round_trip() {
+        let scl = ScalaFile::parse(PENTATONIC).unwrap();
+        let written = scl.to_scl();
+
+        assert_eq!(
+            written,
+            "Just pentatonic\n 5\n!\n 9/8\n 5/4\n 3/2\n 5/3\n 2/1\n"
+        );
+        assert_eq!(ScalaFile::parse(&written).unwrap(), scl);
+    }
+
+    #[test]
+    fn parse_ascl_directives() {
+        let input = format!(
+            "{PENTATONIC}! @ABL REFERENCE_PITCH 4 3 440\n! @ABL NOTE_NAMES C D E G \"A flat\"\n! @ABL SOURCE Partch\n! @ABL LINK https://example.com\n"
+        );
+
+        let ascl = AsclFile::parse(&input).unwrap();
+
+        assert_eq!(ascl.scala.scale.len(), 5);
+        assert_eq!(
+            ascl.reference,
+            Some(AsclReference {
+                octave: 4,
+                degree: 3,
+                frequency: 440.
+            })
+        );
+        assert_eq!(ascl.note_names, vec!["C", "D", "E", "G", "A flat"]);
+        assert_eq!(ascl.source, Some("Partch".to_string()));
+        assert_eq!(ascl.link, Some("https://example.com".to_string()));
+    }
+
+    #[test]
+    fn parse_ascl_invalid_reference() {
+        let input = format!("{PENTATONIC}! @ABL REFERENCE_PITCH 4 x\n");
+
+        assert_eq!(
+            AsclFile::parse(&input),
+            Err(ScalaError::InvalidDirective(
+                "REFERENCE_PITCH 4 x".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn ascl_round_trip() {
+        let mut ascl = AsclFile::new(ScalaFile::parse(PENTATONIC).unwrap());
+        ascl.reference = Some(AsclReference {
+            octave: 4,
+            degree: 0,
+            frequency: 261.63,
+        });
+        ascl.note_names = vec![
+            "C".into(),
+            "D".into(),
+            "E".into(),
+            "G".into(),
+            "A flat".into(),
+        ];
+
+        assert_eq!(AsclFile::parse(&ascl.to_ascl()).unwrap(), ascl);
+    }
+
+    #[test]
+    fn diagnostics_point_at_bad_lines() {
+        let input = "broken\n 4\n!\n 9/8\n 701,955\n foo\n 2/1\n";
+        let (file, diagnostics) = ScalaFile::parse_with_diagnostics(input);
+
+        assert_eq!(file.unwrap().scale.len(), 3);
+        assert_eq!(
+            diagnostics
+                .items
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "5:2: warning: decimal comma in 701,955, read as 701.955",
+                "6:2: error: invalid pitch: foo",
+                "2:2: warning: expected 4 pitches, found 3, using the pitches found",
+            ]
+        );
+        assert_eq!(
+            ScalaFile::parse(input),
+            Err(ScalaError::InvalidPitch("701,955".to_string()))
+        );
+    }
+
+    #[test]
+    fn diagnostics_for_missing_count() {
+        let (file, diagnostics) = ScalaFile::parse_with_diagnostics("! only\ntitle\n");
+
+        assert!(file.is_none());
+        assert_eq!(diagnostics.items[0].span, Span::new(2, 1, 1));
+        assert_eq!(diagnostics.items[0].message, "missing note count line");
+    }
+
+    #[test]
+    fn metadata_round_trip() {
+        let metadata = Metadata::new()
+            .with("author", "Harry Partch")
+            .with("description", "Otonal hexad")
+            .with_tag("11-limit");
+        let scale = Scale::new(vec![Ratio::new(1, 1), Ratio::new(11, 8)]).with_metadata(metadata);
+        let scl = ScalaFile::new("", scale).to_scl();
+
+        assert!(scl.starts_with("! @META author Harry Partch\n"));
+        let parsed = ScalaFile::parse(&scl).unwrap();
+        assert_eq!(parsed.description, "Otonal hexad");
+        assert_eq!(parsed.scale.metadata.author(), Some("Harry Partch"));
+        assert_eq!(parsed.scale.metadata.tags, vec!["11-limit"]);
+
+        let scale = Scale::new(vec![Ratio::new(1, 1)])
+            .with_metadata(Metadata::new().with("description", "two\nlines"));
+        let scl = ScalaFile::new("", scale).to_scl();
+
+        assert_eq!(ScalaFile::parse(&scl).unwrap().description, "two lines");
+        assert!(scl.contains("! @META description two\\nlines\n"));
+    }
+}
@@ -0,0 +1,1255 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::analysis::propriety_name;
+use crate::chord::Chord;
+use crate::consonance::Metric;
+use crate::degree::Degree;
+use crate::equivalence::Equivalence;
+use crate::metadata::Metadata;
+use crate::mos::StepPattern;
+use crate::order::{compare_degrees, SortOrder};
+use crate::progress::{report, Cancelled, Control, Progress};
+use crate::ratio::{Ratio, Style};
+use crate::report::{Report, Table};
+use crate::tolerance::Tolerance;
+
+const PROPRIETY_EPSILON: f64 = 1e-9;
+
+#[macro_export]
+macro_rules! scale {
+    ($($numer:literal / $denom:literal),* $(,)?) => {
+        $crate::scale::Scale::new(vec![$($crate::ratio::Ratio::new($numer, $denom)),*])
+    };
+    ($($degree:expr),* $(,)?) => {
+        $crate::scale::Scale::new(vec![$($crate::degree::Degree::from($degree)),*])
+    };
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scale {
+    pub degrees: Vec<Degree>,
+    pub equivalence: Equivalence,
+    pub metadata: Metadata,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdoCollision {
+    pub step: i32,
+    pub degrees: Vec<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdoMapping {
+    pub edo: i32,
+    pub steps: Vec<i32>,
+    pub errors: Vec<f64>,
+    pub collisions: Vec<EdoCollision>,
+    pub gaps: Vec<i32>,
+    pub metadata: Metadata,
+}
+
+impl EdoMapping {
+    pub fn is_injective(&self) -> bool {
+        self.collisions.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DyadMatrix {
+    pub metric: Metric,
+    pub scores: Vec<Vec<f64>>,
+}
+
+impl DyadMatrix {
+    pub fn total(&self) -> f64 {
+        self.scores
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|&(j, _)| i != j)
+                    .map(|(_, s)| s)
+                    .sum::<f64>()
+            })
+            .sum()
+    }
+
+    pub fn mean(&self) -> f64 {
+        let n = self.scores.len();
+        match n {
+            0 | 1 => 0.,
+            _ => self.total() / (n * (n - 1)) as f64,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Propriety {
+    StrictlyProper,
+    Proper,
+    Improper,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaleStatistics {
+    pub steps: Vec<Degree>,
+    pub smallest_step: Degree,
+    pub largest_step: Degree,
+    pub step_ratio: f64,
+    pub mean_step: f64,
+    pub step_variance: f64,
+    pub distinct_steps: usize,
+    pub propriety: Propriety,
+    pub spans: Vec<Vec<(Degree, usize)>>,
+    pub metadata: Metadata,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Alignment {
+    #[default]
+    Index,
+    Rotation(usize),
+    Nearest,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChordMatch {
+    pub root: usize,
+    pub degrees: Vec<usize>,
+    pub error: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mode {
+    pub rotation: usize,
+    pub label: String,
+    pub scale: Scale,
+    pub brightness: f64,
+}
+
+impl Report for ScaleStatistics {
+    fn tables(&self) -> Vec<Table> {
+        let mut summary = Table::new(
+            "Scale statistics",
+            &[
+                "smallest_step",
+                "largest_step",
+                "step_ratio",
+                "mean_step",
+                "step_variance",
+                "distinct_steps",
+                "propriety",
+            ],
+        );
+        summary.push(vec![
+            self.smallest_step.to_string().into(),
+            self.largest_step.to_string().into(),
+            self.step_ratio.into(),
+            self.mean_step.into(),
+            self.step_variance.into(),
+            self.distinct_steps.into(),
+            propriety_name(self.propriety).into(),
+        ]);
+
+        let mut steps = Table::new("Steps", &["index", "step", "cents"]);
+        for (i, step) in self.steps.iter().enumerate() {
+            steps.push(vec![i.into(), step.to_string().into(), step.cents().into()]);
+        }
+        vec![summary, steps]
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        Some(&self.metadata)
+    }
+}
+
+impl Report for EdoMapping {
+    fn tables(&self) -> Vec<Table> {
+        let mut mapping = Table::new(
+            &format!("{}-EDO mapping", self.edo),
+            &["index", "step", "error"],
+        );
+        for (i, (step, error)) in self.steps.iter().zip(&self.errors).enumerate() {
+            mapping.push(vec![i.into(), (*step).into(), (*error).into()]);
+        }
+
+        let mut collisions = Table::new("Collisions", &["step", "degrees"]);
+        for c in &self.collisions {
+            let degrees: Vec<String> = c.degrees.iter().map(|d| d.to_string()).collect();
+            collisions.push(vec![c.step.into(), degrees.join(" ").into()]);
+        }
+
+        let mut gaps = Table::new("Gaps", &["step"]);
+        for step in &self.gaps {
+            gaps.push(vec![(*step).into()]);
+        }
+        vec![mapping, collisions, gaps]
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        Some(&self.metadata)
+    }
+}
+
+impl Scale {
+    pub fn new<T: Into<Degree>>(degrees: Vec<T>) -> Self {
+        Self::with_equivalence(degrees, Equivalence::Octave)
+    }
+
+    /// Degrees are reduced by `equivalence`, sorted by ascending pitch and
+    /// deduplicated. Two ratios compare exactly; otherwise degrees compare
+    /// by cents.
+    pub fn with_equivalence<T: Into<Degree>>(degrees: Vec<T>, equivalence: Equivalence) -> Self {
+        let mut degrees: Vec<Degree> = degrees
+            .into_iter()
+            .map(|d| d.into().reduce(equivalence))
+            .collect();
+        degrees.sort_by(Degree::total_cmp);
+        degrees.dedup_by(|a, b| a.approx_eq(b));
+        Self {
+            degrees,
+            equivalence,
+            metadata: Metadata::new(),
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn canonicalize(&self) -> Self {
+        Self::with_equivalence(self.degrees.clone(), self.equivalence)
+            .with_metadata(self.metadata.clone())
+    }
+
+    pub fn is_canonical(&self) -> bool {
+        self.degrees.windows(2).all(|w| w[0] < w[1])
+    }
+
+    pub fn len(&self) -> usize {
+        self.degrees.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.degrees.is_empty()
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.degrees.iter().all(|d| d.is_exact())
+    }
+
+    /// A copy of the degrees sorted by `order`; see [`SortOrder`].
+    pub fn sorted_degrees(&self, order: SortOrder) -> Vec<Degree> {
+        let mut degrees = self.degrees.clone();
+        degrees.sort_by(|a, b| compare_degrees(a, b, order));
+        degrees
+    }
+
+    pub fn ratios(&self) -> Option<Vec<Ratio>> {
+        self.degrees.iter().map(|d| d.ratio()).collect()
+    }
+
+    pub fn cents(&self) -> Vec<f64> {
+        self.degrees.iter().map(|d| d.cents()).collect()
+    }
+
+    pub fn format(&self, style: Style) -> String {
+        match (style, self.ratios()) {
+            (Style::Colon, Some(ratios)) => Chord::new(ratios).format(style),
+            _ => self
+                .degrees
+                .iter()
+                .map(|d| d.format(style))
+                .collect::<Vec<String>>()
+                .join(" "),
+        }
+    }
+
+    pub fn interval(&self, from: usize, to: usize) -> Degree {
+        self.degrees[from]
+            .interval_to(&self.degrees[to])
+            .reduce(self.equivalence)
+    }
+
+    fn spans(&self, span: usize) -> Vec<(usize, usize)> {
+        match self.equivalence.period() {
+            Some(_) => (0..self.len())
+                .map(|i| (i, (i + span) % self.len()))
+                .collect(),
+            None => (0..self.len().saturating_sub(span))
+                .map(|i| (i, i + span))
+                .collect(),
+        }
+    }
+
+    pub fn mode(&self, rotation: usize) -> Scale {
+        let n = self.len();
+        Self::with_equivalence(
+            (0..n)
+                .map(|k| self.interval(rotation, (rotation + k) % n))
+                .collect(),
+            self.equivalence,
+        )
+    }
+
+    pub fn modes_by_brightness(&self) -> Vec<Mode> {
+        let mut modes: Vec<Mode> = (0..self.len())
+            .map(|rotation| {
+                let scale = self.mode(rotation);
+                Mode {
+                    rotation,
+                    label: format!("mode {} on {}", rotation + 1, self.degrees[rotation]),
+                    brightness: scale.cents().iter().sum(),
+                    scale,
+                }
+            })
+            .collect();
+        modes.sort_by(|a, b| a.brightness.total_cmp(&b.brightness));
+        modes
+    }
+
+    pub fn find_chords(
+        &self,
+        pattern: &[Degree],
+        tolerance: impl Into<Tolerance>,
+    ) -> Vec<ChordMatch> {
+        let tolerance = tolerance.into();
+        let Some(base) = pattern.first() else {
+            return vec![];
+        };
+        let intervals: Vec<f64> = pattern
+            .iter()
+            .map(|d| self.equivalence.reduce_cents(d.cents() - base.cents()))
+            .collect();
+        (0..self.len())
+            .filter_map(|root| {
+                let mut degrees = vec![];
+                let mut error: f64 = 0.;
+                for &target in &intervals {
+                    let (j, distance) = (0..self.len())
+                        .map(|j| {
+                            let interval = self.interval(root, j).cents();
+                            (j, self.equivalence.distance(interval, target))
+                        })
+                        .min_by(|a, b| a.1.total_cmp(&b.1))?;
+                    if !tolerance.accepts(distance) {
+                        return None;
+                    }
+                    degrees.push(j);
+                    error = error.max(distance);
+                }
+                Some(ChordMatch {
+                    root,
+                    degrees,
+                    error,
+                })
+            })
+            .collect()
+    }
+
+    pub fn align(&self, other: &Scale, alignment: Alignment) -> Option<Scale> {
+        if self.len() != other.len() {
+            return None;
+        }
+        let distance = |s: &Scale| -> f64 {
+            self.cents()
+                .iter()
+                .zip(s.cents().iter())
+                .map(|(a, b)| (a - b).powi(2))
+                .sum()
+        };
+        match alignment {
+            Alignment::Index => Some(other.clone()),
+            Alignment::Rotation(r) => Some(other.mode(r % other.len().max(1))),
+            Alignment::Nearest => (0..other.len())
+                .map(|r| other.mode(r))
+                .min_by(|a, b| distance(a).total_cmp(&distance(b))),
+        }
+    }
+
+    pub fn interpolate(&self, other: &Scale, t: f64, alignment: Alignment) -> Option<Scale> {
+        let target = self.align(other, alignment)?;
+        match t {
+            t if t <= 0. => Some(self.clone()),
+            t if t >= 1. => Some(target),
+            t => Some(Self::with_equivalence(
+                self.cents()
+                    .iter()
+                    .zip(target.cents().iter())
+                    .map(|(a, b)| (1. - t) * a + t * b)
+                    .collect(),
+                self.equivalence,
+            )),
+        }
+    }
+
+    pub fn unroll(&self, equaves: Range<i32>) -> Vec<Degree> {
+        match self.equivalence.period() {
+            Some(equave) => self.unroll_with(equaves, equave),
+            None if equaves.contains(&0) => self.degrees.clone(),
+            None => vec![],
+        }
+    }
+
+    pub fn unroll_with(&self, equaves: Range<i32>, equave: impl Into<Degree>) -> Vec<Degree> {
+        let equave = equave.into();
+        equaves
+            .flat_map(|k| {
+                self.degrees.iter().map(move |d| match (d, equave) {
+                    (Degree::Ratio(r), Degree::Ratio(e)) if k >= 0 => Degree::Ratio(*r * e.pow(k)),
+                    (Degree::Ratio(r), Degree::Ratio(e)) => Degree::Ratio(*r / e.pow(-k)),
+                    _ => Degree::Cents(d.cents() + k as f64 * equave.cents()),
+                })
+            })
+            .collect()
+    }
+
+    pub fn reflect(&self, axis: impl Into<Degree>) -> Scale {
+        let axis: Degree = axis.into();
+        Self::with_equivalence(
+            self.degrees.iter().map(|d| d.interval_to(&axis)).collect(),
+            self.equivalence,
+        )
+    }
+
+    pub fn complement_table(&self) -> Vec<(Degree, Degree)> {
+        let equave = self
+            .equivalence
+            .period()
+            .unwrap_or(Degree::Ratio(Ratio::new(1, 1)));
+        self.degrees
+            .iter()
+            .map(|d| (*d, d.interval_to(&equave).reduce(self.equivalence)))
+            .collect()
+    }
+
+    pub fn steps(&self) -> Vec<Degree> {
+        self.spans(1)
+            .into_iter()
+            .map(|(i, j)| self.interval(i, j))
+            .collect()
+    }
+
+    pub fn step_pattern(&self) -> Option<StepPattern> {
+        let steps: Vec<f64> = self.steps().iter().map(|d| d.cents()).collect();
+        StepPattern::from_steps(&steps)
+    }
+
+    pub fn span_histogram(&self, span: usize) -> Vec<(Degree, usize)> {
+        let mut histogram: Vec<(Degree, usize)> = vec![];
+        for (i, j) in self.spans(span) {
+            let interval = self.interval(i, j);
+            match histogram.iter_mut().find(|(d, _)| d.approx_eq(&interval)) {
+                Some((_, count)) => *count += 1,
+                None => histogram.push((interval, 1)),
+            }
+        }
+        histogram.sort_by(|a, b| a.0.total_cmp(&b.0));
+        histogram
+    }
+
+    pub fn statistics(&self) -> Option<ScaleStatistics> {
+        if self.len() < 2 {
+            return None;
+        }
+        let steps = self.steps();
+        let cents: Vec<f64> = steps.iter().map(|s| s.cents()).collect();
+        let by_size = |a: &&Degree, b: &&Degree| a.total_cmp(b);
+        let smallest_step = *steps.iter().min_by(by_size)?;
+        let largest_step = *steps.iter().max_by(by_size)?;
+        let mean_step = cents.iter().sum::<f64>() / cents.len() as f64;
+        let step_variance =
+            cents.iter().map(|c| (c - mean_step).powi(2)).sum::<f64>() / cents.len() as f64;
+
+        Some(ScaleStatistics {
+            smallest_step,
+            largest_step,
+            step_ratio: largest_step.cents() / smallest_step.cents(),
+            mean_step,
+            step_variance,
+            distinct_steps: self.span_histogram(1).len(),
+            propriety: self.propriety(),
+            spans: (1..self.len()).map(|k| self.span_histogram(k)).collect(),
+            steps,
+            metadata: self.metadata.clone(),
+        })
+    }
+
+    pub fn prime_limit(&self) -> Option<i32> {
+        self.ratios()
+            .map(|ratios| ratios.iter().map(|r| r.prime_limit()).max().unwrap_or(1))
+    }
+
+    pub fn propriety(&self) -> Propriety {
+        let sizes: Vec<(f64, f64)> = (1..self.len())
+            .map(|k| {
+                let cents: Vec<f64> = self
+                    .spans(k)
+                    .into_iter()
+                    .map(|(i, j)| self.interval(i, j).cents())
+                    .collect();
+                let min = cents.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = cents.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                (min, max)
+            })
+            .collect();
+
+        let mut propriety = Propriety::StrictlyProper;
+        for w in sizes.windows(2) {
+            let (max, next_min) = (w[0].1, w[1].0);
+            if max > next_min + PROPRIETY_EPSILON {
+                return Propriety::Improper;
+            }
+            if max > next_min - PROPRIETY_EPSILON {
+                propriety = Propriety::Proper;
+            }
+        }
+        propriety
+    }
+
+    pub fn edo_fit(&self, edo: i32) -> Option<f64> {
+        let mapping = self.quantize_to_edo(edo)?;
+        Some(mapping.errors.iter().fold(0., |max, e| e.abs().max(max)))
+    }
+
+    pub fn best_edo(&self, min: i32, max: i32) -> Option<(i32, f64)> {
+        self.best_edo_with_progress(min, max, &mut |_| Control::Continue)
+            .unwrap_or(None)
+    }
+
+    pub fn best_edo_with_progress(
+        &self,
+        min: i32,
+        max: i32,
+        callback: &mut dyn FnMut(Progress) -> Control,
+    ) -> Result<Option<(i32, f64)>, Cancelled> {
+        let edos: Vec<i32> = (min.max(1)..=max).collect();
+        let mut best: Option<(i32, f64)> = None;
+        for (i, &edo) in edos.iter().enumerate() {
+            if let (Some(fit), Some(mapping)) = (self.edo_fit(edo), self.quantize_to_edo(edo)) {
+                if best.is_none_or(|(_, e)| fit < e) && mapping.is_injective() {
+                    best = Some((edo, fit));
+                }
+            }
+            report(callback, i + 1, edos.len())?;
+        }
+        Ok(best)
+    }
+
+    pub fn dyad_matrix(&self, metric: Metric) -> DyadMatrix {
+        let scores = (0..self.len())
+            .map(|i| {
+                (0..self.len())
+                    .map(|j| match self.equivalence {
+                        Equivalence::None => self.interval(i.min(j), i.max(j)),
+                        _ => self.interval(i, j),
+                    })
+                    .map(|interval| metric.score_degree(&interval))
+                    .collect()
+            })
+            .collect();
+        DyadMatrix { metric, scores }
+    }
+
+    pub fn quantize_to_edo(&self, edo: i32) -> Option<EdoMapping> {
+        self.quantize_to_edo_with(edo, Tolerance::default())
+    }
+
+    pub fn quantize_to_edo_with(
+        &self,
+        edo: i32,
+        tolerance: impl Into<Tolerance>,
+    ) -> Option<EdoMapping> {
+        if edo <= 0 {
+            return None;
+        }
+        let tolerance = tolerance.into();
+        let equave = self.equivalence.equave_cents();
+        let step_size = equave.unwrap_or(1200.) / edo as f64;
+        let (steps, errors): (Vec<i32>, Vec<f64>) = self
+            .cents()
+            .iter()
+            .map(|c| {
+                let step = tolerance.round(c / step_size) as i32;
+                let error = c - step as f64 * step_size;
+                match equave {
+                    Some(_) => (step.rem_euclid(edo), error),
+                    None => (step, error),
+                }
+            })
+            .unzip();
+
+        let mut by_step: BTreeMap<i32, Vec<usize>> = BTreeMap::new();
+        for (i, &step) in steps.iter().enumerate() {
+            by_step.entry(step).or_default().push(i);
+        }
+        let range = match equave {
+            Some(_) => 0..edo,
+            None => match (by_step.keys().next(), by_step.keys().next_back()) {
+                (Some(&min), Some(&max)) => min..max + 1,
+                _ => 0..0,
+            },
+        };
+        let gaps = range.filter(|s| !by_step.contains_key(s)).collect();
+        let collisions = by_step
+            .into_iter()
+            .filter(|(_, degrees)| degrees.len() > 1)
+            .map(|(step, degrees)| EdoCollision { step, degrees })
+            .collect();
+
+        Some(EdoMapping {
+            edo,
+            steps,
+            errors,
+            collisions,
+            gaps,
+            metadata: self.metadata.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pitch::Pitch;
+    use crate::report::schema;
+    use crate::tolerance::Rounding;
+
+    fn just_major() -> Scale {
+        Scale::new(vec![
+            Ratio::new(1, 1),
+            Ratio::new(9, 8),
+            Ratio::new(5, 4),
+            Ratio::new(4, 3),
+            Ratio::new(3, 2),
+            Ratio::new(5, 3),
+            Ratio::new(15, 8),
+        ])
+    }
+
+    #[test]
+    fn zero_degrees_are_kept() {
+        let scale = Scale::new(vec![Ratio::new(1, 1), Ratio::new(0, 1)]);
+
+        assert_eq!(scale.len(), 2);
+        assert_eq!(scale.degrees[1], Degree::from(Ratio::new(1, 1)));
+    }
+
+    #[test]
+    fn reports_carry_scale_metadata() {
+        let scale = just_major().with_metadata(Metadata::new().with("author", "Zarlino"));
+
+        let rendered = scale.statistics().unwrap().render();
+        assert!(rendered.starts_with(
+            "Metadata\nkey     value\n------  -------\nauthor  Zarlino\n\nScale statistics\n"
+        ));
+        assert!(scale
+            .quantize_to_edo(12)
+            .unwrap()
+            .render()
+            .starts_with("Metadata\n"));
+        assert!(just_major()
+            .statistics()
+            .unwrap()
+            .render()
+            .starts_with("Scale statistics\n"));
+    }
+
+    #[test]
+    fn statistics_and_edo_reports() {
+        let statistics = just_major().statistics().unwrap();
+        let json = statistics.to_json();
+
+        assert!(json.contains("\"smallest_step\":\"16/15\",\"largest_step\":\"9/8\""));
+        assert!(json.contains(&format!(
+            "{{\"index\":0,\"step\":\"9/8\",\"cents\":{}}}",
+            Ratio::new(9, 8).cents()
+        )));
+        assert!(json.contains("\"distinct_steps\":3,\"propriety\":\"strictly proper\""));
+        let unbounded = ScaleStatistics {
+            step_ratio: f64::INFINITY,
+            ..statistics.clone()
+        };
+        assert!(unbounded.to_json().contains("\"step_ratio\":null"));
+        let mapping = just_major().quantize_to_edo(12).unwrap().to_json();
+        assert!(mapping.contains("\"title\":\"12-EDO mapping\""));
+        assert!(mapping.contains(&format!(
+            "{{\"index\":2,\"step\":4,\"error\":{}}}",
+            Ratio::new(5, 4).cents() - 400.
+        )));
+        assert!(mapping.contains("{\"title\":\"Gaps\",\"rows\":[{\"step\":1},"));
+    }
+
+    #[test]
+    fn unroll_across_equaves() {
+        let scale = Scale::new(vec![Ratio::new(1, 1), Ratio::new(3, 2)]);
+
+        assert_eq!(
+            scale.unroll(-1..2),
+            vec![
+                Degree::from(Ratio::new(1, 2)),
+                Degree::from(Ratio::new(3, 4)),
+                Degree::from(Ratio::new(1, 1)),
+                Degree::from(Ratio::new(3, 2)),
+                Degree::from(Ratio::new(2, 1)),
+                Degree::from(Ratio::new(3, 1)),
+            ]
+        );
+        let stretched = scale.unroll_with(0..3, 1201.);
+        assert_eq!(stretched.len(), 6);
+        assert!((stretched[5].cents() - (Ratio::new(3, 2).cents() + 2402.)).abs() < 1e-9);
+        assert!(
+            Scale::with_equivalence(vec![Ratio::new(1, 1)], Equivalence::None)
+                .unroll(1..3)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn format_scale() {
+        assert_eq!(just_major().format(Style::Colon), "24:27:30:32:36:40:45");
+        assert_eq!(
+            Scale::new(vec![Ratio::new(1, 1), Ratio::new(3, 2)]).format(Style::Cents),
+            "0.000 701.955"
+        );
+        assert_eq!(Scale::new(vec![0., 350.]).format(Style::Colon), "0.0 350.0");
+    }
+
+    #[test]
+    fn step_pattern_of_pythagorean_major() {
+        let scale = Scale::new(vec![
+            Ratio::new(1, 1),
+            Ratio::new(9, 8),
+            Ratio::new(81, 64),
+            Ratio::new(4, 3),
+            Ratio::new(3, 2),
+            Ratio::new(27, 16),
+            Ratio::new(243, 128),
+        ]);
+
+        let pattern = scale.step_pattern().unwrap();
+        assert_eq!(pattern.to_string(), "LLsLLLs");
+        assert_eq!(pattern.degree(2).chromatic(), 2);
+        assert_eq!(just_major().step_pattern(), None);
+    }
+
+    #[test]
+    fn edo_scan_progress_and_cancellation() {
+        let mut calls = 0;
+        let best = just_major()
+            .best_edo_with_progress(5, 60, &mut |_| {
+                calls += 1;
+                Control::Continue
+            })
+            .unwrap();
+
+        assert_eq!(calls, 56);
+        assert_eq!(best, just_major().best_edo(5, 60));
+
+        let cancelled = just_major().best_edo_with_progress(5, 60, &mut |p| match p.done {
+            10 => Control::Cancel,
+            _ => Control::Continue,
+        });
+        assert_eq!(cancelled.unwrap_err().progress.done, 10);
+    }
+
+    #[test]
+    fn sorted_degrees_by_complexity() {
+        let degrees = just_major().sorted_degrees(SortOrder::Complexity);
+
+        assert_eq!(degrees[0], Ratio::new(1, 1));
+        assert_eq!(degrees[1], Ratio::new(3, 2));
+        assert_eq!(degrees[6], Ratio::new(15, 8));
+        assert_eq!(
+            just_major().sorted_degrees(SortOrder::Natural),
+            just_major().degrees
+        );
+    }
+
+    #[test]
+    fn negative_harmony_reflection() {
+        let minor = just_major().reflect(Ratio::new(3, 2));
+
+        assert_eq!(
+            minor.ratios(),
+            Some(vec![
+                Ratio::new(1, 1),
+                Ratio::new(9, 8),
+                Ratio::new(6, 5),
+                Ratio::new(4, 3),
+                Ratio::new(3, 2),
+                Ratio::new(8, 5),
+                Ratio::new(9, 5),
+            ])
+        );
+        assert_eq!(minor.reflect(Ratio::new(3, 2)), just_major());
+    }
+
+    #[test]
+    fn complement_tables() {
+        let table = just_major().complement_table();
+
+        assert_eq!(
+            table[0],
+            (
+                Degree::from(Ratio::new(1, 1)),
+                Degree::from(Ratio::new(1, 1))
+            )
+        );
+        assert_eq!(table[1].1, Ratio::new(16, 9));
+        assert_eq!(table[4].1, Ratio::new(4, 3));
+        let tempered = Scale::new(vec![0., 700.]).complement_table();
+        assert!((tempered[1].1.cents() - 500.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn interpolate_between_scales() {
+        let edo = Scale::new(vec![0., 200., 400., 500., 700., 900., 1100.]);
+        let halfway = just_major()
+            .interpolate(&edo, 0.5, Alignment::Index)
+            .unwrap();
+
+        assert!((halfway.cents()[2] - (386.314 + 400.) / 2.).abs() < 1e-3);
+        assert_eq!(
+            just_major().interpolate(&edo, 0., Alignment::Index),
+            Some(just_major())
+        );
+        assert_eq!(
+            just_major().interpolate(&edo, 1., Alignment::Index),
+            Some(edo)
+        );
+        assert_eq!(
+            just_major().interpolate(&Scale::new(vec![0.]), 0.5, Alignment::Index),
+            None
+        );
+    }
+
+    #[test]
+    fn interpolate_with_alignment() {
+        let ionian = Scale::new(vec![0., 200., 400., 500., 700., 900., 1100.]);
+        let aeolian = ionian.mode(5);
+
+        assert_eq!(
+            just_major().align(&aeolian, Alignment::Nearest),
+            Some(ionian.mode(0))
+        );
+        assert_eq!(
+            just_major().align(&ionian, Alignment::Rotation(5)),
+            Some(aeolian.clone())
+        );
+        let morph = just_major()
+            .interpolate(&aeolian, 0.5, Alignment::Index)
+            .unwrap();
+        assert!((morph.cents()[2] - (386.314 + 300.) / 2.).abs() < 1e-3);
+    }
+
+    #[test]
+    fn find_chords_in_just_major() {
+        let triad = |a, b, c| {
+            vec![a, b, c]
+                .into_iter()
+                .map(|n| Degree::from(Ratio::new(n, a)))
+                .collect::<Vec<_>>()
+        };
+        let scale = just_major();
+
+        let major = scale.find_chords(&triad(4, 5, 6), 0.);
+        assert_eq!(
+            major.iter().map(|m| m.root).collect::<Vec<_>>(),
+            vec![0, 3, 4]
+        );
+        assert_eq!(major[1].degrees, vec![3, 5, 0]);
+
+        let minor = scale.find_chords(&triad(10, 12, 15), 0.);
+        assert_eq!(minor.iter().map(|m| m.root).collect::<Vec<_>>(), vec![2, 5]);
+    }
+
+    #[test]
+    fn find_chords_with_tolerance() {
+        let edo = Scale::new((0..12).map(|k| 100. * k as f64).collect::<Vec<_>>());
+        let pattern: Vec<Degree> = [4, 5, 6, 7]
+            .iter()
+            .map(|&n| Degree::from(Ratio::new(n, 4)))
+            .collect();
+
+        assert!(edo.find_chords(&pattern, 20.).is_empty());
+        let matches = edo.find_chords(&pattern, 32.);
+        assert_eq!(matches.len(), 12);
+        assert!((matches[0].error - 31.174).abs() < 1e-3);
+    }
+
+    #[test]
+    fn modes_from_darkest_to_brightest() {
+        let diatonic = Scale::new(vec![0., 200., 400., 500., 700., 900., 1100.]);
+        let modes = diatonic.modes_by_brightness();
+
+        assert_eq!(
+            modes.iter().map(|m| m.rotation).collect::<Vec<_>>(),
+            vec![6, 2, 5, 1, 4, 0, 3]
+        );
+        assert_eq!(modes[6].label, "mode 4 on 500.0");
+        assert_eq!(modes[6].scale.cents()[3], 600.);
+    }
+
+    #[test]
+    fn just_modes_stay_exact() {
+        let modes = just_major().modes_by_brightness();
+        let lydian = modes.last().unwrap();
+
+        assert_eq!(lydian.rotation, 3);
+        assert_eq!(lydian.scale.degrees[3], Ratio::new(45, 32));
+    }
+
+    #[test]
+    fn canonicalize_restores_order() {
+        let mut scale = just_major();
+        scale.degrees.reverse();
+        scale.degrees.push(Degree::from(Ratio::new(9, 4)));
+
+        assert!(!scale.is_canonical());
+        assert_eq!(scale.canonicalize(), just_major());
+        assert!(just_major().is_canonical());
+    }
+
+    #[test]
+    fn new_scale_normalizes_and_sorts() {
+        let scale = Scale::new(vec![Ratio::new(3, 1), Ratio::new(5, 4), Ratio::new(1, 1)]);
+
+        assert_eq!(
+            scale.ratios(),
+            Some(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)])
+        );
+    }
+
+    #[test]
+    fn new_scale_from_pitches_drops_equave() {
+        let scale = Scale::new(vec![
+            Pitch::new(Ratio::new(5, 2)),
+            Pitch::new(Ratio::new(1, 1)),
+        ]);
+
+        assert_eq!(
+            scale.ratios(),
+            Some(vec![Ratio::new(1, 1), Ratio::new(5, 4)])
+        );
+    }
+
+    #[test]
+    fn new_scale_removes_duplicates() {
+        let scale = Scale::new(vec![Ratio::new(3, 2), Ratio::new(3, 1), Ratio::new(1, 1)]);
+
+        assert_eq!(scale.len(), 2);
+    }
+
+    #[test]
+    fn interval_between_degrees() {
+        let scale = just_major();
+
+        assert_eq!(scale.interval(1, 4), Ratio::new(4, 3));
+        assert_eq!(scale.interval(4, 1), Ratio::new(3, 2));
+    }
+
+    #[test]
+    fn steps_wrap_around_the_octave() {
+        assert_eq!(
+            just_major().steps(),
+            vec![
+                Ratio::new(9, 8),
+                Ratio::new(10, 9),
+                Ratio::new(16, 15),
+                Ratio::new(9, 8),
+                Ratio::new(10, 9),
+                Ratio::new(9, 8),
+                Ratio::new(16, 15),
+            ]
+        );
+    }
+
+    #[test]
+    fn span_histogram() {
+        assert_eq!(
+            just_major().span_histogram(4),
+            vec![
+                (Degree::from(Ratio::new(64, 45)), 1),
+                (Degree::from(Ratio::new(40, 27)), 1),
+                (Degree::from(Ratio::new(3, 2)), 5)
+            ]
+        );
+    }
+
+    #[test]
+    fn statistics() {
+        let stats = just_major().statistics().unwrap();
+
+        assert_eq!(stats.distinct_steps, 3);
+        assert_eq!(stats.smallest_step, Ratio::new(16, 15));
+        assert_eq!(stats.largest_step, Ratio::new(9, 8));
+        assert!((stats.step_ratio - 1.8250).abs() < 1e-4);
+        assert!((stats.mean_step - 1200. / 7.).abs() < 1e-9);
+        assert!(stats.step_variance > 0.);
+        assert_eq!(stats.spans.len(), 6);
+    }
+
+    #[test]
+    fn statistics_of_trivial_scale() {
+        assert_eq!(Scale::new(vec![Ratio::new(1, 1)]).statistics(), None);
+    }
+
+    #[test]
+    fn dyad_matrix_odd_limit() {
+        let scale = Scale::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+
+        let matrix = scale.dyad_matrix(Metric::OddLimit);
+
+        assert_eq!(
+            matrix.scores,
+            vec![vec![1., 5., 3.], vec![5., 1., 5.], vec![3., 5., 1.]]
+        );
+        assert_eq!(matrix.total(), 26.);
+        assert!((matrix.mean() - 26. / 6.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn dyad_matrix_compares_scales() {
+        let triad = Scale::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+        let cluster = Scale::new(vec![Ratio::new(1, 1), Ratio::new(16, 15), Ratio::new(9, 8)]);
+
+        assert!(
+            triad.dyad_matrix(Metric::TenneyHeight).mean()
+                < cluster.dyad_matrix(Metric::TenneyHeight).mean()
+        );
+    }
+
+    #[test]
+    fn quantize_major_scale_to_12_edo() {
+        let mapping = just_major().quantize_to_edo(12).unwrap();
+
+        assert_eq!(mapping.steps, vec![0, 2, 4, 5, 7, 9, 11]);
+        assert!(mapping.is_injective());
+        assert_eq!(mapping.gaps, vec![1, 3, 6, 8, 10]);
+        assert!((mapping.errors[2] - -13.686).abs() < 1e-3);
+    }
+
+    #[test]
+    fn quantize_with_rounding_policy() {
+        let tolerance = Tolerance::default().with_rounding(Rounding::Floor);
+        let mapping = just_major().quantize_to_edo_with(12, tolerance).unwrap();
+
+        assert_eq!(mapping.steps, vec![0, 2, 3, 4, 7, 8, 10]);
+        assert!(mapping.errors.iter().all(|e| *e >= 0.));
+    }
+
+    #[test]
+    fn quantize_reports_collisions() {
+        let scale = Scale::new(vec![
+            Ratio::new(1, 1),
+            Ratio::new(16, 15),
+            Ratio::new(10, 9),
+            Ratio::new(9, 8),
+        ]);
+
+        let mapping = scale.quantize_to_edo(5).unwrap();
+
+        assert_eq!(mapping.steps, vec![0, 0, 1, 1]);
+        assert_eq!(
+            mapping.collisions,
+            vec![
+                EdoCollision {
+                    step: 0,
+                    degrees: vec![0, 1]
+                },
+                EdoCollision {
+                    step: 1,
+                    degrees: vec![2, 3]
+                }
+            ]
+        );
+        assert_eq!(mapping.gaps, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn mixed_scale_keeps_exact_and_cents_degrees() {
+        let scale = Scale::new(vec![
+            Degree::from(Ratio::new(1, 1)),
+            Degree::from(700.),
+            Degree::from(Ratio::new(5, 4)),
+            Degree::from(1900.),
+        ]);
+
+        assert_eq!(scale.len(), 3);
+        assert!(!scale.is_exact());
+        assert_eq!(scale.ratios(), None);
+        assert_eq!(scale.interval(0, 1), Ratio::new(5, 4));
+        assert_eq!(
+            scale.interval(1, 2),
+            Degree::from(700. - Ratio::new(5, 4).cents())
+        );
+
+        let stats = scale.statistics().unwrap();
+        assert_eq!(stats.largest_step, Degree::from(500.));
+        assert_eq!(stats.distinct_steps, 3);
+    }
+
+    #[test]
+    fn tempered_scale_analysis() {
+        let edo: Vec<f64> = (0..12).map(|i| i as f64 * 100.).collect();
+        let scale = Scale::new(edo);
+
+        assert_eq!(scale.steps().len(), 12);
+        assert_eq!(scale.statistics().unwrap().distinct_steps, 1);
+        assert_eq!(scale.span_histogram(7), vec![(Degree::from(700.), 12)]);
+        assert!(scale
+            .quantize_to_edo(12)
+            .unwrap()
+            .errors
+            .iter()
+            .all(|e| e.abs() < 1e-9));
+        assert!(scale.dyad_matrix(Metric::HarmonicEntropy).mean() > 0.);
+    }
+
+    #[test]
+    fn prime_limit_of_scale() {
+        assert_eq!(just_major().prime_limit(), Some(5));
+        assert_eq!(Scale::new(vec![0., 700.]).prime_limit(), None);
+    }
+
+    #[test]
+    fn propriety() {
+        assert_eq!(just_major().propriety(), Propriety::StrictlyProper);
+
+        let edo: Vec<f64> = (0..12).map(|i| i as f64 * 100.).collect();
+        assert_eq!(Scale::new(edo).propriety(), Propriety::StrictlyProper);
+
+        let tempered_major = Scale::new(vec![0., 200., 400., 500., 700., 900., 1100.]);
+        assert_eq!(tempered_major.propriety(), Propriety::Proper);
+
+        let lopsided = Scale::new(vec![
+            Ratio::new(1, 1),
+            Ratio::new(16, 15),
+            Ratio::new(9, 8),
+            Ratio::new(3, 2),
+        ]);
+        assert_eq!(lopsided.propriety(), Propriety::Improper);
+    }
+
+    #[test]
+    fn edo_fit() {
+        let scale = just_major();
+
+        assert!((scale.edo_fit(12).unwrap() - 15.641).abs() < 1e-3);
+        let (edo, error) = scale.best_edo(5, 53).unwrap();
+        assert!(error < scale.edo_fit(12).unwrap());
+        assert!(scale.quantize_to_edo(edo).unwrap().is_injective());
+        assert_eq!(scale.best_edo(5, 6), None);
+        assert_eq!(scale.quantize_to_edo(0), None);
+        assert_eq!(scale.edo_fit(-12), None);
+    }
+
+    #[test]
+    fn tritave_equivalent_scale() {
+        let bohlen_pierce = Scale::with_equivalence(
+            vec![
+                Ratio::new(1, 1),
+                Ratio::new(25, 21),
+                Ratio::new(9, 7),
+                Ratio::new(7, 5),
+                Ratio::new(5, 3),
+                Ratio::new(9, 5),
+                Ratio::new(15, 7),
+                Ratio::new(7, 3),
+                Ratio::new(25, 9),
+                Ratio::new(27, 7),
+            ],
+            Equivalence::Tritave,
+        );
+
+        assert_eq!(bohlen_pierce.len(), 9);
+        assert_eq!(bohlen_pierce.degrees[1], Ratio::new(25, 21));
+        assert_eq!(bohlen_pierce.steps()[8], Ratio::new(27, 25));
+        assert_eq!(bohlen_pierce.quantize_to_edo(13).unwrap().steps[4], 6);
+    }
+
+    #[test]
+    fn scale_without_equivalence() {
+        let harmonics = Scale::with_equivalence(
+            vec![
+                Ratio::new(1, 1),
+                Ratio::new(2, 1),
+                Ratio::new(3, 1),
+                Ratio::new(5, 1),
+            ],
+            Equivalence::None,
+        );
+
+        assert_eq!(harmonics.len(), 4);
+        assert_eq!(
+            harmonics.steps(),
+            vec![Ratio::new(2, 1), Ratio::new(3, 2), Ratio::new(5, 3)]
+        );
+        assert_eq!(harmonics.interval(3, 0), Ratio::new(1, 5));
+        assert_eq!(
+            harmonics.span_histogram(3),
+            vec![(Degree::from(Ratio::new(5, 1)), 1)]
+        );
+        assert_eq!(harmonics.dyad_matrix(Metric::OddLimit).scores[3][1], 5.);
+        assert_eq!(
+            harmonics.quantize_to_edo(12).unwrap().steps,
+            vec![0, 12, 19, 28]
+        );
+        let coarse = harmonics.quantize_to_edo(1).unwrap();
+        assert_eq!(coarse.steps, vec![0, 1, 2, 2]);
+        assert_eq!(
+            coarse.collisions,
+            vec![EdoCollision {
+                step: 2,
+                degrees: vec![2, 3]
+            }]
+        );
+        assert!(!coarse.is_injective());
+        assert_eq!(harmonics.quantize_to_edo(3).unwrap().gaps, vec![1, 2, 4, 6]);
+    }
+
+    #[test]
+    fn quantize_wraps_degrees_near_the_octave() {
+        let scale = Scale::new(vec![Ratio::new(1, 1), Ratio::new(243, 128)]);
+
+        let mapping = scale.quantize_to_edo(5).unwrap();
+
+        assert_eq!(mapping.steps, vec![0, 0]);
+        assert_eq!(mapping.collisions.len(), 1);
+    }
+
+    #[test]
+    fn nan_cents_sort_last() {
+        let scale = Scale::new(vec![Degree::Cents(f64::NAN), Degree::Cents(700.)]);
+
+        assert_eq!(scale.degrees[0], Degree::Cents(700.));
+        assert!(scale.degrees[1].cents().is_nan());
+    }
+
+    #[test]
+    fn statistics_schema() {
+        assert_eq!(
+            schema(&just_major().statistics().unwrap()),
+            [
+                "Scale statistics: smallest_step, largest_step, step_ratio, mean_step, step_variance, distinct_steps, propriety",
+                "Steps: index, step, cents",
+            ]
+        );
+    }
+
+    #[test]
+    fn edo_mapping_schema() {
+        assert_eq!(
+            schema(&just_major().quantize_to_edo(12).unwrap()),
+            [
+                "12-EDO mapping: index, step, error",
+                "Collisions: step, degrees",
+                "Gaps: step",
+            ]
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+
+use crate::lattice::{DimensionBound, Lattice};
+use crate::ratio::Ratio;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LatticeDiff {
+    pub added: Vec<(Vec<i32>, Ratio)>,
+    pub removed: Vec<Vec<i32>>,
+    pub changed: Vec<(Vec<i32>, Ratio)>,
+}
+
+impl LatticeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LatticeSession {
+    pub lattice: Lattice,
+    pub radius: i32,
+    factors: Vec<BTreeMap<i32, Ratio>>,
+    points: BTreeMap<Vec<i32>, Ratio>,
+}
+
+impl LatticeSession {
+    pub fn new(lattice: Lattice, radius: i32) -> Self {
+        let mut session = Self {
+            lattice,
+            radius,
+            factors: vec![],
+            points: BTreeMap::new(),
+        };
+        session.rebuild();
+        session
+    }
+
+    pub fn points(&self) -> &BTreeMap<Vec<i32>, Ratio> {
+        &self.points
+    }
+
+    pub fn get(&self, coordinates: &[i32]) -> Option<Ratio> {
+        self.points.get(coordinates).copied()
+    }
+
+    pub fn set_ratio(&mut self, dimension: usize, ratio: Ratio) -> LatticeDiff {
+        self.lattice.dimensions[dimension].ratio = ratio;
+        self.update_dimension(dimension)
+    }
+
+    pub fn set_bounds(&mut self, dimension: usize, bounds: DimensionBound) -> LatticeDiff {
+        self.lattice.dimensions[dimension].bounds = bounds;
+        self.update_dimension(dimension)
+    }
+
+    pub fn set_radius(&mut self, radius: i32) -> LatticeDiff {
+        let old = std::mem::take(&mut self.points);
+        self.radius = radius;
+        self.rebuild();
+
+        let mut diff = LatticeDiff {
+            removed: old
+                .keys()
+                .filter(|c| !self.points.contains_key(*c))
+                .cloned()
+                .collect(),
+            ..Default::default()
+        };
+        for (coordinates, &ratio) in &self.points {
+            match old.get(coordinates) {
+                None => diff.added.push((coordinates.clone(), ratio)),
+                Some(&r) if r != ratio => diff.changed.push((coordinates.clone(), ratio)),
+                Some(_) => {}
+            }
+        }
+        diff
+    }
+
+    fn factor_table(&self, dimension: usize) -> BTreeMap<i32, Ratio> {
+        let dim = &self.lattice.dimensions[dimension];
+        dim.range(self.radius)
+            .map(|i| {
+                let ratio = dim
+                    .ratio
+                    .pow_with(dim.resolve_index(i), self.lattice.equivalence);
+                (i, ratio)
+            })
+            .collect()
+    }
+
+    fn evaluate(&self, coordinates: &[i32]) -> Ratio {
+        coordinates
+            .iter()
+            .zip(self.factors.iter())
+            .fold(Ratio::new(1, 1), |acc, (i, table)| acc * table[i])
+    }
+
+    fn rebuild(&mut self) {
+        self.factors = (0..self.lattice.dimensions.len())
+            .map(|k| self.factor_table(k))
+            .collect();
+        self.points = self
+            .combinations(None)
+            .into_iter()
+            .map(|c| {
+                let ratio = self.evaluate(&c);
+                (c, ratio)
+            })
+            .collect();
+    }
+
+    fn combinations(&self, fixed: Option<(usize, i32)>) -> Vec<Vec<i32>> {
+        self.factors
+            .iter()
+            .enumerate()
+            .fold(vec![vec![]], |coords, (k, table)| {
+                let indices: Vec<i32> = match fixed {
+                    Some((dimension, index)) if dimension == k => vec![index],
+                    _ => table.keys().copied().collect(),
+                };
+                coords
+                    .iter()
+                    .flat_map(|c| {
+                        indices.iter().map(move |&i| {
+                            let mut c = c.clone();
+                            c.push(i);
+                            c
+                        })
+                    })
+                    .collect()
+            })
+    }
+
+    fn update_dimension(&mut self, dimension: usize) -> LatticeDiff {
+        let table = self.factor_table(dimension);
+        let old = std::mem::replace(&mut self.factors[dimension], table.clone());
+        let mut diff = LatticeDiff {
+            removed: self
+                .points
+                .keys()
+                .filter(|c| !table.contains_key(&c[dimension]))
+                .cloned()
+                .collect(),
+            ..Default::default()
+        };
+        for coordinates in &diff.removed {
+            self.points.remove(coordinates);
+        }
+
+        let stale: Vec<Vec<i32>> = self
+            .points
+            .keys()
+            .filter(|c| old.get(&c[dimension]) != table.get(&c[dimension]))
+            .cloned()
+            .collect();
+        for coordinates in stale {
+            let ratio = self.evaluate(&coordinates);
+            if self.points.insert(coordinates.clone(), ratio) != Some(ratio) {
+                diff.changed.push((coordinates, ratio));
+            }
+        }
+
+        let fresh: Vec<i32> = table
+            .keys()
+            .filter(|i| !old.contains_key(i))
+            .copied()
+            .collect();
+        for index in fresh {
+            for coordinates in self.combinations(Some((dimension, index))) {
+                let ratio = self.evaluate(&coordinates);
+                self.points.insert(coordinates.clone(), ratio);
+                diff.added.push((coordinates, ratio));
+            }
+        }
+        diff.added.sort_by(|a, b| a.0.cmp(&b.0));
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lattice::LatticeDimension;
+
+    fn lattice() -> Lattice {
+        Lattice::new(vec![
+            LatticeDimension {
+                ratio: Ratio::new(3, 2),
+                bounds: DimensionBound::Infinity,
+            },
+            LatticeDimension {
+                ratio: Ratio::new(5, 4),
+                bounds: DimensionBound::Infinity,
+            },
+        ])
+    }
+
+    fn full(session: &LatticeSession) -> BTreeMap<Vec<i32>, Ratio> {
+        session.lattice.points(session.radius).into_iter().collect()
+    }
+
+    #[test]
+    fn session_matches_lattice() {
+        let session = LatticeSession::new(lattice(), 2);
+
+        assert_eq!(session.points().len(), 25);
+        assert_eq!(*session.points(), full(&session));
+        assert_eq!(session.get(&[1, 0]), Some(Ratio::new(3, 2)));
+    }
+
+    #[test]
+    fn ratio_change_only_touches_its_dimension() {
+        let mut session = LatticeSession::new(lattice(), 1);
+        let diff = session.set_ratio(1, Ratio::new(7, 4));
+
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 6);
+        assert!(diff.changed.iter().all(|(c, _)| c[1] != 0));
+        assert!(diff.changed.contains(&(vec![0, 1], Ratio::new(7, 4))));
+        assert_eq!(*session.points(), full(&session));
+    }
+
+    #[test]
+    fn bounds_change_adds_and_removes() {
+        let mut session = LatticeSession::new(lattice(), 2);
+        let diff = session.set_bounds(0, DimensionBound::ZeroBounded(2));
+
+        assert_eq!(diff.removed.len(), 15);
+        assert!(diff.removed.iter().all(|c| c[0] < 0 || c[0] > 1));
+        assert!(diff.added.is_empty());
+        assert_eq!(*session.points(), full(&session));
+
+        let diff = session.set_bounds(0, DimensionBound::Infinity);
+        assert_eq!(diff.added.len(), 15);
+        assert_eq!(*session.points(), full(&session));
+    }
+
+    #[test]
+    fn radius_change_and_no_op() {
+        let mut session = LatticeSession::new(lattice(), 1);
+        let diff = session.set_radius(2);
+
+        assert_eq!(diff.added.len(), 16);
+        assert!(diff.removed.is_empty() && diff.changed.is_empty());
+        assert!(session.set_ratio(0, Ratio::new(3, 2)).is_empty());
+    }
+}
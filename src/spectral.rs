@@ -0,0 +1,199 @@
+use crate::chord::Chord;
+use crate::scale::Scale;
+use crate::spectrum::Spectrum;
+
+const MERGE_EPSILON: f64 = 1e-9;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Envelope {
+    Flat,
+    Tilt { db_per_octave: f64, reference: f64 },
+    Formant { center: f64, octaves: f64 },
+}
+
+impl Envelope {
+    pub fn gain(&self, frequency: f64) -> f64 {
+        match self {
+            Envelope::Flat => 1.,
+            Envelope::Tilt {
+                db_per_octave,
+                reference,
+            } => 10_f64.powf(db_per_octave * (frequency / reference).log2() / 20.),
+            Envelope::Formant { center, octaves } => {
+                let distance = (frequency / center).log2() / octaves;
+                (-distance * distance / 2.).exp()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpectralPartial {
+    pub frequency: f64,
+    pub amplitude: f64,
+}
+
+pub fn spectral_partials(
+    fundamentals: &[f64],
+    spectrum: &Spectrum,
+    envelope: Envelope,
+) -> Vec<SpectralPartial> {
+    let mut partials: Vec<SpectralPartial> = fundamentals
+        .iter()
+        .flat_map(|&f| {
+            spectrum.partials.iter().map(move |p| {
+                let frequency = p.ratio * f;
+                SpectralPartial {
+                    frequency,
+                    amplitude: p.amplitude * envelope.gain(frequency),
+                }
+            })
+        })
+        .collect();
+    partials.sort_by(|a, b| a.frequency.total_cmp(&b.frequency));
+
+    let mut merged: Vec<SpectralPartial> = vec![];
+    for partial in partials {
+        match merged.last_mut() {
+            Some(last)
+                if (partial.frequency - last.frequency).abs() < MERGE_EPSILON * last.frequency =>
+            {
+                last.amplitude += partial.amplitude
+            }
+            _ => merged.push(partial),
+        }
+    }
+
+    let peak = merged.iter().map(|p| p.amplitude).fold(0., f64::max);
+    if peak > 0. {
+        for partial in &mut merged {
+            partial.amplitude /= peak;
+        }
+    }
+    merged
+}
+
+pub fn chord_partials(
+    chord: &Chord,
+    reference: impl Into<f64>,
+    spectrum: &Spectrum,
+    envelope: Envelope,
+) -> Vec<SpectralPartial> {
+    let reference: f64 = reference.into();
+    let fundamentals: Vec<f64> = chord
+        .pitches
+        .iter()
+        .map(|p| p.frequency(reference))
+        .collect();
+    spectral_partials(&fundamentals, spectrum, envelope)
+}
+
+pub fn scale_partials(
+    scale: &Scale,
+    reference: impl Into<f64>,
+    spectrum: &Spectrum,
+    envelope: Envelope,
+) -> Vec<SpectralPartial> {
+    let reference: f64 = reference.into();
+    let fundamentals: Vec<f64> = scale
+        .degrees
+        .iter()
+        .map(|d| reference * d.multiplier())
+        .collect();
+    spectral_partials(&fundamentals, spectrum, envelope)
+}
+
+pub fn to_csv(partials: &[SpectralPartial]) -> String {
+    let mut out = String::from("frequency,amplitude\n");
+    for p in partials {
+        out.push_str(&format!("{:.6},{:.6}\n", p.frequency, p.amplitude));
+    }
+    out
+}
+
+pub fn to_spear(partials: &[SpectralPartial], duration: f64) -> String {
+    let mut out = format!(
+        "par-text-partials-format\npoint-type time frequency amplitude\npartials-count {}\npartials\n",
+        partials.len()
+    );
+    for (i, p) in partials.iter().enumerate() {
+        out.push_str(&format!("{i} 2 0.000000 {duration:.6}\n"));
+        out.push_str(&format!(
+            "0.000000 {0:.6} {1:.6} {duration:.6} {0:.6} {1:.6}\n",
+            p.frequency, p.amplitude
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measurement::parse_measurements;
+    use crate::ratio::Ratio;
+
+    fn triad() -> Chord {
+        Chord::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)])
+    }
+
+    #[test]
+    fn coinciding_partials_merge() {
+        let partials = chord_partials(&triad(), 200., &Spectrum::harmonic(6), Envelope::Flat);
+
+        assert_eq!(partials[0].frequency, 200.);
+        assert!(partials.windows(2).all(|w| w[0].frequency < w[1].frequency));
+        let merged = partials.iter().find(|p| p.frequency == 1000.).unwrap();
+        assert!((merged.amplitude - (1. / 5. + 1. / 4.)).abs() < 1e-12);
+        assert_eq!(partials.len(), 14);
+    }
+
+    #[test]
+    fn nan_fundamentals_sort_last() {
+        let partials = spectral_partials(&[f64::NAN, 200.], &Spectrum::harmonic(2), Envelope::Flat);
+
+        assert_eq!(partials[0].frequency, 200.);
+        assert!(partials.last().unwrap().frequency.is_nan());
+    }
+
+    #[test]
+    fn envelope_shapes_amplitudes() {
+        let tilt = Envelope::Tilt {
+            db_per_octave: -6.,
+            reference: 100.,
+        };
+        let formant = Envelope::Formant {
+            center: 800.,
+            octaves: 0.5,
+        };
+
+        assert!((tilt.gain(200.) - 0.501187).abs() < 1e-6);
+        assert_eq!(formant.gain(800.), 1.);
+        let partials = scale_partials(
+            &Scale::new(vec![Ratio::new(1, 1)]),
+            100.,
+            &Spectrum::harmonic(10),
+            formant,
+        );
+        let loudest = partials
+            .iter()
+            .max_by(|a, b| a.amplitude.total_cmp(&b.amplitude))
+            .unwrap();
+        assert_eq!(loudest.frequency, 700.);
+    }
+
+    #[test]
+    fn spear_and_csv_output() {
+        let partials = chord_partials(&triad(), 200., &Spectrum::sine(), Envelope::Flat);
+
+        let spear = to_spear(&partials, 2.);
+        assert!(spear.starts_with(
+            "par-text-partials-format\npoint-type time frequency amplitude\npartials-count 3\npartials\n"
+        ));
+        assert!(spear.contains(
+            "1 2 0.000000 2.000000\n0.000000 250.000000 1.000000 2.000000 250.000000 1.000000\n"
+        ));
+        let measurements = parse_measurements(&to_csv(&partials)).unwrap();
+        assert_eq!(measurements.len(), 3);
+        assert_eq!(measurements[2].frequency, 300.);
+    }
+}
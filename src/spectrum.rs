@@ -0,0 +1,194 @@
+use crate::pitch::Pitch;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Partial {
+    pub ratio: f64,
+    pub amplitude: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spectrum {
+    pub partials: Vec<Partial>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Beat {
+    pub lower_partial: usize,
+    pub upper_partial: usize,
+    pub lower_frequency: f64,
+    pub upper_frequency: f64,
+    pub rate: f64,
+}
+
+impl Spectrum {
+    pub fn new(partials: Vec<Partial>) -> Self {
+        Self { partials }
+    }
+
+    pub fn harmonic(count: usize) -> Self {
+        Self::new(
+            (1..=count)
+                .map(|n| Partial {
+                    ratio: n as f64,
+                    amplitude: 1. / n as f64,
+                })
+                .collect(),
+        )
+    }
+
+    pub fn sine() -> Self {
+        Self::harmonic(1)
+    }
+
+    pub fn frequencies(&self, fundamental: f64) -> Vec<f64> {
+        self.partials
+            .iter()
+            .map(|p| p.ratio * fundamental)
+            .collect()
+    }
+
+    pub fn roughness(&self, fundamentals: &[f64]) -> f64 {
+        let partials: Vec<(f64, f64)> = fundamentals
+            .iter()
+            .flat_map(|&f| {
+                self.partials
+                    .iter()
+                    .map(move |p| (p.ratio * f, p.amplitude))
+            })
+            .collect();
+
+        let mut total = 0.;
+        for (i, &(f1, a1)) in partials.iter().enumerate() {
+            for &(f2, a2) in partials.iter().skip(i + 1) {
+                total += plomp_levelt(f1, f2, a1, a2);
+            }
+        }
+        total
+    }
+
+    pub fn dissonance_curve(&self, fundamental: f64, max_cents: f64, step: f64) -> Vec<(f64, f64)> {
+        let steps = (max_cents / step).floor() as usize;
+        (0..=steps)
+            .map(|i| {
+                let cents = i as f64 * step;
+                let upper = fundamental * 2_f64.powf(cents / 1200.);
+                (cents, self.roughness(&[fundamental, upper]))
+            })
+            .collect()
+    }
+
+    pub fn beats(
+        &self,
+        lower: &Pitch,
+        upper: &Pitch,
+        reference: impl Into<f64>,
+        max_rate: f64,
+    ) -> Vec<Beat> {
+        let reference: f64 = reference.into();
+        let lower = self.frequencies(lower.frequency(reference));
+        let upper = self.frequencies(upper.frequency(reference));
+
+        let mut beats = vec![];
+        for (i, &fl) in lower.iter().enumerate() {
+            for (j, &fu) in upper.iter().enumerate() {
+                let rate = (fl - fu).abs();
+                if rate <= max_rate {
+                    beats.push(Beat {
+                        lower_partial: i + 1,
+                        upper_partial: j + 1,
+                        lower_frequency: fl,
+                        upper_frequency: fu,
+                        rate,
+                    });
+                }
+            }
+        }
+        beats
+    }
+}
+
+fn plomp_levelt(f1: f64, f2: f64, a1: f64, a2: f64) -> f64 {
+    let s = 0.24 / (0.0207 * f1.min(f2) + 18.96);
+    let x = (f2 - f1).abs();
+    a1 * a2 * ((-3.5 * s * x).exp() - (-5.75 * s * x).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ratio::Ratio;
+
+    #[test]
+    fn harmonic_spectrum() {
+        let spectrum = Spectrum::harmonic(3);
+
+        assert_eq!(spectrum.frequencies(100.), vec![100., 200., 300.]);
+        assert_eq!(spectrum.partials[2].amplitude, 1. / 3.);
+    }
+
+    #[test]
+    fn just_fifth_does_not_beat() {
+        let beats = Spectrum::harmonic(6).beats(
+            &Pitch::new(Ratio::new(1, 1)),
+            &Pitch::new(Ratio::new(3, 2)),
+            200.,
+            1.,
+        );
+
+        assert_eq!(beats.len(), 2);
+        assert_eq!((beats[0].lower_partial, beats[0].upper_partial), (3, 2));
+        assert_eq!(beats[0].rate, 0.);
+        assert_eq!((beats[1].lower_partial, beats[1].upper_partial), (6, 4));
+    }
+
+    #[test]
+    fn wolf_fifth_beats() {
+        let beats = Spectrum::harmonic(3).beats(
+            &Pitch::new(Ratio::new(1, 1)),
+            &Pitch::new(Ratio::new(40, 27)),
+            270.,
+            20.,
+        );
+
+        assert_eq!(beats.len(), 1);
+        assert_eq!(beats[0].lower_frequency, 810.);
+        assert_eq!(beats[0].upper_frequency, 800.);
+        assert_eq!(beats[0].rate, 10.);
+    }
+
+    #[test]
+    fn sine_tones_beat_only_near_unison() {
+        let spectrum = Spectrum::sine();
+        let unison = Pitch::new(Ratio::new(1, 1));
+
+        assert!(spectrum
+            .beats(&unison, &Pitch::new(Ratio::new(3, 2)), 440., 50.)
+            .is_empty());
+        assert_eq!(
+            spectrum.beats(&unison, &Pitch::new(Ratio::new(81, 80)), 440., 10.)[0].rate,
+            5.5
+        );
+    }
+
+    #[test]
+    fn unison_is_not_rough() {
+        assert_eq!(Spectrum::sine().roughness(&[440., 440.]), 0.);
+    }
+
+    #[test]
+    fn small_intervals_are_rougher_than_wide_ones() {
+        let spectrum = Spectrum::sine();
+
+        assert!(spectrum.roughness(&[440., 470.]) > spectrum.roughness(&[440., 660.]));
+    }
+
+    #[test]
+    fn dissonance_curve_has_minimum_at_fifth() {
+        let curve = Spectrum::harmonic(6).dissonance_curve(261.63, 1200., 1.);
+        let at = |cents: usize| curve[cents].1;
+
+        assert_eq!(curve.len(), 1201);
+        assert!(at(702) < at(690));
+        assert!(at(702) < at(714));
+    }
+}
@@ -0,0 +1,116 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PitchStandard {
+    pub name: &'static str,
+    pub note: u8,
+    pub frequency: f64,
+}
+
+impl PitchStandard {
+    pub const A440: Self = Self::new("A440", 69, 440.);
+    pub const A432: Self = Self::new("A432", 69, 432.);
+    pub const PARTCH_G392: Self = Self::new("G392", 67, 392.);
+    pub const VERDI_C256: Self = Self::new("C256", 60, 256.);
+    pub const BAROQUE_A415: Self = Self::new("A415", 69, 415.);
+
+    pub const ALL: [Self; 5] = [
+        Self::A440,
+        Self::A432,
+        Self::PARTCH_G392,
+        Self::VERDI_C256,
+        Self::BAROQUE_A415,
+    ];
+
+    pub const fn new(name: &'static str, note: u8, frequency: f64) -> Self {
+        Self {
+            name,
+            note,
+            frequency,
+        }
+    }
+
+    pub fn named(name: &str) -> Option<Self> {
+        let key: String = name
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        let alias = match key.as_str() {
+            "concert" | "iso" => "a440",
+            "partch" => "g392",
+            "verdi" | "scientific" => "c256",
+            "baroque" => "a415",
+            k => k,
+        };
+        Self::ALL
+            .into_iter()
+            .find(|s| s.name.to_lowercase() == alias)
+    }
+
+    pub fn midi_frequency(&self, note: u8) -> f64 {
+        self.frequency * 2_f64.powf((note as f64 - self.note as f64) / 12.)
+    }
+}
+
+impl Default for PitchStandard {
+    fn default() -> Self {
+        Self::A440
+    }
+}
+
+impl From<PitchStandard> for f64 {
+    fn from(value: PitchStandard) -> Self {
+        value.frequency
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-3
+    }
+
+    #[test]
+    fn lookup_by_name() {
+        assert_eq!(PitchStandard::named("A440"), Some(PitchStandard::A440));
+        assert_eq!(PitchStandard::named("a-432"), Some(PitchStandard::A432));
+        assert_eq!(
+            PitchStandard::named("G-392"),
+            Some(PitchStandard::PARTCH_G392)
+        );
+        assert_eq!(
+            PitchStandard::named("Partch"),
+            Some(PitchStandard::PARTCH_G392)
+        );
+        assert_eq!(
+            PitchStandard::named("verdi"),
+            Some(PitchStandard::VERDI_C256)
+        );
+        assert_eq!(
+            PitchStandard::named("Baroque"),
+            Some(PitchStandard::BAROQUE_A415)
+        );
+        assert_eq!(PitchStandard::named("A999"), None);
+    }
+
+    #[test]
+    fn midi_frequencies() {
+        assert!(approx(PitchStandard::A440.midi_frequency(60), 261.626));
+        assert!(approx(PitchStandard::PARTCH_G392.midi_frequency(67), 392.));
+        assert!(approx(
+            PitchStandard::PARTCH_G392.midi_frequency(69),
+            440.005
+        ));
+        assert!(approx(
+            PitchStandard::VERDI_C256.midi_frequency(69),
+            430.539
+        ));
+    }
+
+    #[test]
+    fn default_is_concert_pitch() {
+        assert_eq!(PitchStandard::default(), PitchStandard::A440);
+        assert_eq!(f64::from(PitchStandard::default()), 440.);
+    }
+}
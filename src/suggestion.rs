@@ -0,0 +1,139 @@
+use std::collections::BTreeSet;
+
+use crate::chord::Chord;
+use crate::consonance::Metric;
+use crate::lattice::{Lattice, Region};
+use crate::ratio::Ratio;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChordSuggestion {
+    pub coordinates: Vec<Vec<i32>>,
+    pub chord: Chord,
+    pub score: f64,
+}
+
+pub fn suggest_chords(
+    lattice: &Lattice,
+    around: &[i32],
+    size: usize,
+    metric: Metric,
+) -> Vec<ChordSuggestion> {
+    if size == 0 || around.len() != lattice.dimensions.len() {
+        return vec![];
+    }
+    let region = Region::Ranges(around.iter().map(|&i| (i - 1)..=(i + 1)).collect());
+    let neighbours: Vec<Vec<i32>> = lattice
+        .region_coordinates(&region)
+        .into_iter()
+        .filter(|c| c != around)
+        .collect();
+
+    let mut seen: BTreeSet<Vec<Ratio>> = BTreeSet::new();
+    let mut suggestions = vec![];
+    let mut chosen = vec![around.to_vec()];
+    for_each_cluster(&neighbours, 0, size - 1, &mut chosen, &mut |points| {
+        let ratios: Vec<Ratio> = points
+            .iter()
+            .map(|c| lattice.at(c.clone()).normalize_with(lattice.equivalence))
+            .collect();
+        let chord = Chord::new(ratios);
+        if chord.len() == size && seen.insert(chord.ratios()) {
+            suggestions.push(ChordSuggestion {
+                coordinates: points.to_vec(),
+                score: aggregate_score(lattice, &chord, metric),
+                chord,
+            });
+        }
+    });
+
+    suggestions.sort_by(|a, b| {
+        a.score
+            .total_cmp(&b.score)
+            .then(a.coordinates.cmp(&b.coordinates))
+    });
+    suggestions
+}
+
+fn for_each_cluster(
+    candidates: &[Vec<i32>],
+    start: usize,
+    remaining: usize,
+    chosen: &mut Vec<Vec<i32>>,
+    visit: &mut dyn FnMut(&[Vec<i32>]),
+) {
+    if remaining == 0 {
+        visit(chosen);
+        return;
+    }
+    for i in start..candidates.len() {
+        if chosen.iter().all(|c| is_adjacent(c, &candidates[i])) {
+            chosen.push(candidates[i].clone());
+            for_each_cluster(candidates, i + 1, remaining - 1, chosen, visit);
+            chosen.pop();
+        }
+    }
+}
+
+fn is_adjacent(a: &[i32], b: &[i32]) -> bool {
+    a.iter().zip(b).all(|(x, y)| (x - y).abs() <= 1)
+}
+
+fn aggregate_score(lattice: &Lattice, chord: &Chord, metric: Metric) -> f64 {
+    let ratios = chord.ratios();
+    let mut total = 0.;
+    let mut pairs = 0;
+    for (i, low) in ratios.iter().enumerate() {
+        for high in &ratios[i + 1..] {
+            total += metric.score(&lattice.equivalence.reduce(*high / *low));
+            pairs += 1;
+        }
+    }
+    match pairs {
+        0 => 0.,
+        n => total / n as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lattice::{DimensionBound, LatticeDimension};
+
+    fn five_limit() -> Lattice {
+        Lattice::new(
+            [(3, 2), (5, 4)]
+                .iter()
+                .map(|&(n, d)| LatticeDimension {
+                    ratio: Ratio::new(n, d),
+                    bounds: DimensionBound::Infinity,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn triads_around_the_origin() {
+        let suggestions = suggest_chords(&five_limit(), &[0, 0], 3, Metric::OddLimit);
+
+        assert!(suggestions.iter().all(|s| s.chord.len() == 3));
+        assert!(suggestions.iter().all(|s| s.coordinates[0] == vec![0, 0]));
+        let best: Vec<Vec<Ratio>> = suggestions[..6].iter().map(|s| s.chord.ratios()).collect();
+        assert!(best.contains(&vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]));
+        assert!(best.contains(&vec![Ratio::new(1, 1), Ratio::new(6, 5), Ratio::new(3, 2)]));
+        assert!((suggestions[5].score - 13. / 3.).abs() < 1e-12);
+        assert!(suggestions[6].score > suggestions[5].score);
+        assert!(suggestions.windows(2).all(|w| w[0].score <= w[1].score));
+    }
+
+    #[test]
+    fn suggestions_stay_near_the_location() {
+        let suggestions = suggest_chords(&five_limit(), &[2, -1], 4, Metric::TenneyHeight);
+
+        assert!(!suggestions.is_empty());
+        assert!(suggestions
+            .iter()
+            .flat_map(|s| &s.coordinates)
+            .all(|c| is_adjacent(c, &[2, -1])));
+        assert!(suggest_chords(&five_limit(), &[0], 3, Metric::OddLimit).is_empty());
+    }
+}
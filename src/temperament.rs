@@ -0,0 +1,633 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::primes::primes_up_to;
+use crate::ratio::{Ratio, Style};
+use crate::ratio_list::parse_degree;
+use crate::report::{Report, Table};
+use crate::scale::Scale;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TemperamentError {
+    NotInSubgroup(Ratio),
+    InvalidSubgroup(String),
+}
+
+impl fmt::Display for TemperamentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TemperamentError::NotInSubgroup(r) => write!(f, "{r} is not in the subgroup"),
+            TemperamentError::InvalidSubgroup(s) => write!(f, "invalid subgroup: {s}"),
+        }
+    }
+}
+
+impl Error for TemperamentError {}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Subgroup {
+    pub basis: Vec<Ratio>,
+}
+
+impl Subgroup {
+    pub fn new(basis: Vec<Ratio>) -> Self {
+        Self { basis }
+    }
+
+    pub fn prime_limit(limit: i32) -> Self {
+        Self::new(
+            primes_up_to(limit)
+                .into_iter()
+                .map(|p| Ratio::new(p, 1))
+                .collect(),
+        )
+    }
+
+    pub fn parse(input: &str) -> Result<Self, TemperamentError> {
+        input
+            .trim()
+            .split('.')
+            .map(|element| {
+                parse_degree(element)
+                    .and_then(|d| d.ratio())
+                    .filter(|r| *r != Ratio::new(1, 1))
+            })
+            .collect::<Option<Vec<Ratio>>>()
+            .map(Self::new)
+            .ok_or_else(|| TemperamentError::InvalidSubgroup(input.to_string()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.basis.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.basis.is_empty()
+    }
+
+    pub fn monzo(&self, ratio: Ratio) -> Option<Vec<i32>> {
+        let target = ratio.monzo()?;
+        let basis = self
+            .basis
+            .iter()
+            .map(|b| b.monzo())
+            .collect::<Option<Vec<Vec<i32>>>>()?;
+        let width = basis
+            .iter()
+            .map(|m| m.len())
+            .chain([target.len()])
+            .max()
+            .unwrap_or(0);
+        let column = |m: Vec<i32>| {
+            let mut m: Vec<f64> = m.into_iter().map(|e| e as f64).collect();
+            m.resize(width, 0.);
+            m
+        };
+        let columns: Vec<Vec<f64>> = basis.into_iter().map(column).collect();
+        let solution = solve(&columns, &column(target))?;
+        let monzo: Vec<i32> = solution.iter().map(|x| x.round() as i32).collect();
+        (self.ratio(&monzo) == ratio).then_some(monzo)
+    }
+
+    pub fn contains(&self, ratio: Ratio) -> bool {
+        self.monzo(ratio).is_some()
+    }
+
+    pub fn ratio(&self, monzo: &[i32]) -> Ratio {
+        self.basis
+            .iter()
+            .zip(monzo.iter())
+            .fold(Ratio::new(1, 1), |acc, (&b, &e)| match e {
+                e if e < 0 => acc / b.pow(-e),
+                e => acc * b.pow(e),
+            })
+    }
+
+    pub fn patent_val(&self, edo: i32) -> Val {
+        let equave = self.basis.first().map(|b| b.cents()).unwrap_or(1200.);
+        Val::new(
+            self.basis
+                .iter()
+                .map(|b| (edo as f64 * b.cents() / equave).round() as i32)
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for Subgroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let elements: Vec<String> = self
+            .basis
+            .iter()
+            .map(|b| match b.denom {
+                1 => b.numer.to_string(),
+                _ => b.to_string(),
+            })
+            .collect();
+        write!(f, "{}", elements.join("."))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Val {
+    pub steps: Vec<i32>,
+}
+
+impl Val {
+    pub fn new(steps: Vec<i32>) -> Self {
+        Self { steps }
+    }
+
+    pub fn map(&self, monzo: &[i32]) -> i32 {
+        self.steps
+            .iter()
+            .zip(monzo.iter())
+            .map(|(v, e)| v * e)
+            .sum()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Temperament {
+    pub subgroup: Subgroup,
+    pub commas: Vec<Ratio>,
+}
+
+impl Temperament {
+    pub fn new(subgroup: Subgroup, commas: Vec<Ratio>) -> Result<Self, TemperamentError> {
+        if let Some(&comma) = commas.iter().find(|&&c| !subgroup.contains(c)) {
+            return Err(TemperamentError::NotInSubgroup(comma));
+        }
+        Ok(Self { subgroup, commas })
+    }
+
+    pub fn comma_monzos(&self) -> Vec<Vec<i32>> {
+        self.commas
+            .iter()
+            .filter_map(|&c| self.subgroup.monzo(c))
+            .collect()
+    }
+
+    pub fn tempers_out(&self, val: &Val) -> bool {
+        self.comma_monzos().iter().all(|m| val.map(m) == 0)
+    }
+
+    pub fn patent_edos(&self, max_edo: i32) -> Vec<i32> {
+        (1..=max_edo)
+            .filter(|&edo| self.tempers_out(&self.subgroup.patent_val(edo)))
+            .collect()
+    }
+
+    pub fn rank(&self) -> usize {
+        self.normal_form().len()
+    }
+
+    pub fn normal_form(&self) -> Vec<Vec<i32>> {
+        let n = self.subgroup.len();
+        let commas: Vec<Vec<i64>> = self
+            .comma_monzos()
+            .into_iter()
+            .map(|m| {
+                let mut m: Vec<i64> = m.into_iter().map(i64::from).collect();
+                m.resize(n, 0);
+                m
+            })
+            .collect();
+        hermite_normal_form(kernel(&commas, n))
+            .into_iter()
+            .map(|row| row.into_iter().map(|e| e as i32).collect())
+            .collect()
+    }
+
+    pub fn generators(&self) -> Vec<f64> {
+        generator_tuning(&self.normal_form(), &self.subgroup)
+    }
+
+    pub fn mingen(&self) -> (Vec<Vec<i32>>, Vec<f64>) {
+        let mut mapping = self.normal_form();
+        let mut generators = generator_tuning(&mapping, &self.subgroup);
+        let Some(&period) = generators.first() else {
+            return (mapping, generators);
+        };
+        for r in 1..mapping.len() {
+            let k = (generators[r] / period).floor();
+            generators[r] -= k * period;
+            let row = mapping[r].clone();
+            for (p, g) in mapping[0].iter_mut().zip(row.iter()) {
+                *p += k as i32 * g;
+            }
+            if generators[r] > period / 2. {
+                generators[r] = period - generators[r];
+                let row = mapping[r].clone();
+                for (p, g) in mapping[0].iter_mut().zip(row.iter()) {
+                    *p += g;
+                }
+                mapping[r].iter_mut().for_each(|g| *g = -*g);
+            }
+        }
+        (mapping, generators)
+    }
+
+    pub fn tempered_cents(&self, ratio: Ratio) -> Option<f64> {
+        let mapping = self.normal_form();
+        let generators = generator_tuning(&mapping, &self.subgroup);
+        let size = |monzo: &[i32]| -> f64 {
+            mapping
+                .iter()
+                .zip(generators.iter())
+                .map(|(row, g)| Val::new(row.clone()).map(monzo) as f64 * g)
+                .sum()
+        };
+        let equave = self.subgroup.basis.first()?;
+        let mut unit = vec![0; self.subgroup.len()];
+        unit[0] = 1;
+        let stretch = equave.cents() / size(&unit);
+        Some(size(&self.subgroup.monzo(ratio)?) * stretch)
+    }
+
+    pub fn temper(&self, scale: &Scale) -> Option<Scale> {
+        let cents = scale
+            .ratios()?
+            .into_iter()
+            .map(|r| self.tempered_cents(r))
+            .collect::<Option<Vec<f64>>>()?;
+        Some(Scale::with_equivalence(cents, scale.equivalence))
+    }
+}
+
+pub fn tempering_schedule(scale: &Scale, comma: Ratio, steps: usize) -> Option<Vec<Scale>> {
+    let ratios = scale.ratios()?;
+    let limit = ratios
+        .iter()
+        .map(|r| r.prime_limit())
+        .chain([comma.prime_limit(), 2])
+        .max()?;
+    let temperament = Temperament::new(Subgroup::prime_limit(limit), vec![comma]).ok()?;
+    let tempered = ratios
+        .iter()
+        .map(|&r| temperament.tempered_cents(r))
+        .collect::<Option<Vec<f64>>>()?;
+    let steps = steps.max(1);
+    Some(
+        (0..=steps)
+            .map(|k| {
+                let t = k as f64 / steps as f64;
+                let cents: Vec<f64> = ratios
+                    .iter()
+                    .zip(tempered.iter())
+                    .map(|(r, c)| (1. - t) * r.cents() + t * c)
+                    .collect();
+                match k {
+                    0 => scale.clone(),
+                    _ => Scale::with_equivalence(cents, scale.equivalence),
+                }
+            })
+            .collect(),
+    )
+}
+
+impl Report for Temperament {
+    fn tables(&self) -> Vec<Table> {
+        let mut summary = Table::new("Temperament", &["subgroup", "rank"]);
+        summary.push(vec![self.subgroup.to_string().into(), self.rank().into()]);
+
+        let mut commas = Table::new("Commas", &["comma", "monzo", "cents"]);
+        for comma in &self.commas {
+            commas.push(vec![
+                comma.to_string().into(),
+                comma.format(Style::Monzo).into(),
+                comma.cents().into(),
+            ]);
+        }
+
+        let (mapping, sizes) = self.mingen();
+        let mut generators = Table::new("Generators", &["index", "mapping", "cents"]);
+        for (i, (row, size)) in mapping.iter().zip(&sizes).enumerate() {
+            let row: Vec<String> = row.iter().map(|m| m.to_string()).collect();
+            generators.push(vec![i.into(), row.join(" ").into(), (*size).into()]);
+        }
+
+        let mut errors = Table::new("Tuning errors", &["basis", "just", "tempered", "error"]);
+        for &basis in &self.subgroup.basis {
+            if let Some(tempered) = self.tempered_cents(basis) {
+                errors.push(vec![
+                    basis.to_string().into(),
+                    basis.cents().into(),
+                    tempered.into(),
+                    (tempered - basis.cents()).into(),
+                ]);
+            }
+        }
+        vec![summary, commas, generators, errors]
+    }
+}
+
+impl PartialEq for Temperament {
+    fn eq(&self, other: &Self) -> bool {
+        self.subgroup == other.subgroup && self.normal_form() == other.normal_form()
+    }
+}
+
+fn kernel(rows: &[Vec<i64>], n: usize) -> Vec<Vec<i64>> {
+    let mut matrix: Vec<Vec<i64>> = rows.to_vec();
+    let mut unimodular: Vec<Vec<i64>> = (0..n)
+        .map(|i| (0..n).map(|j| i64::from(i == j)).collect())
+        .collect();
+    let mut column = 0;
+    for row in 0..matrix.len() {
+        if column == n {
+            break;
+        }
+        loop {
+            let nonzero: Vec<usize> = (column..n).filter(|&c| matrix[row][c] != 0).collect();
+            let Some(&pivot) = nonzero.iter().min_by_key(|&&c| matrix[row][c].abs()) else {
+                break;
+            };
+            if nonzero.len() == 1 {
+                swap_columns(&mut matrix, &mut unimodular, column, pivot);
+                column += 1;
+                break;
+            }
+            for &c in nonzero.iter().filter(|&&c| c != pivot) {
+                let q = matrix[row][c] / matrix[row][pivot];
+                for m in matrix.iter_mut().chain(unimodular.iter_mut()) {
+                    m[c] -= q * m[pivot];
+                }
+            }
+        }
+    }
+    (column..n)
+        .map(|c| unimodular.iter().map(|u| u[c]).collect())
+        .collect()
+}
+
+fn swap_columns(matrix: &mut [Vec<i64>], unimodular: &mut [Vec<i64>], a: usize, b: usize) {
+    for m in matrix.iter_mut().chain(unimodular.iter_mut()) {
+        m.swap(a, b);
+    }
+}
+
+fn hermite_normal_form(mut rows: Vec<Vec<i64>>) -> Vec<Vec<i64>> {
+    let width = rows.first().map(|r| r.len()).unwrap_or(0);
+    let mut r = 0;
+    for c in 0..width {
+        loop {
+            let nonzero: Vec<usize> = (r..rows.len()).filter(|&i| rows[i][c] != 0).collect();
+            let Some(&pivot) = nonzero.iter().min_by_key(|&&i| rows[i][c].abs()) else {
+                break;
+            };
+            if nonzero.len() == 1 {
+                rows.swap(r, pivot);
+                if rows[r][c] < 0 {
+                    rows[r].iter_mut().for_each(|e| *e = -*e);
+                }
+                let pivot_row = rows[r].clone();
+                for row in rows[..r].iter_mut() {
+                    let q = row[c].div_euclid(pivot_row[c]);
+                    for (e, p) in row.iter_mut().zip(pivot_row.iter()) {
+                        *e -= q * p;
+                    }
+                }
+                r += 1;
+                break;
+            }
+            let pivot_row = rows[pivot].clone();
+            for &i in nonzero.iter().filter(|&&i| i != pivot) {
+                let q = rows[i][c] / pivot_row[c];
+                for (e, p) in rows[i].iter_mut().zip(pivot_row.iter()) {
+                    *e -= q * p;
+                }
+            }
+        }
+    }
+    rows.truncate(r);
+    rows
+}
+
+fn generator_tuning(mapping: &[Vec<i32>], subgroup: &Subgroup) -> Vec<f64> {
+    let rank = mapping.len();
+    let weighted: Vec<Vec<f64>> = subgroup
+        .basis
+        .iter()
+        .enumerate()
+        .map(|(i, b)| {
+            mapping
+                .iter()
+                .map(|row| row[i] as f64 / b.cents())
+                .collect()
+        })
+        .collect();
+    let columns: Vec<Vec<f64>> = (0..rank)
+        .map(|j| {
+            (0..rank)
+                .map(|k| weighted.iter().map(|a| a[j] * a[k]).sum())
+                .collect()
+        })
+        .collect();
+    let target: Vec<f64> = (0..rank)
+        .map(|j| weighted.iter().map(|a| a[j]).sum())
+        .collect();
+    solve(&columns, &target).unwrap_or_default()
+}
+
+fn solve(columns: &[Vec<f64>], target: &[f64]) -> Option<Vec<f64>> {
+    let n = columns.len();
+    let mut rows: Vec<Vec<f64>> = (0..target.len())
+        .map(|i| {
+            let mut row: Vec<f64> = columns.iter().map(|c| c[i]).collect();
+            row.push(target[i]);
+            row
+        })
+        .collect();
+    let mut pivots = vec![];
+    let mut r = 0;
+    for c in 0..n {
+        let Some(p) =
+            (r..rows.len()).max_by(|&a, &b| rows[a][c].abs().total_cmp(&rows[b][c].abs()))
+        else {
+            break;
+        };
+        if rows[p][c].abs() < 1e-9 {
+            continue;
+        }
+        rows.swap(r, p);
+        let pivot = rows[r].clone();
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i != r {
+                let factor = row[c] / pivot[c];
+                for (x, p) in row.iter_mut().zip(pivot.iter()).skip(c) {
+                    *x -= factor * p;
+                }
+            }
+        }
+        pivots.push(c);
+        r += 1;
+    }
+    if pivots.len() < n || rows[r..].iter().any(|row| row[n].abs() > 1e-9) {
+        return None;
+    }
+    Some((0..n).map(|k| rows[k][n] / rows[k][pivots[k]]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reference::SYNTONIC_COMMA;
+    use crate::report::schema;
+
+    #[test]
+    fn parse_subgroups() {
+        let subgroup = Subgroup::parse("2.3.7").unwrap();
+
+        assert_eq!(
+            subgroup.basis,
+            vec![Ratio::new(2, 1), Ratio::new(3, 1), Ratio::new(7, 1)]
+        );
+        assert_eq!(Subgroup::parse("2.9.13/5").unwrap().to_string(), "2.9.13/5");
+        assert!(Subgroup::parse("2..5").is_err());
+        assert_eq!(Subgroup::prime_limit(7).to_string(), "2.3.5.7");
+    }
+
+    #[test]
+    fn subgroup_monzos() {
+        let subgroup = Subgroup::parse("2.3.7").unwrap();
+
+        assert_eq!(subgroup.monzo(Ratio::new(7, 6)), Some(vec![-1, -1, 1]));
+        assert_eq!(subgroup.monzo(Ratio::new(5, 4)), None);
+        assert_eq!(subgroup.ratio(&[6, -2, -1]), Ratio::new(64, 63));
+
+        let nonprime = Subgroup::parse("2.9.7").unwrap();
+        assert_eq!(nonprime.monzo(Ratio::new(9, 8)), Some(vec![-3, 1, 0]));
+        assert!(!nonprime.contains(Ratio::new(3, 2)));
+    }
+
+    #[test]
+    fn subgroup_patent_vals() {
+        assert_eq!(
+            Subgroup::parse("2.3.7").unwrap().patent_val(22).steps,
+            vec![22, 35, 62]
+        );
+        assert_eq!(
+            Subgroup::parse("2.5.11").unwrap().patent_val(12).steps,
+            vec![12, 28, 42]
+        );
+    }
+
+    #[test]
+    fn subgroup_temperament() {
+        let subgroup = Subgroup::parse("2.3.7").unwrap();
+        let archytas = Temperament::new(subgroup.clone(), vec![Ratio::new(64, 63)]).unwrap();
+        let edos = archytas.patent_edos(30);
+
+        assert!(edos.contains(&5) && edos.contains(&17) && edos.contains(&22));
+        assert!(!edos.contains(&19));
+        assert_eq!(
+            Temperament::new(subgroup, vec![SYNTONIC_COMMA]),
+            Err(TemperamentError::NotInSubgroup(SYNTONIC_COMMA))
+        );
+    }
+
+    #[test]
+    fn temperament_report_lists_errors() {
+        let meantone = Temperament::new(Subgroup::prime_limit(5), vec![SYNTONIC_COMMA]).unwrap();
+
+        let json = meantone.to_json();
+        assert!(json.contains("{\"subgroup\":\"2.3.5\",\"rank\":2}"));
+        assert!(json.contains(&format!(
+            "{{\"comma\":\"81/80\",\"monzo\":\"[-4 4 -1⟩\",\"cents\":{}}}",
+            SYNTONIC_COMMA.cents()
+        )));
+        assert!(json.contains("{\"basis\":\"2/1\",\"just\":1200,"));
+        assert_eq!(meantone.tables()[3].rows.len(), 3);
+    }
+
+    #[test]
+    fn meantone_normal_form() {
+        let meantone = Temperament::new(Subgroup::prime_limit(5), vec![SYNTONIC_COMMA]).unwrap();
+
+        assert_eq!(meantone.normal_form(), vec![vec![1, 0, -4], vec![0, 1, 4]]);
+        assert_eq!(meantone.rank(), 2);
+    }
+
+    #[test]
+    fn equivalent_comma_lists_compare_equal() {
+        let septimal = |comma| {
+            Temperament::new(Subgroup::prime_limit(7), vec![SYNTONIC_COMMA, comma]).unwrap()
+        };
+        let a = septimal(Ratio::new(126, 125));
+        let b = septimal(Ratio::new(225, 224));
+
+        assert_eq!(a, b);
+        assert_eq!(
+            a.normal_form(),
+            vec![vec![1, 0, -4, -13], vec![0, 1, 4, 10]]
+        );
+        assert_ne!(a, septimal(Ratio::new(64, 63)));
+    }
+
+    #[test]
+    fn mingen_form() {
+        let meantone = Temperament::new(
+            Subgroup::prime_limit(7),
+            vec![SYNTONIC_COMMA, Ratio::new(126, 125)],
+        )
+        .unwrap();
+        let (mapping, generators) = meantone.mingen();
+
+        assert_eq!(mapping, vec![vec![1, 2, 4, 7], vec![0, -1, -4, -10]]);
+        assert!((generators[0] - 1200.).abs() < 3.);
+        assert!((generators[1] - 503.).abs() < 2.);
+    }
+
+    #[test]
+    fn subgroup_normal_form() {
+        let subgroup = Subgroup::parse("2.3.7").unwrap();
+        let archytas = Temperament::new(subgroup, vec![Ratio::new(64, 63)]).unwrap();
+
+        assert_eq!(archytas.normal_form(), vec![vec![1, 0, 6], vec![0, 1, -2]]);
+    }
+
+    #[test]
+    fn tempering_schedule_to_meantone() {
+        let just = Scale::new(vec![
+            Ratio::new(1, 1),
+            Ratio::new(9, 8),
+            Ratio::new(5, 4),
+            Ratio::new(4, 3),
+            Ratio::new(3, 2),
+            Ratio::new(5, 3),
+            Ratio::new(15, 8),
+        ]);
+        let schedule = tempering_schedule(&just, SYNTONIC_COMMA, 4).unwrap();
+
+        assert_eq!(schedule.len(), 5);
+        assert_eq!(schedule[0], just);
+        let fifth = |s: &Scale| s.cents()[4];
+        assert!((fifth(&schedule[4]) - 696.239).abs() < 1e-2);
+        assert!((fifth(&schedule[2]) - (701.955 + 696.239) / 2.).abs() < 1e-2);
+        assert!(fifth(&schedule[1]) > fifth(&schedule[2]));
+        assert!((schedule[4].cents()[2] - 384.956).abs() < 1e-2);
+    }
+
+    #[test]
+    fn tempered_scales_need_ratios() {
+        let tempered = Scale::new(vec![0., 700.]);
+
+        assert!(tempering_schedule(&tempered, SYNTONIC_COMMA, 3).is_none());
+    }
+
+    #[test]
+    fn temperament_schema() {
+        let meantone = Temperament::new(Subgroup::prime_limit(5), vec![SYNTONIC_COMMA]).unwrap();
+
+        assert_eq!(
+            schema(&meantone),
+            [
+                "Temperament: subgroup, rank",
+                "Commas: comma, monzo, cents",
+                "Generators: index, mapping, cents",
+                "Tuning errors: basis, just, tempered, error",
+            ]
+        );
+    }
+}
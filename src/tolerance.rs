@@ -0,0 +1,91 @@
+use crate::degree::CENTS_EPSILON;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    #[default]
+    Nearest,
+    NearestEven,
+    Floor,
+    Ceil,
+    TowardZero,
+}
+
+impl Rounding {
+    pub fn apply(&self, value: f64) -> f64 {
+        match self {
+            Rounding::Nearest => value.round(),
+            Rounding::NearestEven => value.round_ties_even(),
+            Rounding::Floor => value.floor(),
+            Rounding::Ceil => value.ceil(),
+            Rounding::TowardZero => value.trunc(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Tolerance {
+    pub cents: f64,
+    pub rounding: Rounding,
+}
+
+impl Tolerance {
+    pub fn new(cents: f64) -> Self {
+        Self {
+            cents,
+            rounding: Rounding::Nearest,
+        }
+    }
+
+    pub fn exact() -> Self {
+        Self::new(0.)
+    }
+
+    pub fn with_rounding(self, rounding: Rounding) -> Self {
+        Self { rounding, ..self }
+    }
+
+    pub fn accepts(&self, deviation: f64) -> bool {
+        deviation.abs() <= self.cents + CENTS_EPSILON
+    }
+
+    pub fn equal(&self, a: f64, b: f64) -> bool {
+        self.accepts(a - b)
+    }
+
+    pub fn round(&self, value: f64) -> f64 {
+        self.rounding.apply(value)
+    }
+}
+
+impl From<f64> for Tolerance {
+    fn from(value: f64) -> Self {
+        Tolerance::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tolerance_accepts_within_epsilon() {
+        let tolerance = Tolerance::new(5.);
+
+        assert!(tolerance.accepts(-5.));
+        assert!(!tolerance.accepts(5.01));
+        assert!(tolerance.equal(701.955, 700.));
+        assert!(Tolerance::exact().equal(386.3137, 386.3137 + 1e-9));
+        assert_eq!(Tolerance::from(3.), Tolerance::new(3.));
+    }
+
+    #[test]
+    fn rounding_modes() {
+        let round = |r: Rounding, v: f64| Tolerance::exact().with_rounding(r).round(v);
+
+        assert_eq!(round(Rounding::Nearest, 2.5), 3.);
+        assert_eq!(round(Rounding::NearestEven, 2.5), 2.);
+        assert_eq!(round(Rounding::Floor, -2.5), -3.);
+        assert_eq!(round(Rounding::Ceil, 2.1), 3.);
+        assert_eq!(round(Rounding::TowardZero, -2.9), -2.);
+    }
+}
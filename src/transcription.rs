@@ -0,0 +1,247 @@
+use crate::lattice::Lattice;
+use crate::metadata::Metadata;
+use crate::ratio::Ratio;
+use crate::report::{Report, Table, Value};
+use crate::tolerance::Tolerance;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TranscribedNote {
+    pub frequency: f64,
+    pub cents: f64,
+    pub point: Option<(Vec<i32>, Ratio)>,
+    pub equave: i32,
+    pub residual: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transcription {
+    pub reference: f64,
+    pub tolerance: Tolerance,
+    pub notes: Vec<TranscribedNote>,
+    pub metadata: Metadata,
+}
+
+impl Transcription {
+    pub fn assigned(&self) -> Vec<&TranscribedNote> {
+        self.notes.iter().filter(|n| n.point.is_some()).collect()
+    }
+
+    pub fn unassigned(&self) -> Vec<&TranscribedNote> {
+        self.notes.iter().filter(|n| n.point.is_none()).collect()
+    }
+
+    pub fn rms_residual(&self) -> f64 {
+        let assigned = self.assigned();
+        match assigned.len() {
+            0 => 0.,
+            n => (assigned.iter().map(|n| n.residual.powi(2)).sum::<f64>() / n as f64).sqrt(),
+        }
+    }
+}
+
+pub fn transcribe(
+    lattice: &Lattice,
+    frequencies: &[f64],
+    reference: impl Into<f64>,
+    radius: i32,
+    tolerance: impl Into<Tolerance>,
+) -> Transcription {
+    let reference = reference.into();
+    let tolerance = tolerance.into();
+    let equivalence = lattice.equivalence;
+    let points: Vec<(Vec<i32>, Ratio)> = lattice
+        .points(radius)
+        .into_iter()
+        .map(|(c, r)| (c, r.normalize_with(equivalence)))
+        .collect();
+
+    let notes = frequencies
+        .iter()
+        .map(|&frequency| {
+            let cents = 1200. * (frequency / reference).log2();
+            let reduced = equivalence.reduce_cents(cents);
+            let measurable = frequency.is_finite() && frequency > 0.;
+            let nearest = points
+                .iter()
+                .filter(|_| measurable)
+                .map(|(c, r)| {
+                    let offset = reduced - r.cents();
+                    let wrapped = match equivalence.equave_cents() {
+                        Some(e) => (offset + e / 2.).rem_euclid(e) - e / 2.,
+                        None => offset,
+                    };
+                    (c, r, wrapped)
+                })
+                .min_by(|a, b| a.2.abs().total_cmp(&b.2.abs()));
+
+            match nearest {
+                Some((c, r, residual)) if tolerance.accepts(residual) => TranscribedNote {
+                    frequency,
+                    cents,
+                    point: Some((c.clone(), *r)),
+                    equave: match equivalence.equave_cents() {
+                        Some(e) => ((cents - residual - r.cents()) / e).round() as i32,
+                        None => 0,
+                    },
+                    residual,
+                },
+                _ => TranscribedNote {
+                    frequency,
+                    cents,
+                    point: None,
+                    equave: 0,
+                    residual: 0.,
+                },
+            }
+        })
+        .collect();
+
+    Transcription {
+        reference,
+        tolerance,
+        notes,
+        metadata: lattice.metadata.clone(),
+    }
+}
+
+impl Report for Transcription {
+    fn tables(&self) -> Vec<Table> {
+        let mut table = Table::new(
+            &format!("Transcription against {} Hz", self.reference),
+            &[
+                "frequency",
+                "cents",
+                "ratio",
+                "equave",
+                "coordinates",
+                "residual",
+            ],
+        );
+        for note in &self.notes {
+            let (ratio, coordinates, residual) = match &note.point {
+                Some((c, r)) => (
+                    r.to_string().into(),
+                    format!(
+                        "[{}]",
+                        c.iter()
+                            .map(|i| i.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                    .into(),
+                    note.residual.into(),
+                ),
+                None => (Value::Null, Value::Null, Value::Null),
+            };
+            table.push(vec![
+                note.frequency.into(),
+                note.cents.into(),
+                ratio,
+                note.equave.into(),
+                coordinates,
+                residual,
+            ]);
+        }
+        vec![table]
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        Some(&self.metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lattice::{DimensionBound, LatticeDimension};
+    use crate::report::schema;
+    use crate::tolerance::Rounding;
+
+    fn five_limit() -> Lattice {
+        Lattice::new(vec![
+            LatticeDimension {
+                ratio: Ratio::new(3, 2),
+                bounds: DimensionBound::Infinity,
+            },
+            LatticeDimension {
+                ratio: Ratio::new(5, 4),
+                bounds: DimensionBound::Infinity,
+            },
+        ])
+    }
+
+    #[test]
+    fn transcribe_measured_frequencies() {
+        let frequencies = [200.5, 251., 299., 449., 1000.];
+
+        let transcription = transcribe(&five_limit(), &frequencies, 200., 2, 10.);
+
+        let ratios: Vec<Option<Ratio>> = transcription
+            .notes
+            .iter()
+            .map(|n| n.point.as_ref().map(|p| p.1))
+            .collect();
+        assert_eq!(
+            ratios,
+            vec![
+                Some(Ratio::new(1, 1)),
+                Some(Ratio::new(5, 4)),
+                Some(Ratio::new(3, 2)),
+                Some(Ratio::new(9, 8)),
+                Some(Ratio::new(5, 4)),
+            ]
+        );
+        assert_eq!(transcription.notes[3].equave, 1);
+        assert_eq!(transcription.notes[4].equave, 2);
+        assert!((transcription.notes[0].residual - 4.322).abs() < 1e-3);
+        assert!(transcription.unassigned().is_empty());
+        assert!(transcription.rms_residual() < 10.);
+    }
+
+    #[test]
+    fn equave_ignores_rounding_mode() {
+        let tolerance = Tolerance::new(10.).with_rounding(Rounding::Floor);
+
+        let transcription = transcribe(&five_limit(), &[199., 201.], 200., 1, tolerance);
+
+        assert_eq!(transcription.notes[0].equave, 0);
+        assert_eq!(transcription.notes[1].equave, 0);
+    }
+
+    #[test]
+    fn frequencies_outside_tolerance_are_unassigned() {
+        let transcription = transcribe(&five_limit(), &[200. * 7. / 4.], 200., 1, 10.);
+
+        assert_eq!(transcription.unassigned().len(), 1);
+        assert_eq!(transcription.rms_residual(), 0.);
+    }
+
+    #[test]
+    fn unmeasurable_frequencies_are_unassigned() {
+        let transcription = transcribe(&five_limit(), &[0., -200., f64::NAN], 200., 1, 10.);
+
+        assert!(transcription.notes.iter().all(|n| n.point.is_none()));
+    }
+
+    #[test]
+    fn transcription_report() {
+        let transcription = transcribe(&five_limit(), &[250., 350.], 200., 1, 5.);
+        let rendered = transcription.render();
+
+        assert!(rendered.starts_with("Transcription against 200 Hz\n"));
+        assert!(rendered.contains("250.000    386.314  5/4    0       [0, 1]       0.000\n"));
+        assert!(rendered.contains("350.000    968.826  -      0       -            -\n"));
+        assert!(transcription.to_json().contains(&format!(
+            "{{\"frequency\":350,\"cents\":{},\"ratio\":null,\"equave\":0,\"coordinates\":null,\"residual\":null}}",
+            transcription.notes[1].cents
+        )));
+    }
+
+    #[test]
+    fn transcription_schema() {
+        assert_eq!(
+            schema(&transcribe(&five_limit(), &[250.], 200., 1, 5.)),
+            ["Transcription against 200 Hz: frequency, cents, ratio, equave, coordinates, residual"]
+        );
+    }
+}
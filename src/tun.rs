@@ -0,0 +1,237 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::diagnostics::{decimal_comma, to_diagnostics, Diagnostics, Problem, Span};
+use crate::tuning_table::{TuningTable, MIDI_NOTES, MIDI_NOTE_ZERO};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TunError {
+    InvalidLine(String),
+    InvalidNote(String),
+    InvalidValue(String),
+}
+
+impl fmt::Display for TunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TunError::InvalidLine(s) => write!(f, "invalid line: {s}"),
+            TunError::InvalidNote(s) => write!(f, "invalid note: {s}"),
+            TunError::InvalidValue(s) => write!(f, "invalid value: {s}"),
+        }
+    }
+}
+
+impl Error for TunError {}
+
+pub fn parse_tun(input: &str) -> Result<TuningTable, TunError> {
+    let (table, problems) = parse_recovering(input);
+    match problems.into_iter().next() {
+        Some(problem) => Err(problem.error),
+        None => Ok(table),
+    }
+}
+
+pub fn parse_tun_with_diagnostics(input: &str) -> (TuningTable, Diagnostics) {
+    let (table, problems) = parse_recovering(input);
+    (table, to_diagnostics(problems))
+}
+
+fn parse_recovering(input: &str) -> (TuningTable, Vec<Problem<TunError>>) {
+    let mut tuning: Vec<f64> = (0..MIDI_NOTES).map(|n| n as f64 * 100.).collect();
+    let mut exact: Option<Vec<f64>> = None;
+    let mut base_frequency = MIDI_NOTE_ZERO;
+    let mut section = String::new();
+    let mut problems = vec![];
+
+    for (number, raw) in input.lines().enumerate().map(|(i, l)| (i + 1, l)) {
+        let line = raw.split(';').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            section = line[1..line.len() - 1].trim().to_lowercase();
+            if section == "exact tuning" && exact.is_none() {
+                exact = Some(tuning.clone());
+            }
+            continue;
+        }
+        if section != "tuning" && section != "exact tuning" {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            let error = TunError::InvalidLine(line.to_string());
+            problems.push(Problem::error(Span::of(number, raw, line), error));
+            continue;
+        };
+        let value_offset = raw.len() - raw.trim_start().len() + key.len() + 1;
+        let value_offset = value_offset + value.len() - value.trim_start().len();
+        let value_span = Span::at(number, value_offset, value.trim());
+        let value: f64 = match value.trim().parse() {
+            Ok(v) => v,
+            Err(_) => {
+                let error = TunError::InvalidValue(line.to_string());
+                match decimal_comma(value.trim()).and_then(|v| v.parse().ok()) {
+                    Some(v) => {
+                        let message = format!("decimal comma in {}, read as {v}", value.trim());
+                        problems.push(Problem::warning(value_span, error, message));
+                        v
+                    }
+                    None => {
+                        problems.push(Problem::error(value_span, error));
+                        continue;
+                    }
+                }
+            }
+        };
+
+        let key = key.trim().to_lowercase();
+        if key == "basefreq" {
+            base_frequency = value;
+            continue;
+        }
+        let Some(note) = key
+            .strip_prefix("note")
+            .and_then(|n| n.trim().parse::<usize>().ok())
+            .filter(|&n| n < MIDI_NOTES)
+        else {
+            let error = TunError::InvalidNote(line.to_string());
+            problems.push(Problem::error(Span::of(number, raw, line), error));
+            continue;
+        };
+        match (section.as_str(), exact.as_mut()) {
+            ("exact tuning", Some(cents)) => cents[note] = value,
+            _ => tuning[note] = value,
+        }
+    }
+
+    let (cents, base) = match exact {
+        Some(cents) => (cents, base_frequency),
+        None => (tuning, MIDI_NOTE_ZERO),
+    };
+    let table = TuningTable::new(cents.iter().map(|c| base * 2_f64.powf(c / 1200.)).collect());
+    (table, problems)
+}
+
+pub fn to_tun(table: &TuningTable) -> String {
+    let cents: Vec<f64> = (0..MIDI_NOTES as u8).map(|n| table.cents(n)).collect();
+
+    let mut out = String::from("; generated by partch\n[Tuning]\n");
+    for (note, c) in cents.iter().enumerate() {
+        out.push_str(&format!("note {note}={}\n", c.round() as i64));
+    }
+    out.push_str(&format!("\n[Exact Tuning]\nBaseFreq={MIDI_NOTE_ZERO}\n"));
+    for (note, c) in cents.iter().enumerate() {
+        out.push_str(&format!("note {note}={c:.6}\n"));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ratio::Ratio;
+    use crate::scale::Scale;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn empty_tun_is_equal_temperament() {
+        let table = parse_tun("[Tuning]\n").unwrap();
+
+        assert!(approx(table.frequency(69), 440.));
+    }
+
+    #[test]
+    fn parse_tuning_section() {
+        let table =
+            parse_tun("; comment\n[Tuning]\nnote 60=6000\nnote 61 = 6112 ; raised\n").unwrap();
+
+        assert!(approx(table.cents(60), 6000.));
+        assert!(approx(table.cents(61), 6112.));
+        assert!(approx(table.cents(62), 6200.));
+    }
+
+    #[test]
+    fn exact_tuning_overrides_tuning() {
+        let input = "[Tuning]\nnote 60=6000\n[Exact Tuning]\nBaseFreq=10\nnote 60=1200.5\n";
+
+        let table = parse_tun(input).unwrap();
+
+        assert!(approx(
+            table.frequency(60),
+            10. * 2_f64.powf(1200.5 / 1200.)
+        ));
+    }
+
+    #[test]
+    fn ignores_other_sections() {
+        let table = parse_tun("[Scale Begin]\nFormat=AnaMark-TUN\n[Tuning]\n").unwrap();
+
+        assert!(approx(table.cents(1), 100.));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            parse_tun("[Tuning]\nnote 60\n"),
+            Err(TunError::InvalidLine("note 60".to_string()))
+        );
+        assert_eq!(
+            parse_tun("[Tuning]\nnote 200=0\n"),
+            Err(TunError::InvalidNote("note 200=0".to_string()))
+        );
+        assert_eq!(
+            parse_tun("[Tuning]\nnote 1=abc\n"),
+            Err(TunError::InvalidValue("note 1=abc".to_string()))
+        );
+    }
+
+    #[test]
+    fn tun_round_trip() {
+        let scale = Scale::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+        let table = TuningTable::from_scale(&scale, 60, 261.63).unwrap();
+
+        let parsed = parse_tun(&to_tun(&table)).unwrap();
+
+        for note in 0..MIDI_NOTES as u8 {
+            assert!(approx(parsed.cents(note), table.cents(note)));
+        }
+        for (c, e) in parsed
+            .to_scale(60, 3)
+            .unwrap()
+            .cents()
+            .iter()
+            .zip(scale.cents())
+        {
+            assert!((c - e).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn diagnostics_recover_bad_lines() {
+        let input = "[Tuning]\nnote 60 = 6050,5\nnote 61 = abc\nnote 200 = 100\nnote 62 = 6210\n";
+        let (table, diagnostics) = parse_tun_with_diagnostics(input);
+
+        assert!(approx(table.cents(60), 6050.5));
+        assert!(approx(table.cents(62), 6210.));
+        assert_eq!(
+            diagnostics
+                .items
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "2:11: warning: decimal comma in 6050,5, read as 6050.5",
+                "3:11: error: invalid value: note 61 = abc",
+                "4:1: error: invalid note: note 200 = 100",
+            ]
+        );
+
+        let (_, diagnostics) = parse_tun_with_diagnostics("[Tuning]\n  note 1 = e\n");
+        assert_eq!(diagnostics.items[0].span, Span::new(2, 12, 1));
+        assert!(parse_tun(input).is_err());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::keyboard::KeyboardMapping;
+use crate::scale::Scale;
+use crate::standard::PitchStandard;
+
+pub const MIDI_NOTES: usize = 128;
+pub const MIDI_NOTE_ZERO: f64 = 8.175798915643707;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TuningTable {
+    pub frequencies: Vec<f64>,
+}
+
+impl TuningTable {
+    pub fn new(frequencies: Vec<f64>) -> Self {
+        Self { frequencies }
+    }
+
+    pub fn equal_temperament(standard: PitchStandard) -> Self {
+        Self::new(
+            (0..MIDI_NOTES)
+                .map(|n| standard.midi_frequency(n as u8))
+                .collect(),
+        )
+    }
+
+    pub fn from_scale(
+        scale: &Scale,
+        root_note: u8,
+        root_frequency: impl Into<f64>,
+    ) -> Option<Self> {
+        Self::from_mapping(scale, &KeyboardMapping::linear(root_note, root_frequency))
+    }
+
+    pub fn from_mapping(scale: &Scale, mapping: &KeyboardMapping) -> Option<Self> {
+        if scale.is_empty() {
+            return None;
+        }
+        let size = scale.len() as i32;
+        let period = scale.equivalence.period().map(|p| p.multiplier());
+        let mut frequencies: Vec<Option<f64>> = (0..MIDI_NOTES)
+            .map(|n| {
+                let step = mapping.step(n as u8)?;
+                let (degree, multiplier) = match period {
+                    Some(period) => (
+                        scale.degrees[step.rem_euclid(size) as usize],
+                        period.powi(step.div_euclid(size)),
+                    ),
+                    None => (*scale.degrees.get(usize::try_from(step).ok()?)?, 1.),
+                };
+                Some(mapping.root_frequency * degree.multiplier() * multiplier)
+            })
+            .collect();
+        let mut last = frequencies.iter().flatten().next().copied();
+        for frequency in frequencies.iter_mut() {
+            match frequency {
+                Some(f) => last = Some(*f),
+                None => *frequency = last,
+            }
+        }
+        Some(Self::new(
+            frequencies
+                .into_iter()
+                .map(|f| f.unwrap_or(mapping.root_frequency))
+                .collect(),
+        ))
+    }
+
+    pub fn frequency(&self, note: u8) -> f64 {
+        self.frequencies[note as usize]
+    }
+
+    pub fn cents(&self, note: u8) -> f64 {
+        1200. * (self.frequency(note) / MIDI_NOTE_ZERO).log2()
+    }
+
+    pub fn to_scale(&self, root_note: u8, size: usize) -> Option<Scale> {
+        let notes = root_note as usize..root_note as usize + size;
+        let root = *self.frequencies.get(root_note as usize)?;
+        let frequencies = self.frequencies.get(notes)?;
+        Some(Scale::new(
+            frequencies
+                .iter()
+                .map(|f| 1200. * (f / root).log2())
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::equivalence::Equivalence;
+    use crate::ratio::Ratio;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    #[test]
+    fn equal_temperament() {
+        let table = TuningTable::equal_temperament(PitchStandard::A440);
+
+        assert_eq!(table.frequencies.len(), MIDI_NOTES);
+        assert!(approx(table.frequency(69), 440.));
+        assert!(approx(table.frequency(57), 220.));
+        assert!(approx(table.frequency(0), MIDI_NOTE_ZERO));
+        assert!(approx(table.cents(60), 6000.));
+    }
+
+    #[test]
+    fn from_scale_repeats_each_octave() {
+        let scale = Scale::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+
+        let table = TuningTable::from_scale(&scale, 60, 261.).unwrap();
+
+        assert!(approx(table.frequency(60), 261.));
+        assert!(approx(table.frequency(61), 326.25));
+        assert!(approx(table.frequency(62), 391.5));
+        assert!(approx(table.frequency(63), 522.));
+        assert!(approx(table.frequency(59), 195.75));
+    }
+
+    #[test]
+    fn equal_temperament_at_other_standards() {
+        let table = TuningTable::equal_temperament(PitchStandard::BAROQUE_A415);
+
+        assert!(approx(table.frequency(69), 415.));
+        assert!(approx(table.frequency(81), 830.));
+    }
+
+    #[test]
+    fn from_scale_at_partch_standard() {
+        let scale = Scale::new(vec![Ratio::new(1, 1), Ratio::new(3, 2)]);
+
+        let table = TuningTable::from_scale(&scale, 67, PitchStandard::PARTCH_G392).unwrap();
+
+        assert!(approx(table.frequency(67), 392.));
+        assert!(approx(table.frequency(68), 588.));
+    }
+
+    #[test]
+    fn to_scale_recovers_cents() {
+        let scale = Scale::new(vec![Ratio::new(1, 1), Ratio::new(5, 4), Ratio::new(3, 2)]);
+        let table = TuningTable::from_scale(&scale, 60, 261.).unwrap();
+
+        let recovered = table.to_scale(60, 3).unwrap();
+
+        assert!(!recovered.is_exact());
+        assert_eq!(table.to_scale(120, 12), None);
+        for (c, e) in recovered.cents().iter().zip(scale.cents().iter()) {
+            assert!(approx(*c, *e));
+        }
+    }
+
+    #[test]
+    fn from_empty_scale() {
+        let scale = Scale::new(Vec::<Ratio>::new());
+
+        assert_eq!(TuningTable::from_scale(&scale, 60, 261.), None);
+    }
+
+    #[test]
+    fn from_tritave_scale() {
+        let scale = Scale::with_equivalence(
+            vec![Ratio::new(1, 1), Ratio::new(5, 3)],
+            Equivalence::Tritave,
+        );
+
+        let table = TuningTable::from_scale(&scale, 60, 100.).unwrap();
+
+        assert!(approx(table.frequency(62), 300.));
+        assert!(approx(table.frequency(59), 500. / 9.));
+    }
+
+    #[test]
+    fn from_scale_with_other_periods() {
+        let fifths = Scale::with_equivalence(
+            vec![Ratio::new(1, 1), Ratio::new(5, 4)],
+            Equivalence::Period(Ratio::new(3, 2)),
+        );
+        let table = TuningTable::from_scale(&fifths, 60, 100.).unwrap();
+
+        assert!(approx(table.frequency(62), 150.));
+        assert!(approx(table.frequency(63), 187.5));
+
+        let harmonics = Scale::with_equivalence(
+            vec![Ratio::new(1, 1), Ratio::new(2, 1), Ratio::new(3, 1)],
+            Equivalence::None,
+        );
+        let table = TuningTable::from_scale(&harmonics, 60, 100.).unwrap();
+
+        assert!(approx(table.frequency(62), 300.));
+        assert!(approx(table.frequency(63), 300.));
+        assert!(approx(table.frequency(59), 100.));
+    }
+
+    #[test]
+    fn from_tempered_scale() {
+        let scale = Scale::new(vec![0., 400., 700.]);
+
+        let table = TuningTable::from_scale(&scale, 60, 261.).unwrap();
+
+        assert!(approx(table.frequency(61), 261. * 2_f64.powf(1. / 3.)));
+        assert!(approx(table.frequency(63), 522.));
+    }
+
+    #[test]
+    fn from_white_key_mapping() {
+        let scale = Scale::new(vec![
+            Ratio::new(1, 1),
+            Ratio::new(9, 8),
+            Ratio::new(5, 4),
+            Ratio::new(4, 3),
+            Ratio::new(3, 2),
+            Ratio::new(5, 3),
+            Ratio::new(15, 8),
+        ]);
+
+        let table =
+            TuningTable::from_mapping(&scale, &KeyboardMapping::white_keys(60, 264.)).unwrap();
+
+        assert!(approx(table.frequency(62), 297.));
+        assert!(approx(table.frequency(61), 264.));
+        assert!(approx(table.frequency(67), 396.));
+        assert!(approx(table.frequency(72), 528.));
+        assert!(approx(table.frequency(59), 247.5));
+    }
+}
@@ -0,0 +1,235 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+
+use crate::lattice::Lattice;
+use crate::ratio::Ratio;
+use crate::rng::Rng;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepCosts {
+    pub costs: Vec<f64>,
+}
+
+impl StepCosts {
+    pub fn new(costs: Vec<f64>) -> Self {
+        Self { costs }
+    }
+
+    pub fn tenney(lattice: &Lattice) -> Self {
+        Self::new(
+            lattice
+                .dimensions
+                .iter()
+                .map(|d| d.ratio.tenney_height())
+                .collect(),
+        )
+    }
+
+    pub fn uniform(lattice: &Lattice) -> Self {
+        Self::new(vec![1.; lattice.dimensions.len()])
+    }
+
+    pub fn cost(&self, dimension: usize) -> f64 {
+        self.costs.get(dimension).copied().unwrap_or(1.)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LatticePath {
+    pub coordinates: Vec<Vec<i32>>,
+    pub cost: f64,
+}
+
+fn neighbours(lattice: &Lattice, point: &[i32], radius: i32) -> Vec<(usize, Vec<i32>)> {
+    let mut neighbours = vec![];
+    for (k, dim) in lattice.dimensions.iter().enumerate() {
+        let range = dim.range(radius);
+        for delta in [-1, 1] {
+            let index = point[k] + delta;
+            if range.contains(&index) {
+                let mut next = point.to_vec();
+                next[k] = index;
+                neighbours.push((k, next));
+            }
+        }
+    }
+    neighbours
+}
+
+pub fn shortest_path(
+    lattice: &Lattice,
+    from: Vec<i32>,
+    target: Ratio,
+    radius: i32,
+    costs: &StepCosts,
+) -> Option<LatticePath> {
+    let equivalence = lattice.equivalence;
+    let target = target.normalize_with(equivalence);
+    let mut best: BTreeMap<Vec<i32>, (f64, Option<Vec<i32>>)> = BTreeMap::new();
+    let mut queue = BinaryHeap::new();
+    best.insert(from.clone(), (0., None));
+    queue.push(Reverse((0_f64.to_bits(), from)));
+
+    while let Some(Reverse((bits, point))) = queue.pop() {
+        let cost = f64::from_bits(bits);
+        if cost > best[&point].0 {
+            continue;
+        }
+        if lattice.at(point.clone()).normalize_with(equivalence) == target {
+            let mut coordinates = vec![point.clone()];
+            while let Some(previous) = best[coordinates.last()?].1.clone() {
+                coordinates.push(previous);
+            }
+            coordinates.reverse();
+            return Some(LatticePath { coordinates, cost });
+        }
+        for (k, next) in neighbours(lattice, &point, radius) {
+            let next_cost = cost + costs.cost(k);
+            if best.get(&next).is_none_or(|(c, _)| next_cost < *c) {
+                best.insert(next.clone(), (next_cost, Some(point.clone())));
+                queue.push(Reverse((next_cost.to_bits(), next)));
+            }
+        }
+    }
+    None
+}
+
+pub fn random_walk(
+    lattice: &Lattice,
+    start: Vec<i32>,
+    steps: usize,
+    radius: i32,
+    costs: &StepCosts,
+    rng: &mut Rng,
+) -> Vec<Vec<i32>> {
+    let mut walk = vec![start];
+    for _ in 0..steps {
+        let options = neighbours(lattice, walk.last().unwrap(), radius);
+        let weights: Vec<f64> = options
+            .iter()
+            .map(|(k, _)| 2_f64.powf(-costs.cost(*k)))
+            .collect();
+        let total: f64 = weights.iter().sum();
+        if options.is_empty() || total <= 0. {
+            break;
+        }
+        let mut choice = rng.next_f64() * total;
+        let mut index = options.len() - 1;
+        for (i, w) in weights.iter().enumerate() {
+            if choice < *w {
+                index = i;
+                break;
+            }
+            choice -= w;
+        }
+        walk.push(options[index].1.clone());
+    }
+    walk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lattice::{DimensionBound, LatticeDimension};
+
+    fn eleven_limit() -> Lattice {
+        Lattice::new(
+            [(3, 2), (5, 4), (7, 4), (11, 8)]
+                .iter()
+                .map(|&(n, d)| LatticeDimension {
+                    ratio: Ratio::new(n, d),
+                    bounds: DimensionBound::Infinity,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn tenney_costs() {
+        let costs = StepCosts::tenney(&eleven_limit());
+
+        assert!((costs.cost(0) - 6_f64.log2()).abs() < 1e-12);
+        assert!(costs.cost(3) > costs.cost(2));
+        assert_eq!(StepCosts::uniform(&eleven_limit()).costs, vec![1.; 4]);
+    }
+
+    #[test]
+    fn shortest_path_to_ratio() {
+        let lattice = eleven_limit();
+
+        let path = shortest_path(
+            &lattice,
+            vec![0; 4],
+            Ratio::new(15, 8),
+            2,
+            &StepCosts::tenney(&lattice),
+        )
+        .unwrap();
+
+        assert_eq!(path.coordinates.first(), Some(&vec![0; 4]));
+        assert_eq!(path.coordinates.last(), Some(&vec![1, 1, 0, 0]));
+        assert_eq!(path.coordinates.len(), 3);
+        assert!((path.cost - (6_f64.log2() + 20_f64.log2())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn path_back_to_origin_and_unreachable_targets() {
+        let lattice = eleven_limit();
+        let target = Ratio::new(1, 1);
+        let from = vec![0, 0, 0, 1];
+
+        let path = shortest_path(
+            &lattice,
+            from.clone(),
+            target,
+            2,
+            &StepCosts::tenney(&lattice),
+        );
+        assert_eq!(path.unwrap().coordinates.last(), Some(&vec![0; 4]));
+
+        assert!(shortest_path(
+            &lattice,
+            from,
+            Ratio::new(13, 8),
+            2,
+            &StepCosts::uniform(&lattice)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn weighted_walk_prefers_simple_steps() {
+        let lattice = eleven_limit();
+        let mut rng = Rng::seeded(7);
+
+        let walk = random_walk(
+            &lattice,
+            vec![0; 4],
+            500,
+            3,
+            &StepCosts::tenney(&lattice),
+            &mut rng,
+        );
+
+        assert_eq!(walk.len(), 501);
+        let moves: Vec<usize> = walk
+            .windows(2)
+            .map(|w| (0..4).find(|&k| w[0][k] != w[1][k]).unwrap())
+            .collect();
+        let along = |k: usize| moves.iter().filter(|&&m| m == k).count();
+        assert!(along(0) > along(2));
+        assert!(along(1) > along(3));
+        assert!(walk.iter().all(|p| p.iter().all(|i| i.abs() <= 3)));
+    }
+
+    #[test]
+    fn walk_is_reproducible() {
+        let lattice = eleven_limit();
+        let costs = StepCosts::tenney(&lattice);
+
+        let a = random_walk(&lattice, vec![0; 4], 20, 2, &costs, &mut Rng::seeded(1));
+        let b = random_walk(&lattice, vec![0; 4], 20, 2, &costs, &mut Rng::seeded(1));
+
+        assert_eq!(a, b);
+    }
+}
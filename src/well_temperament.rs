@@ -0,0 +1,159 @@
+use crate::ratio::Ratio;
+use crate::reference::PYTHAGOREAN_COMMA;
+use crate::scale::Scale;
+
+pub const NOTE_NAMES: [&str; 12] = [
+    "C", "C#", "D", "Eb", "E", "F", "F#", "G", "G#", "A", "Bb", "B",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WellTemperament {
+    pub comma: Ratio,
+    pub fractions: Vec<f64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeyError {
+    pub root: usize,
+    pub fifth: f64,
+    pub major_third: f64,
+    pub minor_third: f64,
+}
+
+impl KeyError {
+    pub fn name(&self) -> &'static str {
+        NOTE_NAMES[self.root]
+    }
+}
+
+impl WellTemperament {
+    pub fn new(comma: Ratio, fractions: Vec<f64>) -> Self {
+        Self { comma, fractions }
+    }
+
+    pub fn pythagorean() -> Self {
+        Self::new(PYTHAGOREAN_COMMA, vec![])
+    }
+
+    pub fn equal() -> Self {
+        Self::new(PYTHAGOREAN_COMMA, vec![1. / 12.; 11])
+    }
+
+    pub fn werckmeister_iii() -> Self {
+        Self::new(PYTHAGOREAN_COMMA, vec![0.25, 0.25, 0.25, 0., 0., 0.25])
+    }
+
+    pub fn vallotti() -> Self {
+        let mut fractions = vec![1. / 6.; 5];
+        fractions.extend([0.; 6]);
+        Self::new(PYTHAGOREAN_COMMA, fractions)
+    }
+
+    pub fn fifths(&self) -> Vec<f64> {
+        let pure = Ratio::new(3, 2).cents();
+        let comma = self.comma.cents();
+        let mut fifths: Vec<f64> = (0..11)
+            .map(|i| pure - self.fractions.get(i).copied().unwrap_or(0.) * comma)
+            .collect();
+        fifths.push(8400. - fifths.iter().sum::<f64>());
+        fifths
+    }
+
+    pub fn cents(&self) -> Vec<f64> {
+        let mut cents = vec![0.; 12];
+        let mut position = 0.;
+        for (k, fifth) in self.fifths().iter().take(11).enumerate() {
+            position += fifth;
+            cents[7 * (k + 1) % 12] = position.rem_euclid(1200.);
+        }
+        cents
+    }
+
+    pub fn to_scale(&self) -> Scale {
+        Scale::new(self.cents())
+    }
+
+    pub fn key_errors(&self) -> Vec<KeyError> {
+        let cents = self.cents();
+        let interval = |root: usize, steps: usize| {
+            (cents[(root + steps) % 12] - cents[root]).rem_euclid(1200.)
+        };
+        (0..12)
+            .map(|root| KeyError {
+                root,
+                fifth: interval(root, 7) - Ratio::new(3, 2).cents(),
+                major_third: interval(root, 4) - Ratio::new(5, 4).cents(),
+                minor_third: interval(root, 3) - Ratio::new(6, 5).cents(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    #[test]
+    fn equal_temperament_closes_evenly() {
+        let t = WellTemperament::equal();
+
+        assert!(t.fifths().iter().all(|&f| approx(f, 700.)));
+        for (i, c) in t.cents().iter().enumerate() {
+            assert!(approx(*c, 100. * i as f64));
+        }
+    }
+
+    #[test]
+    fn pythagorean_wolf_fifth() {
+        let fifths = WellTemperament::pythagorean().fifths();
+
+        assert!(approx(fifths[0], 701.955000865));
+        assert!(approx(fifths[11], 678.494990485));
+    }
+
+    #[test]
+    fn werckmeister_iii_cents() {
+        let cents = WellTemperament::werckmeister_iii().cents();
+        let expected = [
+            0., 90.225, 192.18, 294.135, 390.225, 498.045, 588.27, 696.09, 792.18, 888.27, 996.09,
+            1092.18,
+        ];
+
+        for (c, e) in cents.iter().zip(expected.iter()) {
+            assert!((c - e).abs() < 1e-2, "{c} {e}");
+        }
+    }
+
+    #[test]
+    fn werckmeister_iii_closes_with_pure_fifth() {
+        let fifths = WellTemperament::werckmeister_iii().fifths();
+
+        assert!(approx(fifths[11], Ratio::new(3, 2).cents()));
+    }
+
+    #[test]
+    fn key_error_report() {
+        let errors = WellTemperament::werckmeister_iii().key_errors();
+
+        assert_eq!(errors.len(), 12);
+        assert_eq!(errors[0].name(), "C");
+        assert!((errors[0].major_third - 3.910).abs() < 1e-2);
+        assert!((errors[6].major_third - 21.506).abs() < 1e-2);
+        assert!(errors.iter().all(|e| e.fifth.abs() < 6.));
+    }
+
+    #[test]
+    fn to_scale_keeps_cents() {
+        let t = WellTemperament::vallotti();
+        let scale = t.to_scale();
+
+        assert_eq!(scale.len(), 12);
+        for (c, e) in scale.cents().iter().zip(t.cents().iter()) {
+            assert!(approx(*c, *e));
+        }
+    }
+}